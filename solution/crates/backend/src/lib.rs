@@ -1,16 +1,24 @@
-use actix_web::web::{get, Data, JsonConfig, PathConfig};
+use std::sync::LazyLock;
+
+use actix_web::{
+    middleware::from_fn,
+    web::{get, Data, JsonConfig, PathConfig},
+};
 use env_config::environment_variables;
 use surrealdb_helper::SurrealDB;
-use utils::{minio::Minio, openapi::Swagger};
+use utils::{auth::authorize, minio::Minio, openapi::Swagger, rate_limit::RateLimiter};
 use utoipa::openapi::OpenApi as OpenApiStruct;
 use utoipa::OpenApi;
-use utoipa_actix_web::service_config::ServiceConfig;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
 
 pub mod models;
 pub mod routes;
 pub mod utils;
 
-use crate::routes::{not_found, ApiError};
+use crate::{
+    models::{AUCTION_MODE, CREATIVE_SELECTION},
+    routes::{not_found, ApiError},
+};
 
 environment_variables! {
     SERVER_ADDRESS: String = "0.0.0.0:8080",
@@ -23,15 +31,35 @@ environment_variables! {
     MINIO_USER: String = "root",
     MINIO_PASSWORD: String = "beetroot",
     MINIO_BUCKET: String = "ad-platform-backend-bucket",
-    MODERATION_ENABLED: bool = true
+    MODERATION_ENABLED: bool = true,
+    AUCTION_MODE: String = "first_price",
+    CAMPAIGN_RETENTION_DAYS: i32 = 30,
+    CREATIVE_SELECTION: String = "uniform",
+    CLICK_ATTRIBUTION_DAYS: i32 = 7,
+    CONVERSION_ATTRIBUTION_DAYS: i32 = 30,
+    ADMIN_API_KEY: String = "",
+    ADS_RATE_LIMIT: i32 = 50,
+    ADS_RATE_LIMIT_BURST: i32 = 100,
+    WRITE_RATE_LIMIT: i32 = 20,
+    WRITE_RATE_LIMIT_BURST: i32 = 200,
+    IP_RATE_LIMIT: i32 = 200,
+    IP_RATE_LIMIT_BURST: i32 = 400,
+    IDEMPOTENCY_KEY_TTL_SECONDS: i32 = 86400,
+    IDEMPOTENCY_KEY_LEASE_SECONDS: i32 = 60
 }
 
 pub fn app_setup(db: SurrealDB, minio: Minio) -> BackendConfig {
     config::init();
+    if config::ADMIN_API_KEY.is_empty() {
+        log::warn!("`ADMIN_API_KEY` is not set, administrator access is disabled");
+    }
+    LazyLock::force(&AUCTION_MODE);
+    LazyLock::force(&CREATIVE_SELECTION);
     BackendConfig {
         db,
         minio,
         openapi: Swagger::openapi(),
+        rate_limiter: RateLimiter::from_config(),
     }
 }
 
@@ -40,6 +68,7 @@ pub struct BackendConfig {
     pub db: SurrealDB,
     pub minio: Minio,
     pub openapi: OpenApiStruct,
+    pub rate_limiter: RateLimiter,
 }
 
 impl BackendConfig {
@@ -55,7 +84,13 @@ impl BackendConfig {
             )
             .app_data(Data::new(self.db))
             .app_data(Data::new(self.minio))
-            .configure(routes::config)
+            .service(
+                scope("")
+                    .wrap(self.rate_limiter.clone())
+                    .wrap(from_fn(authorize))
+                    .wrap(self.rate_limiter.by_ip())
+                    .configure(routes::config),
+            )
             .default_service(get().to(not_found));
         }
     }
@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::ValidationError;
+
+/// Пользовательские атрибуты клиента.
+pub type Attributes = BTreeMap<String, AttributeValue>;
+
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Clone, Debug)]
+#[serde(untagged)]
+#[schema(description = "Значение пользовательского атрибута клиента.")]
+pub enum AttributeValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    List(Vec<String>),
+}
+
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+#[schema(description = "Оператор правила таргетинга по атрибуту клиента.")]
+pub enum RuleOperator {
+    /// Значение атрибута равно `value`.
+    Equals,
+    /// Значение атрибута совпадает с одним из `values`.
+    In,
+    /// Числовое значение атрибута лежит в диапазоне от `min` до `max` (включительно).
+    Range,
+    /// Список (или строка) в атрибуте содержит `value`.
+    Contains,
+}
+
+pub fn validate_attribute_name(name: &str) -> Result<(), ValidationError> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(ValidationError::new(
+            "Attribute names must be snake_case and at most 64 characters long",
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_attributes(attributes: &Attributes) -> Result<(), ValidationError> {
+    attributes
+        .keys()
+        .try_for_each(|name| validate_attribute_name(name))
+}
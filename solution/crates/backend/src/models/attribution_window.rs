@@ -0,0 +1,66 @@
+use crate::{config, routes::ApiError};
+
+/// Окно атрибуции: максимальное количество дней между событием и предшествующим
+/// ему событием, в течение которого событие засчитывается кампании.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AttributionWindow {
+    /// Переход должен произойти не позже `CLICK_ATTRIBUTION_DAYS` дней после последнего показа.
+    Click,
+    /// Конверсия должна произойти не позже `CONVERSION_ATTRIBUTION_DAYS` дней после перехода.
+    Conversion,
+}
+
+impl AttributionWindow {
+    pub fn days(&self) -> i32 {
+        match self {
+            Self::Click => *config::CLICK_ATTRIBUTION_DAYS,
+            Self::Conversion => *config::CONVERSION_ATTRIBUTION_DAYS,
+        }
+    }
+
+    /// Проверяет, что событие, произошедшее в день `current_day`, попадает в окно
+    /// атрибуции предшествующего события, произошедшего в день `reference_day`.
+    pub fn check(&self, reference_day: i32, current_day: i32) -> Result<(), ApiError> {
+        let days = self.days();
+        if Self::contains(days, reference_day, current_day) {
+            return Ok(());
+        }
+
+        Err(ApiError::AttributionWindowExpired {
+            event: match self {
+                Self::Click => "Click",
+                Self::Conversion => "Conversion",
+            }
+            .into(),
+            days,
+        })
+    }
+
+    fn contains(days: i32, reference_day: i32, current_day: i32) -> bool {
+        current_day - reference_day <= days
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::AttributionWindow;
+
+    #[rstest]
+    #[case::same_day(7, 3, 3, true)]
+    #[case::last_day(7, 3, 10, true)]
+    #[case::expired(7, 3, 11, false)]
+    #[case::zero_days(0, 3, 4, false)]
+    fn contains(
+        #[case] days: i32,
+        #[case] reference_day: i32,
+        #[case] current_day: i32,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            AttributionWindow::contains(days, reference_day, current_day),
+            expected
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::{str::FromStr, sync::LazyLock};
+
+use crate::config;
+
+/// Режим аукциона, по которому определяется стоимость показа и перехода для победившей кампании.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AuctionMode {
+    /// Победитель платит собственную ставку.
+    FirstPrice,
+    /// Обобщенный аукцион второй цены: победитель платит минимальную сумму,
+    /// достаточную для того, чтобы опередить следующую по скору кампанию.
+    SecondPrice,
+}
+
+pub static AUCTION_MODE: LazyLock<AuctionMode> = LazyLock::new(|| {
+    config::AUCTION_MODE.parse().unwrap_or_else(|_| {
+        panic!(
+            "Invalid value `{}` for the variable `AUCTION_MODE`! Expected `first_price` or `second_price`.",
+            *config::AUCTION_MODE
+        )
+    })
+});
+
+impl FromStr for AuctionMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first_price" => Ok(Self::FirstPrice),
+            "second_price" => Ok(Self::SecondPrice),
+            _ => Err(()),
+        }
+    }
+}
+
+impl AuctionMode {
+    /// Количество кампаний, которое нужно выбрать, чтобы провести аукцион на `slots` слотов.
+    pub fn candidates(&self, slots: u32) -> u32 {
+        match self {
+            Self::FirstPrice => slots,
+            Self::SecondPrice => slots + 1,
+        }
+    }
+
+    /// Доля от ставок кампании со скором `score`, которую она платит за показ и переход.
+    /// Скор линеен по ставкам, поэтому при аукционе второй цены ставки масштабируются
+    /// до уровня, при котором скор совпадает со скором следующей кампании.
+    /// Если следующей кампании нет или её скор не положителен, победитель платит
+    /// свои ставки полностью, чтобы показ не доставался бесплатно.
+    pub fn price_factor(&self, score: f64, runner_up_score: Option<f64>) -> f64 {
+        match (self, runner_up_score) {
+            (Self::SecondPrice, Some(runner_up_score)) if score > 0.0 && runner_up_score > 0.0 => {
+                (runner_up_score / score).min(1.0)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::AuctionMode;
+
+    #[rstest]
+    #[case::first_price(AuctionMode::FirstPrice, 12.0, Some(6.0), 1.0)]
+    #[case::second_price(AuctionMode::SecondPrice, 12.0, Some(6.0), 0.5)]
+    #[case::no_runner_up(AuctionMode::SecondPrice, 12.0, None, 1.0)]
+    #[case::negative_runner_up(AuctionMode::SecondPrice, 12.0, Some(-3.0), 1.0)]
+    #[case::zero_runner_up(AuctionMode::SecondPrice, 12.0, Some(0.0), 1.0)]
+    fn price_factor(
+        #[case] mode: AuctionMode,
+        #[case] score: f64,
+        #[case] runner_up_score: Option<f64>,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(mode.price_factor(score, runner_up_score), expected);
+    }
+}
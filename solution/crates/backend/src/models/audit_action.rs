@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::CampaignState;
+
+/// Тип сущности, изменение которой записано в журнал аудита.
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditEntity {
+    /// Рекламная кампания, в том числе её изображение.
+    Campaign,
+    /// Креатив рекламной кампании.
+    Creative,
+    /// Сегмент аудитории.
+    Segment,
+    /// Клиент.
+    Client,
+    /// Рекламодатель.
+    Advertiser,
+    /// ML скор пары клиент-рекламодатель.
+    MlScore,
+    /// Текущий день.
+    Time,
+}
+
+/// Действие, записанное в журнал аудита.
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditAction {
+    /// Создание рекламной кампании.
+    CampaignCreated,
+    /// Обновление рекламной кампании.
+    CampaignUpdated,
+    /// Удаление рекламной кампании.
+    CampaignDeleted,
+    /// Восстановление удалённой рекламной кампании.
+    CampaignRestored,
+    /// Приостановка рекламной кампании.
+    CampaignPaused,
+    /// Возобновление рекламной кампании.
+    CampaignResumed,
+    /// Архивирование рекламной кампании.
+    CampaignArchived,
+    /// Загрузка изображения рекламной кампании.
+    CampaignImageUploaded,
+    /// Удаление изображения рекламной кампании.
+    CampaignImageDeleted,
+    /// Создание креатива.
+    CreativeCreated,
+    /// Удаление креатива.
+    CreativeDeleted,
+    /// Загрузка изображения креатива.
+    CreativeImageUploaded,
+    /// Удаление изображения креатива.
+    CreativeImageDeleted,
+    /// Обновление сегмента аудитории.
+    SegmentUpdated,
+    /// Создание или обновление клиента при массовой загрузке.
+    ClientUpserted,
+    /// Создание или обновление рекламодателя при массовой загрузке.
+    AdvertiserUpserted,
+    /// Добавление или обновление ML скора.
+    MlScoreUpserted,
+    /// Переход на новый текущий день.
+    TimeAdvanced,
+}
+
+impl AuditAction {
+    /// Действие, которым кампания переводится в статус `state`.
+    pub fn for_state(state: CampaignState) -> Self {
+        match state {
+            CampaignState::ACTIVE => Self::CampaignResumed,
+            CampaignState::PAUSED => Self::CampaignPaused,
+            CampaignState::ARCHIVED => Self::CampaignArchived,
+        }
+    }
+
+    pub fn entity(&self) -> AuditEntity {
+        match self {
+            Self::CampaignCreated
+            | Self::CampaignUpdated
+            | Self::CampaignDeleted
+            | Self::CampaignRestored
+            | Self::CampaignPaused
+            | Self::CampaignResumed
+            | Self::CampaignArchived
+            | Self::CampaignImageUploaded
+            | Self::CampaignImageDeleted => AuditEntity::Campaign,
+            Self::CreativeCreated
+            | Self::CreativeDeleted
+            | Self::CreativeImageUploaded
+            | Self::CreativeImageDeleted => AuditEntity::Creative,
+            Self::SegmentUpdated => AuditEntity::Segment,
+            Self::ClientUpserted => AuditEntity::Client,
+            Self::AdvertiserUpserted => AuditEntity::Advertiser,
+            Self::MlScoreUpserted => AuditEntity::MlScore,
+            Self::TimeAdvanced => AuditEntity::Time,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::future::{ready, Ready};
+
+use actix_web::{dev::Payload, FromRequest, HttpMessage, HttpRequest};
+use subtle::ConstantTimeEq;
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    models::db::{DBAdvertiser, DBApiKey, DBModel},
+    routes::ApiError,
+};
+
+use super::Role;
+
+/// Вызывающий API, определённый по ключу из заголовка `Authorization`.
+/// Кладётся в расширения запроса middleware `authorize`.
+#[derive(Clone, Debug)]
+pub struct Caller {
+    pub role: Role,
+    pub advertiser_id: Option<Uuid>,
+    /// UUID ключа API. Для ключа администратора из переменной окружения не указывается.
+    pub api_key_id: Option<Uuid>,
+}
+
+impl Caller {
+    pub fn admin() -> Self {
+        Self {
+            role: Role::Admin,
+            advertiser_id: None,
+            api_key_id: None,
+        }
+    }
+
+    /// Находит владельца ключа API. Ключ администратора задаётся переменной
+    /// `ADMIN_API_KEY`, ключи рекламодателей хранятся в базе данных.
+    /// Пустой `ADMIN_API_KEY` отключает доступ администратора.
+    pub async fn resolve(api_key: &str, db: &SurrealDB) -> Result<Self, ApiError> {
+        if Self::is_admin_key(api_key) {
+            return Ok(Self::admin());
+        }
+
+        match DBApiKey::resolve(api_key, db).await? {
+            None => Err(ApiError::Unauthorized),
+            Some(api_key) => Ok(Self {
+                role: api_key.role,
+                advertiser_id: api_key
+                    .advertiser_id
+                    .as_ref()
+                    .map(DBAdvertiser::record_id_to_uuid),
+                api_key_id: Some(DBApiKey::record_id_to_uuid(&api_key.id)),
+            }),
+        }
+    }
+
+    /// Сравнивает ключ с ключом администратора за время, не зависящее
+    /// от совпадающего префикса.
+    fn is_admin_key(api_key: &str) -> bool {
+        !config::ADMIN_API_KEY.is_empty()
+            && bool::from(api_key.as_bytes().ct_eq(config::ADMIN_API_KEY.as_bytes()))
+    }
+
+    /// Проверяет, что вызывающий может работать с данными рекламодателя.
+    /// Ключи рекламодателей ограничены собственными данными, остальные роли
+    /// ограничиваются набором доступных методов.
+    pub fn check_advertiser(&self, advertiser_id: Uuid) -> Result<(), ApiError> {
+        match self.role {
+            Role::Advertiser if self.advertiser_id != Some(advertiser_id) => {
+                Err(ApiError::NotOwner)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromRequest for Caller {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<Self>()
+                .cloned()
+                .ok_or(ApiError::Unauthorized),
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Статус кампании, который задаёт рекламодатель.
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Clone, Copy, Debug)]
+pub enum CampaignState {
+    ACTIVE,
+    PAUSED,
+    ARCHIVED,
+}
+impl fmt::Display for CampaignState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl CampaignState {
+    /// Архивированная кампания не может сменить статус.
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::ACTIVE, Self::PAUSED)
+                | (Self::PAUSED, Self::ACTIVE)
+                | (Self::ACTIVE | Self::PAUSED, Self::ARCHIVED)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::CampaignState::{self, *};
+
+    #[rstest]
+    #[case(ACTIVE, PAUSED, true)]
+    #[case(PAUSED, ACTIVE, true)]
+    #[case(ACTIVE, ARCHIVED, true)]
+    #[case(PAUSED, ARCHIVED, true)]
+    #[case(ACTIVE, ACTIVE, false)]
+    #[case(PAUSED, PAUSED, false)]
+    #[case(ARCHIVED, ACTIVE, false)]
+    #[case(ARCHIVED, PAUSED, false)]
+    fn can_transition_to(
+        #[case] current: CampaignState,
+        #[case] next: CampaignState,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(current.can_transition_to(next), expected);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{str::FromStr, sync::LazyLock};
+
+use rand::Rng;
+use rand_distr::{Beta, Distribution};
+
+use crate::config;
+
+/// Стратегия выбора креатива рекламной кампании для очередного показа.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CreativeSelection {
+    /// Все креативы кампании показываются с равной вероятностью.
+    Uniform,
+    /// Томпсоновское сэмплирование: для каждого креатива из апостериорного
+    /// распределения Beta(переходы + 1, показы - переходы + 1) выбирается
+    /// значение конверсии, показывается креатив с наибольшим значением.
+    Thompson,
+}
+
+pub static CREATIVE_SELECTION: LazyLock<CreativeSelection> = LazyLock::new(|| {
+    config::CREATIVE_SELECTION.parse().unwrap_or_else(|_| {
+        panic!(
+            "Invalid value `{}` for the variable `CREATIVE_SELECTION`! Expected `uniform` or `thompson`.",
+            *config::CREATIVE_SELECTION
+        )
+    })
+});
+
+impl FromStr for CreativeSelection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => Ok(Self::Uniform),
+            "thompson" => Ok(Self::Thompson),
+            _ => Err(()),
+        }
+    }
+}
+
+impl CreativeSelection {
+    /// Возвращает индекс креатива, который нужно показать, по статистике креативов
+    /// в виде пар (показы, переходы). Для пустого списка возвращает `None`.
+    pub fn choose(&self, stats: &[(u64, u64)], rng: &mut impl Rng) -> Option<usize> {
+        if stats.is_empty() {
+            return None;
+        }
+
+        match self {
+            Self::Uniform => Some(rng.random_range(0..stats.len())),
+            Self::Thompson => stats
+                .iter()
+                .map(|&(impressions, clicks)| {
+                    let successes = clicks.min(impressions);
+                    Beta::new((successes + 1) as f64, (impressions - successes + 1) as f64)
+                        .unwrap()
+                        .sample(rng)
+                })
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(i, _)| i),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rstest::rstest;
+
+    use super::CreativeSelection;
+
+    #[rstest]
+    #[case::uniform(CreativeSelection::Uniform)]
+    #[case::thompson(CreativeSelection::Thompson)]
+    fn choose_empty(#[case] selection: CreativeSelection) {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert_eq!(selection.choose(&[], &mut rng), None);
+    }
+
+    #[rstest]
+    #[case::uniform(CreativeSelection::Uniform)]
+    #[case::thompson(CreativeSelection::Thompson)]
+    fn choose_in_bounds(#[case] selection: CreativeSelection) {
+        let mut rng = StdRng::seed_from_u64(0);
+        let stats = [(0, 0), (10, 1), (10, 5)];
+
+        for _ in 0..100 {
+            assert!(selection.choose(&stats, &mut rng).unwrap() < stats.len());
+        }
+    }
+
+    #[test]
+    fn thompson_prefers_better_creative() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let stats = [(1000, 10), (1000, 500)];
+
+        for _ in 0..100 {
+            assert_eq!(
+                CreativeSelection::Thompson.choose(&stats, &mut rng),
+                Some(1)
+            );
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Deserialize;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use super::{DBCampaign, DBClient, DBModel};
+
+#[derive(Deserialize, Debug)]
+pub struct DBAdDecision {
+    pub id: RecordId,
+    pub advertiser_id: RecordId,
+    pub predicates: DBAdPredicates,
+    pub score: DBScoreBreakdown,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DBAdPredicates {
+    pub is_active: bool,
+    pub gender: bool,
+    pub age: bool,
+    pub location: bool,
+    pub client_lists: bool,
+    pub rules: bool,
+    pub active_window: bool,
+    pub impressions_limit: bool,
+    pub budget: bool,
+    pub frequency_cap: bool,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DBScoreBreakdown {
+    pub impression_term: f64,
+    pub click_term: f64,
+    pub ml_score: f64,
+    pub limit_penalty: f64,
+    pub total: f64,
+}
+
+impl DBAdDecision {
+    pub async fn list_for_client(client_id: Uuid, db: &SurrealDB) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT
+                        id,
+                        advertiser_id,
+                        {
+                            is_active: is_active,
+                            gender: fn::targeting_gender_matches(fn::campaign_targeting(id), $client),
+                            age: fn::targeting_age_matches(fn::campaign_targeting(id), $client),
+                            location: fn::targeting_location_matches(fn::campaign_targeting(id), $client),
+                            client_lists: fn::targeting_client_allowed(fn::campaign_targeting(id), $client),
+                            rules: fn::targeting_rules_match(fn::campaign_targeting(id), $client),
+                            active_window: start_date <= fn::current_day() && fn::current_day() <= end_date,
+                            impressions_limit: ((SELECT VALUE total.impressions_count FROM ONLY fn::stats_id_from_obj_id($parent.id)) ?? 0) < impressions_limit,
+                            budget: fn::campaign_within_budget(id),
+                            frequency_cap: fn::campaign_within_frequency_cap($client, id)
+                        } AS predicates,
+                        fn::score_campaign_breakdown($client, id) AS score
+                    FROM type::table($campaign_table)
+                        WHERE deleted_at == NONE
+                        ORDER BY score.total DESC
+                "#,
+            )
+            .bind(("client", DBClient::record_id_from_uuid(&client_id)))
+            .bind(("campaign_table", DBCampaign::TABLE))
+            .await?
+            .take(0)?)
+    }
+}
+
+impl DBAdPredicates {
+    /// Флаг `is_active` обновляется только при записи кампании и её статистики,
+    /// поэтому период показа и лимит показов проверяются отдельно.
+    pub fn passed(&self) -> bool {
+        self.is_active
+            && self.active_window
+            && self.impressions_limit
+            && self.gender
+            && self.age
+            && self.location
+            && self.client_lists
+            && self.rules
+            && self.budget
+            && self.frequency_cap
+    }
+}
@@ -5,12 +5,13 @@ use uuid::Uuid;
 
 use crate::models::dto::Advertiser;
 
-use super::DBModel;
+use super::{DBAudit, DBCampaign, DBLedgerEntry, DBModel};
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DBAdvertiser {
     pub id: RecordId,
     pub name: String,
+    pub currency: String,
 }
 
 impl DBModel for DBAdvertiser {
@@ -22,7 +23,42 @@ impl DBAdvertiser {
         Ok(db.0.select(Self::record_id_from_uuid(&id)).await?)
     }
 
-    pub async fn bulk(advertisers: Vec<Advertiser>, db: &SurrealDB) -> Result<Vec<Self>> {
+    /// Рекламодатели, у которых обновление меняет валюту, хотя в ней уже указаны
+    /// цены их кампаний или записи журнала списаний и пополнений.
+    pub async fn currency_locked(
+        advertisers: &[Advertiser],
+        db: &SurrealDB,
+    ) -> Result<Vec<RecordId>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT VALUE id FROM $advertisers
+                        WHERE id.currency != NONE
+                            AND id.currency != currency
+                            AND (
+                                (SELECT VALUE id FROM type::table($campaign_table)
+                                    WHERE advertiser_id = $parent.id LIMIT 1) != []
+                                OR (SELECT VALUE id FROM type::table($ledger_table)
+                                    WHERE advertiser_id = $parent.id LIMIT 1) != []
+                            )
+                "#,
+            )
+            .bind((
+                "advertisers",
+                advertisers.iter().map(Self::from).collect::<Vec<Self>>(),
+            ))
+            .bind(("campaign_table", DBCampaign::TABLE))
+            .bind(("ledger_table", DBLedgerEntry::TABLE))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn bulk(
+        advertisers: Vec<Advertiser>,
+        audit: DBAudit,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
         Ok(db
             .0
             .query("BEGIN")
@@ -44,8 +80,11 @@ impl DBAdvertiser {
                 advertisers.iter().map(Self::from).collect::<Vec<Self>>(),
             ))
             .bind(("advertiser_table", Self::TABLE))
+            .query("fn::audit($audit)")
+            .bind(("audit", audit))
             .query("COMMIT")
             .await?
+            .check()?
             .take(1)?)
     }
 }
@@ -55,6 +94,7 @@ impl From<&Advertiser> for DBAdvertiser {
         Self {
             id: Self::record_id_from_uuid(&dto.id),
             name: dto.name.clone(),
+            currency: dto.currency.clone(),
         }
     }
 }
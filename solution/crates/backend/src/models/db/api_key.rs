@@ -0,0 +1,134 @@
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::Role;
+
+use super::{DBAdvertiser, DBModel};
+
+/// Ключ API. В базе данных хранится только хэш ключа, сам ключ
+/// возвращается один раз при выпуске. Ключи платформенных ролей
+/// не привязаны к рекламодателю.
+#[derive(Deserialize, Debug)]
+pub struct DBApiKey {
+    pub id: RecordId,
+    pub advertiser_id: Option<RecordId>,
+    pub role: Role,
+    pub date: i32,
+}
+
+impl DBModel for DBApiKey {
+    const TABLE: &str = "api_key";
+}
+
+impl DBApiKey {
+    pub async fn create(
+        id: Uuid,
+        advertiser_id: Option<Uuid>,
+        role: Role,
+        key: &str,
+        db: &SurrealDB,
+    ) -> Result<Self> {
+        let api_key: Option<Self> = db
+            .0
+            .query(
+                r#"
+                    CREATE ONLY type::thing($api_key_table, $api_key_id)
+                        CONTENT {
+                            advertiser_id: IF $advertiser_id != NONE {
+                                type::thing($advertiser_table, $advertiser_id)
+                            },
+                            role: $role,
+                            key_hash: fn::hash_api_key($key)
+                        }
+                "#,
+            )
+            .bind(json!({
+                "api_key_table": Self::TABLE,
+                "api_key_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "role": role,
+                "key": key
+            }))
+            .await?
+            .take(0)?;
+        Ok(api_key.unwrap())
+    }
+
+    pub async fn resolve(key: &str, db: &SurrealDB) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM ONLY type::table($api_key_table)
+                        WHERE key_hash = fn::hash_api_key($key)
+                        LIMIT 1
+                "#,
+            )
+            .bind(json!({
+                "api_key_table": Self::TABLE,
+                "key": key
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn list(
+        advertiser_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM type::table($api_key_table)
+                        WHERE advertiser_id = (IF $advertiser_id != NONE {
+                            type::thing($advertiser_table, $advertiser_id)
+                        })
+                        ORDER BY id
+                        LIMIT <number>($limit)
+                        START <number>($offset)
+                "#,
+            )
+            .bind(json!({
+                "api_key_table": Self::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "limit": limit,
+                "offset": offset
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn delete(
+        advertiser_id: Option<Uuid>,
+        id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    DELETE type::thing($api_key_table, $api_key_id)
+                        WHERE advertiser_id = (IF $advertiser_id != NONE {
+                            type::thing($advertiser_table, $advertiser_id)
+                        })
+                        RETURN BEFORE
+                "#,
+            )
+            .bind(json!({
+                "api_key_table": Self::TABLE,
+                "api_key_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .await?
+            .take(0)?)
+    }
+}
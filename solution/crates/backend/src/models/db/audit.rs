@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::{AuditAction, AuditEntity, Caller, Role};
+
+use super::{DBAdvertiser, DBModel};
+
+/// Запись журнала аудита. Поля записей доступны только для чтения,
+/// поэтому журнал можно только дополнять.
+#[derive(Deserialize, Debug)]
+pub struct DBAuditEntry {
+    pub id: RecordId,
+    pub action: AuditAction,
+    pub entity: AuditEntity,
+    pub entity_id: Option<Uuid>,
+    pub advertiser_id: Option<RecordId>,
+    pub actor_role: Role,
+    pub actor_advertiser_id: Option<RecordId>,
+    pub actor_api_key_id: Option<Uuid>,
+    pub date: i32,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl DBModel for DBAuditEntry {
+    const TABLE: &str = "audit_entry";
+}
+
+/// Изменение, которое необходимо записать в журнал аудита.
+#[derive(Serialize, Debug)]
+pub struct DBAuditRecord {
+    pub action: AuditAction,
+    pub entity: AuditEntity,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_id: Option<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advertiser_id: Option<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+impl DBAuditRecord {
+    pub fn new(action: AuditAction, entity_id: Option<Uuid>, advertiser_id: Option<Uuid>) -> Self {
+        Self {
+            action,
+            entity: action.entity(),
+            entity_id,
+            advertiser_id,
+            before: None,
+            after: None,
+        }
+    }
+
+    /// Добавляет снимок сущности до изменения.
+    pub fn with_before(self, before: impl Serialize) -> Self {
+        Self {
+            before: serde_json::to_value(before).ok(),
+            ..self
+        }
+    }
+
+    /// Добавляет снимок сущности после изменения.
+    pub fn with_after(self, after: impl Serialize) -> Self {
+        Self {
+            after: serde_json::to_value(after).ok(),
+            ..self
+        }
+    }
+}
+
+/// Изменения и их автор. Записываются в журнал функцией `fn::audit` в той же
+/// транзакции, что и само изменение.
+#[derive(Serialize, Debug)]
+pub struct DBAudit {
+    records: Vec<DBAuditRecord>,
+    actor: Value,
+}
+
+impl DBAudit {
+    pub fn new(records: Vec<DBAuditRecord>, caller: &Caller) -> Self {
+        let mut actor = json!({ "role": caller.role });
+        if let Some(advertiser_id) = caller.advertiser_id {
+            actor["advertiser_id"] = json!(advertiser_id);
+        }
+        if let Some(api_key_id) = caller.api_key_id {
+            actor["api_key_id"] = json!(api_key_id);
+        }
+        Self { records, actor }
+    }
+}
+
+impl DBAuditEntry {
+    /// Записывает в журнал действия, которые не изменяют данные в базе (например,
+    /// загрузку изображения).
+    pub async fn create(audit: DBAudit, db: &SurrealDB) -> Result<()> {
+        db.0.query("fn::audit($audit)")
+            .bind(("audit", audit))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn list(
+        entity: Option<AuditEntity>,
+        entity_id: Option<Uuid>,
+        advertiser_id: Option<Uuid>,
+        from: Option<i32>,
+        to: Option<i32>,
+        limit: i64,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM type::table($audit_table)
+                        WHERE (!$entity || entity == $entity)
+                            && (!$entity_id || entity_id == $entity_id)
+                            && (!$advertiser_id
+                                || advertiser_id == type::thing($advertiser_table, $advertiser_id))
+                            && (!$from || date >= $from)
+                            && (!$to || date <= $to)
+                        ORDER BY id
+                        LIMIT <number>($limit)
+                        START <number>($offset)
+                "#,
+            )
+            .bind(json!({
+                "audit_table": Self::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "entity": entity,
+                "entity_id": entity_id,
+                "advertiser_id": advertiser_id,
+                "from": from,
+                "to": to,
+                "limit": limit,
+                "offset": offset
+            }))
+            .await?
+            .take(0)?)
+    }
+}
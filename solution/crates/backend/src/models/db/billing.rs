@@ -0,0 +1,181 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::LedgerEntryKind;
+
+use super::{DBAdvertiser, DBModel};
+
+/// Запись журнала списаний и пополнений. Списания создаются событиями
+/// `impressed`, `clicked` и `converted` через `fn::charge`.
+#[derive(Deserialize, Debug)]
+pub struct DBLedgerEntry {
+    pub id: RecordId,
+    pub advertiser_id: RecordId,
+    pub campaign_id: Option<RecordId>,
+    pub kind: LedgerEntryKind,
+    pub date: i32,
+
+    #[serde(default, with = "crate::models::db::decimal::option")]
+    pub unit_price: Option<Decimal>,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub amount: Decimal,
+}
+
+impl DBModel for DBLedgerEntry {
+    const TABLE: &str = "ledger_entry";
+}
+
+impl DBLedgerEntry {
+    pub async fn top_up(advertiser_id: Uuid, amount: Decimal, db: &SurrealDB) -> Result<Self> {
+        let entry: Option<Self> =
+            db.0.query(
+                r#"
+                    CREATE ONLY type::thing($ledger_table, <string> rand::uuid::v7())
+                        CONTENT {
+                            advertiser_id: type::thing($advertiser_table, $advertiser_id),
+                            kind: "TOP_UP",
+                            amount: <decimal> $amount
+                        }
+                "#,
+            )
+            .bind(json!({
+                "ledger_table": Self::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "amount": amount
+            }))
+            .await?
+            .take(0)?;
+        Ok(entry.unwrap())
+    }
+
+    pub async fn list(
+        advertiser_id: Uuid,
+        limit: i64,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM type::table($ledger_table)
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                        ORDER BY date, id
+                        LIMIT <number>($limit)
+                        START <number>($offset)
+                "#,
+            )
+            .bind(json!({
+                "ledger_table": Self::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "limit": limit,
+                "offset": offset
+            }))
+            .await?
+            .take(0)?)
+    }
+}
+
+/// Баланс рекламодателя. Создаётся при первой записи в журнале и изменяется
+/// событием `ledger_entry_created`, предоплаченным становится после первого пополнения.
+#[derive(Deserialize, Debug)]
+pub struct DBBillingAccount {
+    pub prepaid: bool,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub balance: Decimal,
+}
+
+impl DBModel for DBBillingAccount {
+    const TABLE: &str = "billing_account";
+}
+
+impl DBBillingAccount {
+    pub async fn get(advertiser_id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .select(Self::record_id_from_uuid(&advertiser_id))
+            .await?)
+    }
+}
+
+/// Строка счёта: списания одного типа по одной цене в рамках рекламной кампании.
+#[derive(Deserialize, Debug)]
+pub struct DBInvoiceLine {
+    pub campaign_id: RecordId,
+    pub kind: LedgerEntryKind,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub unit_price: Decimal,
+
+    pub quantity: u64,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub amount: Decimal,
+}
+
+#[derive(Deserialize, Debug)]
+struct DBInvoiceTotals {
+    #[serde(with = "crate::models::db::decimal")]
+    total: Decimal,
+
+    #[serde(with = "crate::models::db::decimal")]
+    top_ups: Decimal,
+}
+
+#[derive(Debug)]
+pub struct DBInvoice {
+    pub lines: Vec<DBInvoiceLine>,
+    pub total: Decimal,
+    pub top_ups: Decimal,
+}
+
+impl DBInvoice {
+    /// Суммы считаются в базе данных по `decimal` значениям журнала,
+    /// поэтому итог счёта совпадает с суммой его строк без погрешности округления.
+    pub async fn get(advertiser_id: Uuid, from: i32, to: i32, db: &SurrealDB) -> Result<Self> {
+        let mut response = db
+            .0
+            .query(
+                r#"
+                    LET $entries = SELECT kind, campaign_id, unit_price, amount
+                        FROM type::table($ledger_table)
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            && date >= $from
+                            && date <= $to;
+                    LET $charges = $entries.filter(|$entry| $entry.kind != "TOP_UP");
+                    SELECT campaign_id, kind, unit_price, count() AS quantity, math::sum(amount) AS amount
+                        FROM $charges
+                        GROUP BY campaign_id, kind, unit_price
+                        ORDER BY campaign_id, kind, unit_price;
+                    RETURN {
+                        total: <decimal> math::sum($charges.amount),
+                        top_ups: <decimal> math::sum($entries.filter(|$entry| $entry.kind == "TOP_UP").amount)
+                    };
+                "#,
+            )
+            .bind(json!({
+                "ledger_table": DBLedgerEntry::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "from": from,
+                "to": to
+            }))
+            .await?;
+
+        let totals: Option<DBInvoiceTotals> = response.take(3)?;
+        let totals = totals.unwrap();
+        Ok(Self {
+            lines: response.take(2)?,
+            total: totals.total,
+            top_ups: totals.top_ups,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use surrealdb::{RecordId, Result};
@@ -6,46 +7,116 @@ use uuid::Uuid;
 
 use crate::{
     models::{
-        dto::{Campaign, CampaignUpdate, Targeting},
-        TargetingGender,
+        dto::{AgeRange, Campaign, CampaignUpdate, FrequencyCap, Targeting, TargetingRule},
+        AttributeValue, CampaignState, RuleOperator, TargetingGender,
     },
     utils::RoundToDigits,
 };
 
-use super::{DBAdvertiser, DBClient, DBModel};
+use super::{DBAdvertiser, DBAudit, DBClient, DBModel, DBSegment};
 
+/// Денежные поля передаются в базу данных и хранятся в ней как `decimal`
+/// (см. [`decimal`](super::decimal)).
 #[derive(Deserialize, Serialize, Debug)]
 pub struct DBCampaign {
     pub id: RecordId,
     pub advertiser_id: RecordId,
     pub impressions_limit: i32,
     pub clicks_limit: i32,
-    pub cost_per_impression: f64,
-    pub cost_per_click: f64,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub cost_per_impression: Decimal,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub cost_per_click: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal::option")]
+    pub cost_per_conversion: Option<Decimal>,
+
+    #[serde(default, with = "crate::models::db::decimal::option")]
+    pub total_budget: Option<Decimal>,
+
+    #[serde(default, with = "crate::models::db::decimal::option")]
+    pub daily_budget: Option<Decimal>,
+
     pub ad_title: String,
     pub ad_text: String,
     pub start_date: i32,
     pub end_date: i32,
     pub targeting: DBTargeting,
+    pub segment_id: Option<RecordId>,
+    pub frequency_cap: Option<DBFrequencyCap>,
+    pub state: CampaignState,
+    pub deleted_at: Option<i32>,
     pub is_active: bool,
+
+    /// Скор кампании для клиента, заполняется только при подборе объявлений.
+    #[serde(default, skip_serializing)]
+    pub score: f64,
 }
 
 impl DBModel for DBCampaign {
     const TABLE: &str = "campaign";
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct DBTargeting {
     pub gender: Option<TargetingGender>,
     pub age_from: Option<i32>,
     pub age_to: Option<i32>,
     pub location: Option<String>,
+    pub locations: Option<Vec<String>>,
+    pub excluded_locations: Option<Vec<String>>,
+    pub age_ranges: Option<Vec<DBAgeRange>>,
+    pub allowed_clients: Option<Vec<RecordId>>,
+    pub denied_clients: Option<Vec<RecordId>>,
+    pub rules: Option<Vec<DBTargetingRule>>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DBTargetingRule {
+    pub attribute: String,
+    pub op: RuleOperator,
+    pub value: Option<AttributeValue>,
+    pub values: Option<Vec<AttributeValue>>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DBAgeRange {
+    pub age_from: Option<i32>,
+    pub age_to: Option<i32>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DBCampaignStateChange {
+    pub state: CampaignState,
+    pub date: i32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DBFrequencyCap {
+    pub per_day: Option<i32>,
+    pub lifetime: Option<i32>,
+    pub min_days_between: Option<i32>,
 }
 
 impl DBCampaign {
-    pub async fn create(campaign: Campaign, db: &SurrealDB) -> Result<Self> {
-        let campaign = Self::from(campaign);
-        Ok(db.0.create(&campaign.id).content(campaign).await?.unwrap())
+    pub async fn create(campaign: Campaign, audit: DBAudit, db: &SurrealDB) -> Result<Self> {
+        Ok(db
+            .0
+            .query("BEGIN")
+            .query("CREATE ONLY type::table($campaign_table) CONTENT $campaign")
+            .query("fn::audit($audit)")
+            .query("COMMIT")
+            .bind(("campaign_table", Self::TABLE))
+            .bind(("campaign", Self::from(campaign)))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take::<Option<Self>>(0)?
+            .unwrap())
     }
 
     pub async fn list(
@@ -60,6 +131,7 @@ impl DBCampaign {
                 r#"
                     SELECT * FROM type::table($campaign_table)
                         WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            && deleted_at == NONE
                         LIMIT <number>($limit)
                         START <number>($offset)
                 "#,
@@ -82,6 +154,31 @@ impl DBCampaign {
                 r#"
                     SELECT * FROM ONLY type::thing($campaign_table, $campaign_id) 
                         WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            && deleted_at == NONE
+                "#,
+            )
+            .bind(json!({
+                "campaign_table": Self::TABLE,
+                "campaign_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn get_deleted(
+        advertiser_id: Uuid,
+        id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM ONLY type::thing($campaign_table, $campaign_id) 
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            && deleted_at != NONE
                 "#,
             )
             .bind(json!({
@@ -101,18 +198,24 @@ impl DBCampaign {
     pub async fn update(
         advertiser_id: Uuid,
         id: Uuid,
-        campaign_update: CampaignUpdate,
+        campaign_update: DBCampaignUpdate,
+        audit: DBAudit,
         db: &SurrealDB,
     ) -> Result<Option<Self>> {
-        let campaign_update = DBCampaignUpdate::from(campaign_update);
         Ok(db
             .0
             .query(
                 r#"
-                    UPDATE ONLY type::thing($campaign_table, $campaign_id) 
-                        MERGE $campaign_update
-                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
-                        RETURN AFTER
+                    BEGIN TRANSACTION;
+                    LET $campaign = (
+                        UPDATE ONLY type::thing($campaign_table, $campaign_id) 
+                            MERGE $campaign_update
+                            WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            RETURN AFTER
+                    );
+                    IF $campaign != NONE { fn::audit($audit) };
+                    RETURN $campaign;
+                    COMMIT TRANSACTION;
                 "#,
             )
             .bind(json!({
@@ -120,20 +223,35 @@ impl DBCampaign {
                 "campaign_id": id,
                 "advertiser_table": DBAdvertiser::TABLE,
                 "advertiser_id": advertiser_id,
-                "campaign_update": campaign_update
             }))
+            .bind(("campaign_update", campaign_update))
+            .bind(("audit", audit))
             .await?
-            .take(0)?)
+            .check()?
+            .take(2)?)
     }
 
-    pub async fn delete(advertiser_id: Uuid, id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
+    pub async fn delete(
+        advertiser_id: Uuid,
+        id: Uuid,
+        audit: DBAudit,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
         Ok(db
             .0
             .query(
                 r#"
-                    DELETE type::thing($campaign_table, $campaign_id) 
-                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
-                        RETURN BEFORE
+                    BEGIN TRANSACTION;
+                    LET $campaign = (
+                        UPDATE ONLY type::thing($campaign_table, $campaign_id) 
+                            SET deleted_at = fn::current_day()
+                            WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                                && deleted_at == NONE
+                            RETURN BEFORE
+                    );
+                    IF $campaign != NONE { fn::audit($audit) };
+                    RETURN $campaign;
+                    COMMIT TRANSACTION;
                 "#,
             )
             .bind(json!({
@@ -142,33 +260,169 @@ impl DBCampaign {
                 "advertiser_table": DBAdvertiser::TABLE,
                 "advertiser_id": advertiser_id,
             }))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take(2)?)
+    }
+
+    pub async fn restore(
+        advertiser_id: Uuid,
+        id: Uuid,
+        audit: DBAudit,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    BEGIN TRANSACTION;
+                    LET $campaign = (
+                        UPDATE ONLY type::thing($campaign_table, $campaign_id) 
+                            SET deleted_at = NONE
+                            WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                                && deleted_at != NONE
+                            RETURN AFTER
+                    );
+                    IF $campaign != NONE { fn::audit($audit) };
+                    RETURN $campaign;
+                    COMMIT TRANSACTION;
+                "#,
+            )
+            .bind(json!({
+                "campaign_table": Self::TABLE,
+                "campaign_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take(2)?)
+    }
+
+    /// Возвращает удалённые кампании, срок хранения которых истёк.
+    pub async fn list_retention_expired(retention_days: i32, db: &SurrealDB) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM type::table($campaign_table)
+                        WHERE fn::campaign_retention_expired(id, $retention_days)
+                "#,
+            )
+            .bind(("campaign_table", Self::TABLE))
+            .bind(("retention_days", retention_days))
             .await?
             .take(0)?)
     }
 
-    pub async fn get_ad_for_client(client_id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
+    /// Окончательно удаляет кампании вместе с их статистикой. Вклад кампаний
+    /// в статистику рекламодателя сохраняется, чтобы он не терялся при её пересчёте.
+    pub async fn purge(ids: Vec<RecordId>, db: &SurrealDB) -> Result<()> {
+        db.0.query(
+            r#"
+                BEGIN TRANSACTION;
+                fn::purge_campaigns($campaigns);
+                COMMIT TRANSACTION;
+            "#,
+        )
+        .bind(("campaigns", ids))
+        .await?
+        .check()?;
+        Ok(())
+    }
+
+    pub async fn set_state(
+        advertiser_id: Uuid,
+        id: Uuid,
+        state: CampaignState,
+        audit: DBAudit,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
         Ok(db
             .0
             .query(
+                r#"
+                    BEGIN TRANSACTION;
+                    LET $campaign = (
+                        UPDATE ONLY type::thing($campaign_table, $campaign_id) 
+                            SET state = $state,
+                                state_history += { state: $state, date: fn::current_day() }
+                            WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            RETURN AFTER
+                    );
+                    IF $campaign != NONE { fn::audit($audit) };
+                    RETURN $campaign;
+                    COMMIT TRANSACTION;
+                "#,
+            )
+            .bind(json!({
+                "campaign_table": Self::TABLE,
+                "campaign_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "state": state
+            }))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take(2)?)
+    }
+
+    pub async fn state_history(
+        advertiser_id: Uuid,
+        id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Vec<DBCampaignStateChange>> {
+        let history: Option<Vec<DBCampaignStateChange>> =
+            db.0.query(
+                r#"
+                    SELECT VALUE state_history FROM ONLY type::thing($campaign_table, $campaign_id) 
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                "#,
+            )
+            .bind(json!({
+                "campaign_table": Self::TABLE,
+                "campaign_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .await?
+            .take(0)?;
+        Ok(history.unwrap_or_default())
+    }
+
+    /// Возвращает подходящие клиенту кампании в порядке убывания скора.
+    /// Если `limit` не указан, возвращаются все подходящие кампании.
+    pub async fn get_ads_for_client(
+        client_id: Uuid,
+        limit: Option<u32>,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        let limit_clause = match limit {
+            Some(_) => "LIMIT $limit",
+            None => "",
+        };
+
+        Ok(db
+            .0
+            .query(format!(
                 r#"
                     SELECT *,
                         (fn::score_campaign($client, $this.id)) as score
-                    FROM ONLY type::table($campaign_table)
+                    FROM type::table($campaign_table)
                         WHERE is_active 
-                            && (targeting.gender ?? "ALL") ∈ ["ALL", $client.gender]
-                            && (targeting.age_from ?? 0) <= $client.age && $client.age <= (targeting.age_to ?? math::inf)
-                            && $client.location == (targeting.location ?? $client.location)
-                        ORDER BY score
-                        LIMIT 1
+                            && fn::targeting_matches(fn::campaign_targeting(id), $client)
+                            && fn::campaign_within_budget(id)
+                            && fn::campaign_within_frequency_cap($client, id)
+                        ORDER BY score DESC
+                        {limit_clause}
                 "#
-            )
-            .bind((
-                "client", 
-                DBClient::record_id_from_uuid(&client_id)
-            ))
-            .bind((
-                "campaign_table", Self::TABLE
             ))
+            .bind(("client", DBClient::record_id_from_uuid(&client_id)))
+            .bind(("campaign_table", Self::TABLE))
+            .bind(("limit", limit))
             .await?
             .take(0)?)
     }
@@ -182,11 +436,35 @@ pub struct DBCampaignUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clicks_limit: Option<i32>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub cost_per_impression: Option<f64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub cost_per_impression: Option<Decimal>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub cost_per_click: Option<f64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub cost_per_click: Option<Decimal>,
+
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub cost_per_conversion: Option<Decimal>,
+
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub total_budget: Option<Decimal>,
+
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub daily_budget: Option<Decimal>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ad_title: Option<String>,
@@ -202,6 +480,45 @@ pub struct DBCampaignUpdate {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub targeting: Option<DBTargeting>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment_id: Option<RecordId>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_cap: Option<DBFrequencyCap>,
+}
+
+impl DBCampaignUpdate {
+    /// Кампания после применения обновления, как её сохранит `MERGE`.
+    pub fn apply(&self, campaign: DBCampaign) -> DBCampaign {
+        DBCampaign {
+            impressions_limit: self.impressions_limit.unwrap_or(campaign.impressions_limit),
+            clicks_limit: self.clicks_limit.unwrap_or(campaign.clicks_limit),
+            cost_per_impression: self
+                .cost_per_impression
+                .unwrap_or(campaign.cost_per_impression),
+            cost_per_click: self.cost_per_click.unwrap_or(campaign.cost_per_click),
+            cost_per_conversion: self.cost_per_conversion.or(campaign.cost_per_conversion),
+            total_budget: self.total_budget.or(campaign.total_budget),
+            daily_budget: self.daily_budget.or(campaign.daily_budget),
+            ad_title: self.ad_title.clone().unwrap_or(campaign.ad_title),
+            ad_text: self.ad_text.clone().unwrap_or(campaign.ad_text),
+            start_date: self.start_date.unwrap_or(campaign.start_date),
+            end_date: self.end_date.unwrap_or(campaign.end_date),
+            targeting: self.targeting.clone().unwrap_or(campaign.targeting),
+            segment_id: self.segment_id.clone().or(campaign.segment_id),
+            frequency_cap: self.frequency_cap.clone().or(campaign.frequency_cap),
+            ..campaign
+        }
+    }
+
+    /// Названия полей, которые изменяет обновление.
+    pub fn fields(&self) -> Vec<String> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(fields)) => fields.keys().cloned().collect(),
+            _ => vec![],
+        }
+    }
 }
 
 impl From<CampaignUpdate> for DBCampaignUpdate {
@@ -211,11 +528,20 @@ impl From<CampaignUpdate> for DBCampaignUpdate {
             clicks_limit: dto.clicks_limit,
             cost_per_impression: dto.cost_per_impression.map(|c| c.round_to_digits(2)),
             cost_per_click: dto.cost_per_click.map(|c| c.round_to_digits(2)),
+            cost_per_conversion: dto.cost_per_conversion.map(|c| c.round_to_digits(2)),
+            total_budget: dto.total_budget.map(|b| b.round_to_digits(2)),
+            daily_budget: dto.daily_budget.map(|b| b.round_to_digits(2)),
             ad_title: dto.ad_title,
             ad_text: dto.ad_text,
             start_date: dto.start_date,
             end_date: dto.end_date,
-            targeting: dto.targeting.map(DBTargeting::from),
+            // При привязке сегмента собственный таргетинг кампании сбрасывается.
+            targeting: match dto.segment_id {
+                Some(_) => Some(DBTargeting::default()),
+                None => dto.targeting.map(DBTargeting::from),
+            },
+            segment_id: dto.segment_id.as_ref().map(DBSegment::record_id_from_uuid),
+            frequency_cap: dto.frequency_cap.map(DBFrequencyCap::from),
         }
     }
 }
@@ -227,6 +553,52 @@ impl From<Targeting> for DBTargeting {
             age_from: dto.age_from,
             age_to: dto.age_to,
             location: dto.location,
+            locations: dto.locations,
+            excluded_locations: dto.excluded_locations,
+            age_ranges: dto
+                .age_ranges
+                .map(|ranges| ranges.into_iter().map(DBAgeRange::from).collect()),
+            allowed_clients: dto
+                .allowed_clients
+                .map(|clients| clients.iter().map(DBClient::record_id_from_uuid).collect()),
+            denied_clients: dto
+                .denied_clients
+                .map(|clients| clients.iter().map(DBClient::record_id_from_uuid).collect()),
+            rules: dto
+                .rules
+                .map(|rules| rules.into_iter().map(DBTargetingRule::from).collect()),
+        }
+    }
+}
+
+impl From<TargetingRule> for DBTargetingRule {
+    fn from(dto: TargetingRule) -> Self {
+        Self {
+            attribute: dto.attribute,
+            op: dto.op,
+            value: dto.value,
+            values: dto.values,
+            min: dto.min,
+            max: dto.max,
+        }
+    }
+}
+
+impl From<AgeRange> for DBAgeRange {
+    fn from(dto: AgeRange) -> Self {
+        Self {
+            age_from: dto.age_from,
+            age_to: dto.age_to,
+        }
+    }
+}
+
+impl From<FrequencyCap> for DBFrequencyCap {
+    fn from(dto: FrequencyCap) -> Self {
+        Self {
+            per_day: dto.per_day,
+            lifetime: dto.lifetime,
+            min_days_between: dto.min_days_between,
         }
     }
 }
@@ -240,12 +612,24 @@ impl From<Campaign> for DBCampaign {
             clicks_limit: dto.inner.clicks_limit,
             cost_per_impression: dto.inner.cost_per_impression.round_to_digits(2),
             cost_per_click: dto.inner.cost_per_click.round_to_digits(2),
+            cost_per_conversion: dto.inner.cost_per_conversion.map(|c| c.round_to_digits(2)),
+            total_budget: dto.inner.total_budget.map(|b| b.round_to_digits(2)),
+            daily_budget: dto.inner.daily_budget.map(|b| b.round_to_digits(2)),
             ad_title: dto.inner.ad_title,
             ad_text: dto.inner.ad_text,
             start_date: dto.inner.start_date,
             end_date: dto.inner.end_date,
             targeting: DBTargeting::from(dto.inner.targeting),
+            segment_id: dto
+                .inner
+                .segment_id
+                .as_ref()
+                .map(DBSegment::record_id_from_uuid),
+            frequency_cap: dto.inner.frequency_cap.map(DBFrequencyCap::from),
+            state: dto.state,
+            deleted_at: None,
             is_active: false,
+            score: 0.0,
         }
     }
 }
@@ -3,9 +3,9 @@ use surrealdb::{RecordId, Result};
 use surrealdb_helper::SurrealDB;
 use uuid::Uuid;
 
-use crate::models::{dto::Client, Gender};
+use crate::models::{dto::Client, Attributes, Gender};
 
-use super::DBModel;
+use super::{DBAudit, DBModel};
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DBClient {
@@ -14,6 +14,9 @@ pub struct DBClient {
     pub age: i32,
     pub location: String,
     pub gender: Gender,
+
+    #[serde(default)]
+    pub attributes: Attributes,
 }
 
 impl DBModel for DBClient {
@@ -25,7 +28,7 @@ impl DBClient {
         Ok(db.0.select(Self::record_id_from_uuid(&id)).await?)
     }
 
-    pub async fn bulk(clients: Vec<Client>, db: &SurrealDB) -> Result<Vec<Self>> {
+    pub async fn bulk(clients: Vec<Client>, audit: DBAudit, db: &SurrealDB) -> Result<Vec<Self>> {
         Ok(db
             .0
             .query("BEGIN")
@@ -47,8 +50,11 @@ impl DBClient {
                 clients.iter().map(Self::from).collect::<Vec<Self>>(),
             ))
             .bind(("client_table", Self::TABLE))
+            .query("fn::audit($audit)")
+            .bind(("audit", audit))
             .query("COMMIT")
             .await?
+            .check()?
             .take(1)?)
     }
 }
@@ -61,6 +67,7 @@ impl From<&Client> for DBClient {
             age: dto.age,
             location: dto.location.clone(),
             gender: dto.gender.clone(),
+            attributes: dto.attributes.clone(),
         }
     }
 }
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::dto::Creative;
+
+use super::{stats::DBStatsBasic, DBAudit, DBCampaign, DBModel};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DBCreative {
+    pub id: RecordId,
+    pub campaign_id: RecordId,
+    pub ad_title: String,
+    pub ad_text: String,
+
+    /// Статистика креатива, заполняется событиями показов и переходов.
+    #[serde(skip_serializing)]
+    pub stats: Option<DBStatsBasic>,
+}
+
+impl DBModel for DBCreative {
+    const TABLE: &str = "creative";
+}
+
+impl DBCreative {
+    pub async fn create(creative: Creative, audit: DBAudit, db: &SurrealDB) -> Result<Self> {
+        Ok(db
+            .0
+            .query("BEGIN")
+            .query("CREATE ONLY type::table($creative_table) CONTENT $creative")
+            .query("fn::audit($audit)")
+            .query("COMMIT")
+            .bind(("creative_table", Self::TABLE))
+            .bind(("creative", Self::from(creative)))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take::<Option<Self>>(0)?
+            .unwrap())
+    }
+
+    /// Возвращает креативы кампании. Если `limit` не указан, возвращаются все креативы.
+    pub async fn list(
+        campaign_id: Uuid,
+        limit: Option<i64>,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        let limit_clause = match limit {
+            Some(_) => "LIMIT <number>($limit)",
+            None => "",
+        };
+
+        Ok(db
+            .0
+            .query(format!(
+                r#"
+                    SELECT * FROM type::table($creative_table)
+                        WHERE campaign_id = type::thing($campaign_table, $campaign_id)
+                        ORDER BY id
+                        {limit_clause}
+                        START <number>($offset)
+                "#
+            ))
+            .bind(json!({
+                "creative_table": Self::TABLE,
+                "campaign_table": DBCampaign::TABLE,
+                "campaign_id": campaign_id,
+                "limit": limit,
+                "offset": offset
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn get(campaign_id: Uuid, id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM ONLY type::thing($creative_table, $creative_id)
+                        WHERE campaign_id = type::thing($campaign_table, $campaign_id)
+                "#,
+            )
+            .bind(json!({
+                "creative_table": Self::TABLE,
+                "creative_id": id,
+                "campaign_table": DBCampaign::TABLE,
+                "campaign_id": campaign_id,
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn delete(
+        campaign_id: Uuid,
+        id: Uuid,
+        audit: DBAudit,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    BEGIN TRANSACTION;
+                    LET $creative = (
+                        DELETE type::thing($creative_table, $creative_id)
+                            WHERE campaign_id = type::thing($campaign_table, $campaign_id)
+                            RETURN BEFORE
+                    )[0];
+                    IF $creative != NONE { fn::audit($audit) };
+                    RETURN $creative;
+                    COMMIT TRANSACTION;
+                "#,
+            )
+            .bind(json!({
+                "creative_table": Self::TABLE,
+                "creative_id": id,
+                "campaign_table": DBCampaign::TABLE,
+                "campaign_id": campaign_id,
+            }))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take(2)?)
+    }
+}
+
+impl From<Creative> for DBCreative {
+    fn from(dto: Creative) -> Self {
+        Self {
+            id: Self::record_id_from_uuid(&dto.id),
+            campaign_id: DBCampaign::record_id_from_uuid(&dto.campaign_id),
+            ad_title: dto.inner.ad_title,
+            ad_text: dto.inner.ad_text,
+            stats: None,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Передача денежных значений в базу данных без потери точности.
+//!
+//! Значения записываются как `decimal` SurrealDB, а не как `f64`, поэтому схема
+//! таблиц не приводит их через число с плавающей точкой. При чтении база данных
+//! отдаёт `decimal` строкой, которая разбирается без округления.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use surrealdb::sql::Number;
+
+pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Number::Decimal(*value).serialize(serializer)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    <Decimal as Deserialize>::deserialize(deserializer)
+}
+
+pub mod option {
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<Decimal>::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Serialize};
+    use surrealdb::sql::{from_value, to_value, Number, Value};
+
+    #[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+    struct Price {
+        #[serde(with = "super")]
+        amount: Decimal,
+
+        #[serde(with = "super::option")]
+        limit: Option<Decimal>,
+    }
+
+    #[rstest]
+    #[case(Decimal::new(1, 1), None)]
+    #[case(Decimal::new(1_000_000_000_000_001, 2), Some(Decimal::new(333, 2)))]
+    fn round_trip(#[case] amount: Decimal, #[case] limit: Option<Decimal>) {
+        let price = Price { amount, limit };
+
+        let value = to_value(price.clone()).unwrap();
+        assert_eq!(
+            value.pick(&["amount".into()]),
+            Value::Number(Number::Decimal(amount))
+        );
+        assert_eq!(from_value::<Price>(value).unwrap(), price);
+    }
+}
@@ -0,0 +1,26 @@
+use serde::Deserialize;
+use surrealdb::Result;
+use surrealdb_helper::SurrealDB;
+
+use super::DBTargeting;
+
+#[derive(Deserialize, Debug)]
+pub struct DBForecast {
+    pub clients_count: u64,
+    pub clients_total: u64,
+    pub competing_campaigns_count: u64,
+    pub history_days: u64,
+    pub history_impressions: u64,
+    pub history_clicks: u64,
+}
+
+impl DBForecast {
+    pub async fn for_targeting(targeting: DBTargeting, db: &SurrealDB) -> Result<Self> {
+        let forecast: Option<Self> =
+            db.0.query("RETURN fn::targeting_forecast($targeting)")
+                .bind(("targeting", targeting))
+                .await?
+                .take(0)?;
+        Ok(forecast.unwrap())
+    }
+}
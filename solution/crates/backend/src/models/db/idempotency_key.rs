@@ -0,0 +1,145 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use surrealdb::Result;
+use surrealdb_helper::SurrealDB;
+
+use super::DBModel;
+
+/// Ключ идемпотентности. Запись идентифицируется владельцем ключа API,
+/// методом и самим ключом и хранит хэш запроса и ответ на него.
+pub struct DBIdempotencyKey;
+
+impl DBModel for DBIdempotencyKey {
+    const TABLE: &str = "idempotency_key";
+}
+
+/// Состояние запроса, уже выполненного с тем же ключом идемпотентности.
+#[derive(Deserialize, Debug)]
+pub struct DBIdempotentRequest {
+    /// Совпадает ли тело запроса с телом первого запроса.
+    pub same_request: bool,
+    /// Код ответа. Не указывается, пока первый запрос ещё выполняется.
+    pub status: Option<u16>,
+    pub body: Option<String>,
+}
+
+impl DBIdempotencyKey {
+    /// Резервирует ключ за запросом на `lease_seconds`. Если ключ уже использован
+    /// и не истёк, возвращает состояние запроса, которому он принадлежит.
+    pub async fn reserve(
+        id: [&str; 3],
+        request: &Value,
+        lease_seconds: i32,
+        db: &SurrealDB,
+    ) -> Result<Option<DBIdempotentRequest>> {
+        let reserved =
+            db.0.query(
+                r#"
+                    RETURN fn::reserve_idempotency_key(
+                        type::thing($idempotency_key_table, $id),
+                        $request,
+                        <duration> $lease
+                    )
+                "#,
+            )
+            .bind(json!({
+                "idempotency_key_table": Self::TABLE,
+                "id": id,
+                "request": request,
+                "lease": format!("{lease_seconds}s")
+            }))
+            .await?
+            .check();
+
+        match reserved {
+            Ok(_) => Ok(None),
+            Err(err) if Self::is_taken(&err) => {
+                // Ключ мог быть освобождён запросом, который его занял, но до этого
+                // повторять запрос всё равно нельзя — считаем, что он ещё выполняется.
+                Ok(Some(Self::request(id, request, db).await?.unwrap_or(
+                    DBIdempotentRequest {
+                        same_request: true,
+                        status: None,
+                        body: None,
+                    },
+                )))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Возвращает состояние запроса, которому принадлежит ключ.
+    async fn request(
+        id: [&str; 3],
+        request: &Value,
+        db: &SurrealDB,
+    ) -> Result<Option<DBIdempotentRequest>> {
+        db.0.query(
+            "RETURN fn::idempotent_request(type::thing($idempotency_key_table, $id), $request)",
+        )
+        .bind(json!({
+            "idempotency_key_table": Self::TABLE,
+            "id": id,
+            "request": request
+        }))
+        .await?
+        .take(0)
+    }
+
+    /// Проверяет, что ключ не удалось занять, потому что его уже занял
+    /// другой запрос: запись уже существует или параллельная транзакция
+    /// записала её раньше.
+    fn is_taken(err: &surrealdb::Error) -> bool {
+        let message = err.to_string();
+        message.contains("already exists") || message.contains("read or write conflict")
+    }
+
+    /// Удаляет ключи, срок хранения которых истёк.
+    pub async fn purge_expired(db: &SurrealDB) -> Result<()> {
+        db.0.query("DELETE type::table($idempotency_key_table) WHERE expires_at <= time::now()")
+            .bind(json!({ "idempotency_key_table": Self::TABLE }))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    /// Сохраняет ответ на запрос и продлевает ключ на `ttl_seconds`.
+    pub async fn store_response(
+        id: [&str; 3],
+        status: u16,
+        body: Option<String>,
+        ttl_seconds: i32,
+        db: &SurrealDB,
+    ) -> Result<()> {
+        db.0.query(
+            r#"
+                UPDATE ONLY type::thing($idempotency_key_table, $id)
+                    SET status = $status,
+                        body = $body ?? NONE,
+                        expires_at = time::now() + <duration> $ttl
+            "#,
+        )
+        .bind(json!({
+            "idempotency_key_table": Self::TABLE,
+            "id": id,
+            "status": status,
+            "body": body,
+            "ttl": format!("{ttl_seconds}s")
+        }))
+        .await?
+        .check()?;
+        Ok(())
+    }
+
+    /// Освобождает ключ, если запрос завершился ошибкой, чтобы его можно было повторить.
+    pub async fn release(id: [&str; 3], db: &SurrealDB) -> Result<()> {
+        db.0.query("DELETE type::thing($idempotency_key_table, $id)")
+            .bind(json!({
+                "idempotency_key_table": Self::TABLE,
+                "id": id
+            }))
+            .await?
+            .check()?;
+        Ok(())
+    }
+}
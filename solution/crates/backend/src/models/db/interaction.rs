@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use surrealdb::{opt::PatchOp, RecordId, Result};
 use surrealdb_helper::SurrealDB;
@@ -17,8 +18,45 @@ pub struct DBInteraction {
 
     pub impressed: i32,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_impressed: Option<i32>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clicked: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted: Option<i32>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub conversion_value: Option<Decimal>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub impression_price: Option<Decimal>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub click_price: Option<Decimal>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::db::decimal::option"
+    )]
+    pub conversion_price: Option<Decimal>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creative_id: Option<RecordId>,
 }
 impl DBModel for DBInteraction {
     const TABLE: &str = "interacted_with";
@@ -29,6 +67,9 @@ impl DBInteraction {
     pub async fn create(
         client_id: Uuid,
         campaign_id: RecordId,
+        impression_price: Decimal,
+        click_price: Decimal,
+        creative_id: Option<RecordId>,
         db: &SurrealDB,
     ) -> Result<Option<Self>> {
         let interaction = Self {
@@ -36,12 +77,66 @@ impl DBInteraction {
             client_id: DBClient::record_id_from_uuid(&client_id),
             campaign_id,
             impressed: -1,
+            last_impressed: None,
             clicked: None,
+            converted: None,
+            conversion_value: None,
+            impression_price: Some(impression_price),
+            click_price: Some(click_price),
+            conversion_price: None,
+            creative_id,
         };
 
         Ok(db.0.insert(&interaction.id).relation(interaction).await?)
     }
 
+    /// Фиксирует показ объявления клиенту: создает связь при первом показе
+    /// и увеличивает счетчики показов при повторных. Цены показа и перехода,
+    /// определенные аукционом, сохраняются на связи и списываются событиями.
+    /// Списывается и учитывается в статистике только первый показ, повторные
+    /// показы учитываются только ограничениями частоты показов.
+    /// Показанный креатив и цены сохраняются при первом показе и не меняются
+    /// повторными, поэтому переход засчитывается креативу учтённого показа.
+    pub async fn record_impression(
+        client_id: Uuid,
+        campaign_id: RecordId,
+        impression_price: Decimal,
+        click_price: Decimal,
+        creative_id: Option<RecordId>,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        let id = Self::relation_id(client_id, DBCampaign::record_id_to_uuid(&campaign_id));
+        let existing: Option<Self> = db.0.select(&id).await?;
+        if existing.is_none() {
+            return Self::create(
+                client_id,
+                campaign_id,
+                impression_price,
+                click_price,
+                creative_id,
+                db,
+            )
+            .await;
+        }
+
+        Ok(db
+            .0
+            .query(
+                r#"
+                    UPDATE ONLY $interaction
+                        SET
+                            impressions_today = IF last_impressed == fn::current_day() {
+                                impressions_today + 1
+                            } ELSE {1},
+                            last_impressed = fn::current_day(),
+                            impressions_count += 1
+                "#,
+            )
+            .bind(("interaction", id))
+            .await?
+            .take(0)?)
+    }
+
     pub async fn get(client_id: Uuid, campaign_id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
         Ok(db
             .0
@@ -60,4 +155,31 @@ impl DBInteraction {
             .patch(PatchOp::replace("clicked", -1))
             .await?)
     }
+
+    pub async fn add_converted(
+        client_id: Uuid,
+        campaign_id: Uuid,
+        conversion_value: Option<Decimal>,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    UPDATE ONLY $interaction
+                        SET
+                            conversion_price = IF converted == NONE {
+                                out.cost_per_conversion ?? 0dec
+                            } ELSE { conversion_price },
+                            converted = -1,
+                            conversion_value = IF $conversion_value != NONE {
+                                <decimal> $conversion_value
+                            }
+                "#,
+            )
+            .bind(("interaction", Self::relation_id(client_id, campaign_id)))
+            .bind(("conversion_value", conversion_value))
+            .await?
+            .take(0)?)
+    }
 }
@@ -4,7 +4,7 @@ use surrealdb_helper::SurrealDB;
 
 use crate::models::dto::MLScore;
 
-use super::{DBAdvertiser, DBClient, DBModel, DBRelation};
+use super::{DBAdvertiser, DBAudit, DBClient, DBModel, DBRelation};
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DBMLScore {
@@ -24,9 +24,10 @@ impl DBModel for DBMLScore {
 impl DBRelation for DBMLScore {}
 
 impl DBMLScore {
-    pub async fn upsert(score: MLScore, db: &SurrealDB) -> Result<Self> {
+    pub async fn upsert(score: MLScore, audit: DBAudit, db: &SurrealDB) -> Result<Self> {
         let score = Self::from(score);
         let result: Option<Self> = db.0
+            .query("BEGIN")
             .query(
                 r#"
                     RELATE ONLY (type::record($advertiser_id))->(type::record($score_id))->(type::record($client_id))
@@ -37,7 +38,11 @@ impl DBMLScore {
             .bind(("client_id", score.client_id))
             .bind(("score_id", score.id))
             .bind(("score", score.score))
+            .query("fn::audit($audit)")
+            .bind(("audit", audit))
+            .query("COMMIT")
             .await?
+            .check()?
             .take(0)?;
         Ok(result.unwrap())
     }
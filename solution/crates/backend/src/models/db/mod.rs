@@ -1,19 +1,39 @@
 use surrealdb::RecordId;
 use uuid::Uuid;
 
+mod ad_decision;
 mod advertiser;
+mod api_key;
+mod audit;
+mod billing;
 mod campaign;
 mod client;
+mod creative;
+mod decimal;
+mod forecast;
+mod idempotency_key;
 mod interaction;
 mod ml_score;
+mod segment;
 pub mod stats;
 mod time;
 
+pub use ad_decision::{DBAdDecision, DBAdPredicates, DBScoreBreakdown};
 pub use advertiser::DBAdvertiser;
-pub use campaign::{DBCampaign, DBTargeting};
+pub use api_key::DBApiKey;
+pub use audit::{DBAudit, DBAuditEntry, DBAuditRecord};
+pub use billing::{DBBillingAccount, DBInvoice, DBInvoiceLine, DBLedgerEntry};
+pub use campaign::{
+    DBAgeRange, DBCampaign, DBCampaignStateChange, DBCampaignUpdate, DBFrequencyCap, DBTargeting,
+    DBTargetingRule,
+};
 pub use client::DBClient;
+pub use creative::DBCreative;
+pub use forecast::DBForecast;
+pub use idempotency_key::{DBIdempotencyKey, DBIdempotentRequest};
 pub use interaction::DBInteraction;
 pub use ml_score::DBMLScore;
+pub use segment::DBSegment;
 pub use time::DBTime;
 
 pub trait DBModel {
@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::dto::{Segment, SegmentUpdate};
+
+use super::{DBAdvertiser, DBAudit, DBCampaign, DBClient, DBModel, DBTargeting};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DBSegment {
+    pub id: RecordId,
+    pub advertiser_id: RecordId,
+    pub name: String,
+    pub targeting: DBTargeting,
+}
+
+impl DBModel for DBSegment {
+    const TABLE: &str = "segment";
+}
+
+impl DBSegment {
+    pub async fn create(segment: Segment, db: &SurrealDB) -> Result<Self> {
+        let segment = Self::from(segment);
+        Ok(db.0.create(&segment.id).content(segment).await?.unwrap())
+    }
+
+    pub async fn list(
+        advertiser_id: Uuid,
+        limit: i64,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM type::table($segment_table)
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                        LIMIT <number>($limit)
+                        START <number>($offset)
+                "#,
+            )
+            .bind(json!({
+                "segment_table": Self::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "limit": limit,
+                "offset": offset
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn get(advertiser_id: Uuid, id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM ONLY type::thing($segment_table, $segment_id) 
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                "#,
+            )
+            .bind(json!({
+                "segment_table": Self::TABLE,
+                "segment_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn update(
+        advertiser_id: Uuid,
+        id: Uuid,
+        segment_update: SegmentUpdate,
+        audit: DBAudit,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        let segment_update = DBSegmentUpdate::from(segment_update);
+        Ok(db
+            .0
+            .query(
+                r#"
+                    BEGIN TRANSACTION;
+                    LET $segment = (
+                        UPDATE ONLY type::thing($segment_table, $segment_id) 
+                            MERGE $segment_update
+                            WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                            RETURN AFTER
+                    );
+                    IF $segment != NONE { fn::audit($audit) };
+                    RETURN $segment;
+                    COMMIT TRANSACTION;
+                "#,
+            )
+            .bind(json!({
+                "segment_table": Self::TABLE,
+                "segment_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+                "segment_update": segment_update
+            }))
+            .bind(("audit", audit))
+            .await?
+            .check()?
+            .take(2)?)
+    }
+
+    pub async fn delete(advertiser_id: Uuid, id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    DELETE type::thing($segment_table, $segment_id) 
+                        WHERE advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                        RETURN BEFORE
+                "#,
+            )
+            .bind(json!({
+                "segment_table": Self::TABLE,
+                "segment_id": id,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    pub async fn campaigns_count(id: Uuid, db: &SurrealDB) -> Result<i64> {
+        let count: Option<i64> =
+            db.0.query(
+                r#"
+                    SELECT VALUE count() FROM type::table($campaign_table)
+                        WHERE segment_id = $segment
+                        GROUP ALL
+                "#,
+            )
+            .bind(("campaign_table", DBCampaign::TABLE))
+            .bind(("segment", Self::record_id_from_uuid(&id)))
+            .await?
+            .take(0)?;
+        Ok(count.unwrap_or(0))
+    }
+
+    /// Количество кампаний, использующих сегмент, которые уже начались к дню `current_date`.
+    pub async fn started_campaigns_count(
+        id: Uuid,
+        current_date: i32,
+        db: &SurrealDB,
+    ) -> Result<i64> {
+        let count: Option<i64> =
+            db.0.query(
+                r#"
+                    SELECT VALUE count() FROM type::table($campaign_table)
+                        WHERE segment_id = $segment AND start_date <= $current_date
+                        GROUP ALL
+                "#,
+            )
+            .bind(("campaign_table", DBCampaign::TABLE))
+            .bind(("segment", Self::record_id_from_uuid(&id)))
+            .bind(("current_date", current_date))
+            .await?
+            .take(0)?;
+        Ok(count.unwrap_or(0))
+    }
+
+    pub async fn reach(&self, db: &SurrealDB) -> Result<i64> {
+        let count: Option<i64> =
+            db.0.query(
+                r#"
+                    SELECT VALUE count() FROM type::table($client_table)
+                        WHERE fn::targeting_matches($targeting, id)
+                        GROUP ALL
+                "#,
+            )
+            .bind(("client_table", DBClient::TABLE))
+            .bind(("targeting", self.targeting.clone()))
+            .await?
+            .take(0)?;
+        Ok(count.unwrap_or(0))
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct DBSegmentUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targeting: Option<DBTargeting>,
+}
+
+impl From<SegmentUpdate> for DBSegmentUpdate {
+    fn from(dto: SegmentUpdate) -> Self {
+        Self {
+            name: dto.name,
+            targeting: dto.targeting.map(DBTargeting::from),
+        }
+    }
+}
+
+impl From<Segment> for DBSegment {
+    fn from(dto: Segment) -> Self {
+        Self {
+            id: Self::record_id_from_uuid(&dto.id),
+            advertiser_id: DBAdvertiser::record_id_from_uuid(&dto.advertiser_id),
+            name: dto.inner.name,
+            targeting: DBTargeting::from(dto.inner.targeting),
+        }
+    }
+}
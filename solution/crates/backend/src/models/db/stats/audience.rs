@@ -0,0 +1,132 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::Result;
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::db::{DBAdvertiser, DBCampaign, DBInteraction, DBModel};
+
+use super::DBStatsBasic;
+
+/// Статистика, накопленная взаимодействиями клиентов с одинаковыми полом,
+/// возрастом и местоположением.
+#[derive(Deserialize, Debug)]
+pub struct DBAudienceContribution {
+    pub gender: Option<String>,
+    pub age: Option<i32>,
+    pub location: Option<String>,
+
+    #[serde(default)]
+    pub impressions_count: u64,
+
+    #[serde(default)]
+    pub clicks_count: u64,
+
+    #[serde(default)]
+    pub conversions_count: u64,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub spent_impressions: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub spent_clicks: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub spent_conversions: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub conversion_value: Decimal,
+}
+
+impl DBAudienceContribution {
+    pub async fn list_by_campaign(campaign_id: Uuid, db: &SurrealDB) -> Result<Vec<Self>> {
+        Self::list(
+            "out = type::thing($owner_table, $owner_id)",
+            DBCampaign::TABLE,
+            campaign_id,
+            db,
+        )
+        .await
+    }
+
+    pub async fn list_by_advertiser(advertiser_id: Uuid, db: &SurrealDB) -> Result<Vec<Self>> {
+        Self::list(
+            "out.advertiser_id = type::thing($owner_table, $owner_id)",
+            DBAdvertiser::TABLE,
+            advertiser_id,
+            db,
+        )
+        .await
+    }
+
+    /// Агрегирует взаимодействия по полу, возрасту и местоположению клиентов.
+    /// Цены берутся так же, как при выгрузке взаимодействий: сохранённые на связи,
+    /// а при их отсутствии — текущие цены кампании.
+    async fn list(
+        condition: &str,
+        owner_table: &str,
+        owner_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        let mut response = db
+            .0
+            .query(format!(
+                r#"
+                    SELECT
+                        in.gender AS gender,
+                        in.age AS age,
+                        in.location AS location,
+                        count() AS impressions_count,
+                        math::sum(impression_price ?? out.cost_per_impression) AS spent_impressions
+                    FROM type::table($interaction_table)
+                        WHERE {condition}
+                        GROUP BY gender, age, location;
+                    SELECT
+                        in.gender AS gender,
+                        in.age AS age,
+                        in.location AS location,
+                        count() AS clicks_count,
+                        math::sum(click_price ?? out.cost_per_click) AS spent_clicks
+                    FROM type::table($interaction_table)
+                        WHERE {condition} AND clicked != NONE
+                        GROUP BY gender, age, location;
+                    SELECT
+                        in.gender AS gender,
+                        in.age AS age,
+                        in.location AS location,
+                        count() AS conversions_count,
+                        math::sum(conversion_price ?? out.cost_per_conversion ?? 0dec) AS spent_conversions,
+                        math::sum(conversion_value ?? 0dec) AS conversion_value
+                    FROM type::table($interaction_table)
+                        WHERE {condition} AND converted != NONE
+                        GROUP BY gender, age, location;
+                "#
+            ))
+            .bind(json!({
+                "interaction_table": DBInteraction::TABLE,
+                "owner_table": owner_table,
+                "owner_id": owner_id,
+            }))
+            .await?;
+
+        let mut contributions: Vec<Self> = response.take(0)?;
+        contributions.extend(response.take::<Vec<Self>>(1)?);
+        contributions.extend(response.take::<Vec<Self>>(2)?);
+        Ok(contributions)
+    }
+
+    pub fn stats(&self) -> DBStatsBasic {
+        DBStatsBasic {
+            impressions_count: self.impressions_count,
+            clicks_count: self.clicks_count,
+            conversions_count: self.conversions_count,
+            spent_impressions: self.spent_impressions,
+            spent_clicks: self.spent_clicks,
+            spent_conversions: self.spent_conversions,
+            spent_total: self.spent_impressions + self.spent_clicks + self.spent_conversions,
+            conversion_value: self.conversion_value,
+            ..DBStatsBasic::empty()
+        }
+    }
+}
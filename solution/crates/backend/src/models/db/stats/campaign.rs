@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
-use surrealdb::RecordId;
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
 
-use crate::models::db::DBModel;
+use crate::models::db::{DBAdvertiser, DBModel};
 
 use super::{DBStats, DBStatsModel};
 
@@ -21,3 +24,24 @@ impl DBStatsModel for DBStatsCampaign {
         self.stats.clone()
     }
 }
+
+impl DBStatsCampaign {
+    pub async fn list_by_advertiser(advertiser_id: Uuid, db: &SurrealDB) -> Result<Vec<Self>> {
+        Ok(db
+            .0
+            .query(
+                r#"
+                    SELECT * FROM type::table($stats_table)
+                        WHERE campaign_id.advertiser_id = type::thing($advertiser_table, $advertiser_id)
+                        ORDER BY campaign_id
+                "#,
+            )
+            .bind(json!({
+                "stats_table": Self::TABLE,
+                "advertiser_table": DBAdvertiser::TABLE,
+                "advertiser_id": advertiser_id,
+            }))
+            .await?
+            .take(0)?)
+    }
+}
@@ -0,0 +1,176 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::models::db::{DBAdvertiser, DBCampaign, DBInteraction, DBModel};
+
+use super::DBStatsBasic;
+
+/// Взаимодействие клиента с рекламной кампанией вместе с ценами,
+/// по которым строится выгрузка взаимодействий.
+#[derive(Deserialize, Debug)]
+pub struct DBInteractionStats {
+    pub client_id: RecordId,
+    pub campaign_id: RecordId,
+    pub creative_id: Option<RecordId>,
+
+    /// Хотя бы одна из цен не была сохранена на связи при записи взаимодействия
+    /// и взята из текущих цен кампании.
+    pub prices_estimated: bool,
+
+    pub impressed: i32,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub impression_price: Decimal,
+
+    pub clicked: Option<i32>,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub click_price: Decimal,
+
+    pub converted: Option<i32>,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub conversion_price: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal::option")]
+    pub conversion_value: Option<Decimal>,
+}
+
+impl DBInteractionStats {
+    pub async fn list_by_campaign(
+        campaign_id: Uuid,
+        limit: Option<i64>,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        Self::list(
+            "out = type::thing($owner_table, $owner_id)",
+            DBCampaign::TABLE,
+            campaign_id,
+            limit,
+            offset,
+            db,
+        )
+        .await
+    }
+
+    pub async fn list_by_advertiser(
+        advertiser_id: Uuid,
+        limit: Option<i64>,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        Self::list(
+            "out.advertiser_id = type::thing($owner_table, $owner_id)",
+            DBAdvertiser::TABLE,
+            advertiser_id,
+            limit,
+            offset,
+            db,
+        )
+        .await
+    }
+
+    async fn list(
+        condition: &str,
+        owner_table: &str,
+        owner_id: Uuid,
+        limit: Option<i64>,
+        offset: i64,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>> {
+        let limit_clause = match limit {
+            Some(_) => "LIMIT <number>($limit)",
+            None => "",
+        };
+
+        Ok(db
+            .0
+            .query(format!(
+                r#"
+                    SELECT
+                        id,
+                        in AS client_id,
+                        out AS campaign_id,
+                        creative_id,
+                        (
+                            impression_price == NONE
+                                || (clicked != NONE && click_price == NONE)
+                                || (converted != NONE && conversion_price == NONE)
+                        ) AS prices_estimated,
+                        impressed,
+                        impression_price ?? out.cost_per_impression AS impression_price,
+                        clicked,
+                        click_price ?? out.cost_per_click AS click_price,
+                        converted,
+                        conversion_price ?? out.cost_per_conversion ?? 0dec AS conversion_price,
+                        conversion_value
+                    FROM type::table($interaction_table)
+                        WHERE {condition}
+                        ORDER BY id
+                        {limit_clause}
+                        START <number>($offset)
+                "#
+            ))
+            .bind(json!({
+                "interaction_table": DBInteraction::TABLE,
+                "owner_table": owner_table,
+                "owner_id": owner_id,
+                "limit": limit,
+                "offset": offset
+            }))
+            .await?
+            .take(0)?)
+    }
+
+    /// Статистика по одному взаимодействию в том виде, в котором её
+    /// накапливают события `impressed`, `clicked` и `converted`.
+    pub fn stats(&self) -> DBStatsBasic {
+        DBStatsBasic::sum(self.daily_stats().iter().map(|(_, stats)| stats))
+    }
+
+    /// Статистика по одному взаимодействию, разбитая по дням, в которые она была
+    /// накоплена. Списывается и учитывается только первый показ.
+    pub fn daily_stats(&self) -> Vec<(i32, DBStatsBasic)> {
+        let mut daily = vec![(
+            self.impressed,
+            DBStatsBasic {
+                impressions_count: 1,
+                spent_impressions: self.impression_price,
+                spent_total: self.impression_price,
+                ..DBStatsBasic::empty()
+            },
+        )];
+
+        if let Some(clicked) = self.clicked {
+            daily.push((
+                clicked,
+                DBStatsBasic {
+                    clicks_count: 1,
+                    spent_clicks: self.click_price,
+                    spent_total: self.click_price,
+                    ..DBStatsBasic::empty()
+                },
+            ));
+        }
+
+        if let Some(converted) = self.converted {
+            daily.push((
+                converted,
+                DBStatsBasic {
+                    conversions_count: 1,
+                    spent_conversions: self.conversion_price,
+                    spent_total: self.conversion_price,
+                    conversion_value: self.conversion_value.unwrap_or(Decimal::ZERO),
+                    ..DBStatsBasic::empty()
+                },
+            ));
+        }
+
+        daily
+    }
+}
@@ -1,16 +1,25 @@
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use surrealdb::Result;
+use surrealdb::{RecordId, Result};
 use surrealdb_helper::SurrealDB;
 use uuid::Uuid;
 
+use crate::{models::dto::Time, utils::RoundToDigits};
+
 use super::DBModel;
 
 mod advertiser;
+mod audience;
 mod campaign;
+mod interaction;
+mod snapshot;
 
 pub use advertiser::DBStatsAdvertiser;
+pub use audience::DBAudienceContribution;
 pub use campaign::DBStatsCampaign;
+pub use interaction::DBInteractionStats;
+pub use snapshot::DBStatsSnapshot;
 
 #[async_trait]
 pub trait DBStatsModel: DBModel + DeserializeOwned {
@@ -20,9 +29,12 @@ pub trait DBStatsModel: DBModel + DeserializeOwned {
         self.stats().total
     }
 
-    fn daily(&self) -> Vec<DBStatsBasic> {
+    fn daily(&self) -> Vec<DBStatsDaily> {
         let stats = self.stats();
-        let current = stats.current;
+        let current = DBStatsDaily {
+            date: Some(Time::get().current_date),
+            stats: stats.current,
+        };
         let mut daily = stats.daily;
         daily.push(current);
         daily
@@ -31,13 +43,55 @@ pub trait DBStatsModel: DBModel + DeserializeOwned {
     async fn get(id: Uuid, db: &SurrealDB) -> Result<Option<Self>> {
         db.0.select(Self::record_id_from_uuid(&id)).await
     }
+
+    /// Заменяет статистику, если с момента чтения `before` в неё не были записаны
+    /// новые взаимодействия и не был завершён день. Иначе возвращает `None`.
+    async fn replace_if_unchanged(
+        id: &RecordId,
+        before: &DBStats,
+        stats: DBStats,
+        db: &SurrealDB,
+    ) -> Result<Option<Self>> {
+        let mut response =
+            db.0.query(
+                r#"
+                    UPDATE $id MERGE $stats
+                        WHERE array::len(daily) == $daily_len
+                            AND current.impressions_count == $current.impressions_count
+                            AND current.clicks_count == $current.clicks_count
+                            AND current.conversions_count == $current.conversions_count
+                            AND total.impressions_count == $total.impressions_count
+                            AND total.clicks_count == $total.clicks_count
+                            AND total.conversions_count == $total.conversions_count
+                "#,
+            )
+            .bind(("id", id.clone()))
+            .bind(("stats", stats))
+            .bind(("daily_len", before.daily.len()))
+            .bind(("current", before.current.clone()))
+            .bind(("total", before.total.clone()))
+            .await?;
+
+        let updated: Vec<Self> = response.take(0)?;
+        Ok(updated.into_iter().next())
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DBStats {
-    current: DBStatsBasic,
-    total: DBStatsBasic,
-    daily: Vec<DBStatsBasic>,
+    pub current: DBStatsBasic,
+    pub total: DBStatsBasic,
+    pub daily: Vec<DBStatsDaily>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DBStatsDaily {
+    /// День, за который собрана статистика. Отсутствует у записей, накопленных
+    /// до того, как дни стали сохраняться.
+    pub date: Option<i32>,
+
+    #[serde(flatten)]
+    pub stats: DBStatsBasic,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -45,7 +99,68 @@ pub struct DBStatsBasic {
     pub impressions_count: u64,
     pub clicks_count: u64,
     pub conversion: f32,
-    pub spent_impressions: f64,
-    pub spent_clicks: f64,
-    pub spent_total: f64,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub spent_impressions: Decimal,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub spent_clicks: Decimal,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub spent_conversions: Decimal,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub spent_total: Decimal,
+
+    pub conversions_count: u64,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub conversion_value: Decimal,
+
+    #[serde(with = "crate::models::db::decimal")]
+    pub cost_per_acquisition: Decimal,
+}
+
+impl DBStatsBasic {
+    pub fn empty() -> Self {
+        Self {
+            impressions_count: 0,
+            clicks_count: 0,
+            conversion: 0.0,
+            spent_impressions: Decimal::ZERO,
+            spent_clicks: Decimal::ZERO,
+            spent_conversions: Decimal::ZERO,
+            spent_total: Decimal::ZERO,
+            conversions_count: 0,
+            conversion_value: Decimal::ZERO,
+            cost_per_acquisition: Decimal::ZERO,
+        }
+    }
+
+    /// Суммирует статистику и пересчитывает производные показатели
+    /// так же, как это делает `fn::update_stats`.
+    pub fn sum<'a>(stats: impl IntoIterator<Item = &'a Self>) -> Self {
+        let mut sum = Self::empty();
+        for stats in stats {
+            sum.impressions_count += stats.impressions_count;
+            sum.clicks_count += stats.clicks_count;
+            sum.spent_impressions += stats.spent_impressions;
+            sum.spent_clicks += stats.spent_clicks;
+            sum.spent_conversions += stats.spent_conversions;
+            sum.spent_total += stats.spent_total;
+            sum.conversions_count += stats.conversions_count;
+            sum.conversion_value += stats.conversion_value;
+        }
+
+        let conversion = (sum.clicks_count * 100) as f32 / sum.impressions_count as f32;
+        if (0.0..=100.0).contains(&conversion) {
+            sum.conversion = conversion.round_to_digits(2);
+        }
+        if sum.conversions_count > 0 {
+            sum.cost_per_acquisition =
+                (sum.spent_total / Decimal::from(sum.conversions_count)).round_to_digits(2);
+        }
+
+        sum
+    }
 }
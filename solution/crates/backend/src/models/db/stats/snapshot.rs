@@ -0,0 +1,135 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::{RecordId, Result};
+use surrealdb_helper::SurrealDB;
+
+use crate::models::db::{DBInteraction, DBModel};
+
+use super::{DBStatsAdvertiser, DBStatsBasic, DBStatsCampaign};
+
+/// Статистика, накопленная взаимодействиями с одной кампанией за один день.
+#[derive(Deserialize, Debug)]
+pub struct DBStatsContribution {
+    pub campaign_id: RecordId,
+    pub advertiser_id: RecordId,
+    pub date: i32,
+
+    #[serde(default)]
+    pub impressions_count: u64,
+
+    #[serde(default)]
+    pub clicks_count: u64,
+
+    #[serde(default)]
+    pub conversions_count: u64,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub spent_impressions: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub spent_clicks: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub spent_conversions: Decimal,
+
+    #[serde(default, with = "crate::models::db::decimal")]
+    pub conversion_value: Decimal,
+
+    /// Конверсии, цена которых не была сохранена на связи при их записи.
+    #[serde(default)]
+    pub unpriced_conversions_count: u64,
+}
+
+impl DBStatsContribution {
+    pub fn stats(&self) -> DBStatsBasic {
+        DBStatsBasic {
+            impressions_count: self.impressions_count,
+            clicks_count: self.clicks_count,
+            conversions_count: self.conversions_count,
+            spent_impressions: self.spent_impressions,
+            spent_clicks: self.spent_clicks,
+            spent_conversions: self.spent_conversions,
+            spent_total: self.spent_impressions + self.spent_clicks + self.spent_conversions,
+            conversion_value: self.conversion_value,
+            ..DBStatsBasic::empty()
+        }
+    }
+}
+
+/// Сохранённая статистика и история взаимодействий, прочитанные в одной транзакции.
+#[derive(Debug)]
+pub struct DBStatsSnapshot {
+    pub current_date: i32,
+    pub campaigns: Vec<DBStatsCampaign>,
+    pub advertisers: Vec<DBStatsAdvertiser>,
+    pub contributions: Vec<DBStatsContribution>,
+}
+
+impl DBStatsSnapshot {
+    /// Читает статистику и агрегирует взаимодействия по кампаниям и дням. Показы,
+    /// переходы и конверсии учитываются в дни, когда они произошли, по ценам,
+    /// сохранённым на связи, а если цена не сохранена — по текущей цене кампании.
+    /// Взаимодействия окончательно удалённых кампаний берутся из их вклада,
+    /// сохранённого при удалении.
+    pub async fn take(db: &SurrealDB) -> Result<Self> {
+        let mut response =
+            db.0.query(
+                r#"
+                    BEGIN TRANSACTION;
+                    fn::current_day();
+                    SELECT * FROM type::table($stats_campaign_table) ORDER BY id;
+                    SELECT * FROM type::table($stats_advertiser_table) ORDER BY id;
+                    SELECT
+                        out AS campaign_id,
+                        out.advertiser_id AS advertiser_id,
+                        impressed AS date,
+                        count() AS impressions_count,
+                        math::sum(impression_price ?? out.cost_per_impression) AS spent_impressions
+                    FROM type::table($interaction_table)
+                        GROUP BY campaign_id, advertiser_id, date;
+                    SELECT
+                        out AS campaign_id,
+                        out.advertiser_id AS advertiser_id,
+                        clicked AS date,
+                        count() AS clicks_count,
+                        math::sum(click_price ?? out.cost_per_click) AS spent_clicks
+                    FROM type::table($interaction_table)
+                        WHERE clicked != NONE
+                        GROUP BY campaign_id, advertiser_id, date;
+                    SELECT
+                        out AS campaign_id,
+                        out.advertiser_id AS advertiser_id,
+                        converted AS date,
+                        count() AS conversions_count,
+                        math::sum(conversion_price ?? out.cost_per_conversion ?? 0dec) AS spent_conversions,
+                        math::sum(conversion_value ?? 0dec) AS conversion_value,
+                        count(conversion_price == NONE) AS unpriced_conversions_count
+                    FROM type::table($interaction_table)
+                        WHERE converted != NONE
+                        GROUP BY campaign_id, advertiser_id, date;
+                    SELECT * FROM purged_contribution;
+                    COMMIT TRANSACTION;
+                "#,
+            )
+            .bind(json!({
+                "stats_campaign_table": DBStatsCampaign::TABLE,
+                "stats_advertiser_table": DBStatsAdvertiser::TABLE,
+                "interaction_table": DBInteraction::TABLE,
+            }))
+            .await?;
+
+        let current_date: Option<i32> = response.take(0)?;
+        let mut contributions: Vec<DBStatsContribution> = response.take(3)?;
+        contributions.extend(response.take::<Vec<DBStatsContribution>>(4)?);
+        contributions.extend(response.take::<Vec<DBStatsContribution>>(5)?);
+        contributions.extend(response.take::<Vec<DBStatsContribution>>(6)?);
+
+        Ok(Self {
+            current_date: current_date.unwrap_or(1),
+            campaigns: response.take(1)?,
+            advertisers: response.take(2)?,
+            contributions,
+        })
+    }
+}
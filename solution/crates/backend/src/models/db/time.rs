@@ -4,6 +4,8 @@ use surrealdb_helper::SurrealDB;
 
 use crate::models::dto::Time;
 
+use super::DBAudit;
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DBTime {
     pub id: RecordId,
@@ -20,12 +22,18 @@ impl DBTime {
         Ok(db.0.select(Self::DATE_ID()).await?.unwrap_or_default())
     }
 
-    pub async fn upsert(new_date: Time, db: &SurrealDB) -> Result<Self> {
+    pub async fn upsert(new_date: Time, audit: DBAudit, db: &SurrealDB) -> Result<Self> {
         Ok(db
             .0
-            .upsert(Self::DATE_ID())
-            .content(Self::from(new_date))
+            .query("BEGIN")
+            .query("UPSERT ONLY $time.id CONTENT $time")
+            .query("fn::audit($audit)")
+            .query("COMMIT")
+            .bind(("time", Self::from(new_date)))
+            .bind(("audit", audit))
             .await?
+            .check()?
+            .take::<Option<Self>>(0)?
             .unwrap_or_default())
     }
 }
@@ -1,15 +1,22 @@
+use std::collections::HashSet;
+
 use actix_web::{http::StatusCode, HttpResponse};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
 use serde::Serialize;
 use surrealdb_helper::SurrealDB;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    models::db::{DBAdvertiser, DBCampaign, DBInteraction, DBModel},
+    models::{
+        db::{DBAdvertiser, DBCampaign, DBCreative, DBInteraction, DBModel},
+        AttributionWindow, AUCTION_MODE, CREATIVE_SELECTION,
+    },
     routes::ApiError,
+    utils::RoundToDigits,
 };
 
-use super::{Campaign, Client};
+use super::{Campaign, Client, Time};
 
 #[derive(Serialize, ToSchema, Debug)]
 #[schema(
@@ -30,18 +37,124 @@ pub struct Ad {
 
     /// UUID рекламодателя, которому принадлежит объявление.
     advertiser_id: Uuid,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    /// UUID показанного креатива кампании. Отсутствует, если у кампании нет креативов
+    /// и показываются её собственные название и текст.
+    creative_id: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[serde(untagged)]
+#[schema(
+    description = "Рекламное объявление для клиента или, если указан параметр `slots`, массив объявлений."
+)]
+pub enum AdResponse {
+    Single(Ad),
+    Slots(Vec<Ad>),
 }
 
 impl Ad {
     pub async fn get_for_client(client_id: Uuid, db: &SurrealDB) -> Result<HttpResponse, ApiError> {
+        Ok(
+            match Self::get_many_for_client(client_id, 1, false, db)
+                .await?
+                .pop()
+            {
+                None => HttpResponse::NoContent().into(),
+                Some(ad) => HttpResponse::Ok().json(AdResponse::Single(ad)),
+            },
+        )
+    }
+
+    pub async fn get_many_for_client(
+        client_id: Uuid,
+        slots: u32,
+        unique_advertisers: bool,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
         Client::get_by_id(client_id, db).await?;
-        Ok(match DBCampaign::get_ad_for_client(client_id, db).await? {
-            None => HttpResponse::NoContent().into(),
-            Some(campaign) => {
-                DBInteraction::create(client_id, campaign.id.clone(), db).await?;
-                HttpResponse::Ok().json(Self::from(campaign))
-            }
-        })
+
+        // Для аукциона второй цены нужна кампания, следующая за последним слотом
+        let candidates = AUCTION_MODE.candidates(slots);
+
+        // При дедупликации по рекламодателям заранее неизвестно, сколько кампаний придется отбросить
+        let limit = (!unique_advertisers).then_some(candidates);
+        let mut advertisers = HashSet::new();
+        let campaigns: Vec<_> = DBCampaign::get_ads_for_client(client_id, limit, db)
+            .await?
+            .into_iter()
+            .filter(|campaign| {
+                !unique_advertisers
+                    || advertisers.insert(DBAdvertiser::record_id_to_uuid(&campaign.advertiser_id))
+            })
+            .take(candidates as usize)
+            .collect();
+
+        let winners = campaigns.len().min(slots as usize);
+        let mut ads = Vec::with_capacity(winners);
+        for (i, campaign) in campaigns[..winners].iter().enumerate() {
+            let creative = Self::choose_creative(campaign, db).await?;
+
+            let runner_up_score = campaigns.get(i + 1).map(|runner_up| runner_up.score);
+            let price_factor =
+                Decimal::from_f64(AUCTION_MODE.price_factor(campaign.score, runner_up_score))
+                    .unwrap_or(Decimal::ONE);
+            DBInteraction::record_impression(
+                client_id,
+                campaign.id.clone(),
+                (campaign.cost_per_impression * price_factor).round_to_digits(2),
+                (campaign.cost_per_click * price_factor).round_to_digits(2),
+                creative.as_ref().map(|creative| creative.id.clone()),
+                db,
+            )
+            .await?;
+
+            ads.push(Self::from_campaign(campaign, creative));
+        }
+
+        Ok(ads)
+    }
+
+    /// Выбирает креатив кампании для показа согласно `CREATIVE_SELECTION`.
+    async fn choose_creative(
+        campaign: &DBCampaign,
+        db: &SurrealDB,
+    ) -> Result<Option<DBCreative>, ApiError> {
+        let mut creatives =
+            DBCreative::list(DBCampaign::record_id_to_uuid(&campaign.id), None, 0, db).await?;
+        let stats: Vec<_> = creatives
+            .iter()
+            .map(|creative| {
+                creative.stats.as_ref().map_or((0, 0), |stats| {
+                    (stats.impressions_count, stats.clicks_count)
+                })
+            })
+            .collect();
+
+        Ok(CREATIVE_SELECTION
+            .choose(&stats, &mut rand::rng())
+            .map(|i| creatives.swap_remove(i)))
+    }
+
+    fn from_campaign(campaign: &DBCampaign, creative: Option<DBCreative>) -> Self {
+        let (title, text, creative_id) = match creative {
+            Some(creative) => (
+                creative.ad_title,
+                creative.ad_text,
+                Some(DBCreative::record_id_to_uuid(&creative.id)),
+            ),
+            None => (campaign.ad_title.clone(), campaign.ad_text.clone(), None),
+        };
+
+        Self {
+            id: DBCampaign::record_id_to_uuid(&campaign.id),
+            title,
+            text,
+            advertiser_id: DBAdvertiser::record_id_to_uuid(&campaign.advertiser_id),
+            creative_id,
+        }
     }
 
     pub async fn record_click(
@@ -64,20 +177,48 @@ impl Ad {
         };
 
         if interaction.clicked.is_none() {
+            AttributionWindow::Click.check(
+                interaction.last_impressed.unwrap_or(interaction.impressed),
+                Time::get().current_date,
+            )?;
             DBInteraction::add_clicked(client_id, ad_id, db).await?;
         }
 
         Ok(())
     }
-}
 
-impl From<DBCampaign> for Ad {
-    fn from(db: DBCampaign) -> Self {
-        Self {
-            id: DBCampaign::record_id_to_uuid(&db.id),
-            title: db.ad_title,
-            text: db.ad_text,
-            advertiser_id: DBAdvertiser::record_id_to_uuid(&db.advertiser_id),
-        }
+    pub async fn record_conversion(
+        client_id: Uuid,
+        ad_id: Uuid,
+        value: Option<Decimal>,
+        db: &SurrealDB,
+    ) -> Result<(), ApiError> {
+        Client::get_by_id(client_id, db).await?;
+        Campaign::get_by_id_unchecked(ad_id, db).await?;
+
+        let clicked = match DBInteraction::get(client_id, ad_id, db).await? {
+            Some(DBInteraction {
+                clicked: Some(clicked),
+                converted,
+                ..
+            }) => {
+                if converted.is_some() {
+                    return Ok(());
+                }
+                clicked
+            }
+            _ => {
+                return Err(ApiError::Custom {
+                    error: "no_click".into(),
+                    status_code: StatusCode::CONFLICT,
+                    message: "This client haven't clicked this ad yet".into(),
+                })
+            }
+        };
+
+        AttributionWindow::Conversion.check(clicked, Time::get().current_date)?;
+        DBInteraction::add_converted(client_id, ad_id, value, db).await?;
+
+        Ok(())
     }
 }
@@ -0,0 +1,142 @@
+use serde::Serialize;
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::db::{
+        DBAdDecision, DBAdPredicates, DBAdvertiser, DBCampaign, DBModel, DBScoreBreakdown,
+    },
+    routes::ApiError,
+};
+
+use super::Client;
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Объект, объясняющий, почему рекламная кампания была или не была выбрана для показа клиенту."
+)]
+pub struct AdDecision {
+    /// UUID рекламной кампании.
+    pub campaign_id: Uuid,
+
+    /// UUID рекламодателя, которому принадлежит кампания.
+    pub advertiser_id: Uuid,
+
+    /// Проходит ли кампания все условия отбора.
+    pub eligible: bool,
+
+    /// Результаты проверки условий отбора кампании.
+    pub predicates: AdPredicates,
+
+    /// Составляющие скора кампании.
+    pub score: ScoreBreakdown,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Результаты проверки условий отбора рекламной кампании для клиента.")]
+pub struct AdPredicates {
+    /// Кампания помечена как активная.
+    pub is_active: bool,
+
+    /// Пол клиента подходит под таргетинг кампании.
+    pub gender: bool,
+
+    /// Возраст клиента попадает в диапазоны таргетинга кампании.
+    pub age: bool,
+
+    /// Локация клиента подходит под таргетинг кампании.
+    pub location: bool,
+
+    /// Клиент не исключен списками разрешенных и запрещенных клиентов кампании.
+    pub client_lists: bool,
+
+    /// Атрибуты клиента удовлетворяют всем правилам таргетинга кампании.
+    pub rules: bool,
+
+    /// Текущий день попадает в период показа кампании.
+    pub active_window: bool,
+
+    /// Лимит показов кампании ещё не исчерпан.
+    pub impressions_limit: bool,
+
+    /// Бюджет кампании позволяет совершить ещё один показ.
+    pub budget: bool,
+
+    /// Ограничения частоты показов кампании позволяют показать объявление клиенту ещё раз.
+    pub frequency_cap: bool,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Составляющие скора рекламной кампании для клиента.")]
+pub struct ScoreBreakdown {
+    /// Вклад стоимости показа (0, если клиент уже видел объявление).
+    pub impression_term: f64,
+
+    /// Вклад стоимости перехода с учётом ML скора и штрафа за лимит (0, если клиент уже переходил по объявлению).
+    pub click_term: f64,
+
+    /// Нормализованный ML скор пары клиент-рекламодатель.
+    pub ml_score: f64,
+
+    /// Множитель за исчерпанный лимит переходов.
+    pub limit_penalty: f64,
+
+    /// Итоговый скор кампании.
+    pub total: f64,
+}
+
+impl AdDecision {
+    pub async fn explain_for_client(
+        client_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        Client::get_by_id(client_id, db).await?;
+        Ok(DBAdDecision::list_for_client(client_id, db)
+            .await?
+            .into_iter()
+            .map(Self::from)
+            .collect())
+    }
+}
+
+impl From<DBAdDecision> for AdDecision {
+    fn from(db: DBAdDecision) -> Self {
+        Self {
+            campaign_id: DBCampaign::record_id_to_uuid(&db.id),
+            advertiser_id: DBAdvertiser::record_id_to_uuid(&db.advertiser_id),
+            eligible: db.predicates.passed(),
+            predicates: db.predicates.into(),
+            score: db.score.into(),
+        }
+    }
+}
+
+impl From<DBAdPredicates> for AdPredicates {
+    fn from(db: DBAdPredicates) -> Self {
+        Self {
+            is_active: db.is_active,
+            gender: db.gender,
+            age: db.age,
+            location: db.location,
+            client_lists: db.client_lists,
+            rules: db.rules,
+            active_window: db.active_window,
+            impressions_limit: db.impressions_limit,
+            budget: db.budget,
+            frequency_cap: db.frequency_cap,
+        }
+    }
+}
+
+impl From<DBScoreBreakdown> for ScoreBreakdown {
+    fn from(db: DBScoreBreakdown) -> Self {
+        Self {
+            impression_term: db.impression_term,
+            click_term: db.click_term,
+            ml_score: db.ml_score,
+            limit_penalty: db.limit_penalty,
+            total: db.total,
+        }
+    }
+}
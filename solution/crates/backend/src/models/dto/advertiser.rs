@@ -1,3 +1,4 @@
+use actix_web::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use surrealdb_helper::SurrealDB;
 use utoipa::ToSchema;
@@ -5,9 +6,12 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
-    models::db::{DBAdvertiser, DBModel},
+    models::{
+        db::{DBAdvertiser, DBAudit, DBAuditRecord, DBModel},
+        AuditAction, Caller,
+    },
     routes::ApiError,
-    utils::validation::check_profanity,
+    utils::validation::{check_currency_code, check_profanity},
 };
 
 #[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
@@ -20,9 +24,20 @@ pub struct Advertiser {
     #[validate(custom(function = "check_profanity"))]
     /// Название рекламодателя.
     pub name: String,
+
+    #[serde(default = "Advertiser::default_currency")]
+    #[schema(examples("RUB"), default = "RUB")]
+    #[validate(custom(function = "check_currency_code"))]
+    /// Код валюты (ISO 4217), в которой указываются стоимости и бюджеты кампаний рекламодателя.
+    /// Не может быть изменён, если у рекламодателя уже есть кампании или записи журнала списаний.
+    pub currency: String,
 }
 
 impl Advertiser {
+    fn default_currency() -> String {
+        "RUB".into()
+    }
+
     pub async fn get_by_id(advertiser_id: Uuid, db: &SurrealDB) -> Result<Self, ApiError> {
         match DBAdvertiser::get(advertiser_id, db).await? {
             None => Err(ApiError::NotFound(format!(
@@ -33,8 +48,37 @@ impl Advertiser {
         }
     }
 
-    pub async fn upsert(body: Vec<Self>, db: &SurrealDB) -> Result<Vec<Self>, ApiError> {
-        Ok(DBAdvertiser::bulk(body, db)
+    pub async fn upsert(
+        body: Vec<Self>,
+        caller: &Caller,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        // Валюта не сохраняется в деньгах, поэтому её смена пересчитала бы их без конвертации
+        if let Some(id) = DBAdvertiser::currency_locked(&body, db).await?.first() {
+            return Err(ApiError::Custom {
+                error: "currency_locked".into(),
+                status_code: StatusCode::CONFLICT,
+                message: format!(
+                    "Currency of advertiser with UUID `{}` can't be changed: it already has campaigns or ledger entries",
+                    DBAdvertiser::record_id_to_uuid(id)
+                ),
+            });
+        }
+
+        let records = body
+            .iter()
+            .map(|advertiser| {
+                DBAuditRecord::new(
+                    AuditAction::AdvertiserUpserted,
+                    Some(advertiser.id),
+                    Some(advertiser.id),
+                )
+                .with_after(advertiser)
+            })
+            .collect();
+        let audit = DBAudit::new(records, caller);
+
+        Ok(DBAdvertiser::bulk(body, audit, db)
             .await?
             .iter()
             .map(Self::from)
@@ -47,6 +91,7 @@ impl From<&DBAdvertiser> for Advertiser {
         Self {
             id: DBAdvertiser::record_id_to_uuid(&db.id),
             name: db.name.clone(),
+            currency: db.currency.clone(),
         }
     }
 }
@@ -0,0 +1,170 @@
+use rand::{distr::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        db::{DBAdvertiser, DBApiKey, DBModel},
+        url::Pagination,
+        Role,
+    },
+    routes::ApiError,
+    utils::validation::check_platform_role,
+};
+
+use super::Advertiser;
+
+/// Длина случайной части ключа API.
+const API_KEY_LENGTH: usize = 40;
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
+#[schema(description = "Объект для выпуска ключа API платформенной роли.")]
+pub struct CreateApiKey {
+    #[validate(custom(function = "check_platform_role"))]
+    /// Роль владельца ключа: `OPERATOR`, `CLIENT_APP` или `DATA_SCIENCE`.
+    pub role: Role,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Ключ API.")]
+pub struct ApiKey {
+    #[serde(rename = "api_key_id")]
+    /// Уникальный идентификатор ключа (UUID).
+    pub id: Uuid,
+
+    #[schema(nullable)]
+    /// UUID рекламодателя, которому выпущен ключ. Для платформенных ролей не указывается.
+    pub advertiser_id: Option<Uuid>,
+
+    pub role: Role,
+
+    #[schema(examples(1), minimum = 1)]
+    /// День, в который выпущен ключ.
+    pub date: i32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples("adk_8Gq0Xr2VbN1sLmZ4yTf7Ua9KcPw3HdEj6RoYi5Qs"))]
+    /// Сам ключ. Возвращается только при выпуске, передаётся в заголовке
+    /// `Authorization: Bearer <ключ>`.
+    pub key: Option<String>,
+}
+
+impl ApiKey {
+    pub async fn issue(advertiser_id: Uuid, db: &SurrealDB) -> Result<Self, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        Self::create(Some(advertiser_id), Role::Advertiser, db).await
+    }
+
+    pub async fn issue_platform(
+        create_api_key: CreateApiKey,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Self::create(None, create_api_key.role, db).await
+    }
+
+    pub async fn list(
+        advertiser_id: Uuid,
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        Self::list_by_owner(Some(advertiser_id), pagination, db).await
+    }
+
+    pub async fn list_platform(
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        Self::list_by_owner(None, pagination, db).await
+    }
+
+    pub async fn revoke(
+        advertiser_id: Uuid,
+        api_key_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<(), ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        Self::delete(Some(advertiser_id), api_key_id, db).await
+    }
+
+    pub async fn revoke_platform(api_key_id: Uuid, db: &SurrealDB) -> Result<(), ApiError> {
+        Self::delete(None, api_key_id, db).await
+    }
+
+    async fn create(
+        advertiser_id: Option<Uuid>,
+        role: Role,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        let key = Self::generate();
+        let api_key = DBApiKey::create(Uuid::now_v7(), advertiser_id, role, &key, db).await?;
+        Ok(Self {
+            key: Some(key),
+            ..Self::from(api_key)
+        })
+    }
+
+    async fn list_by_owner(
+        advertiser_id: Option<Uuid>,
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        let size: i64 = match pagination.size {
+            Some(size) if size < 57 => size,
+            Some(..) => 57,
+            None => 7,
+        };
+
+        if size == 0 {
+            return Ok(vec![]);
+        }
+
+        let offset: i64 = pagination.page.unwrap_or(0) * size;
+        Ok(DBApiKey::list(advertiser_id, size, offset, db)
+            .await?
+            .into_iter()
+            .map(Self::from)
+            .collect())
+    }
+
+    async fn delete(
+        advertiser_id: Option<Uuid>,
+        api_key_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<(), ApiError> {
+        match DBApiKey::delete(advertiser_id, api_key_id, db).await? {
+            None => Err(ApiError::NotFound(format!(
+                "API key with UUID `{}`",
+                api_key_id
+            ))),
+            Some(..) => Ok(()),
+        }
+    }
+
+    fn generate() -> String {
+        let key: String = rand::rng()
+            .sample_iter(Alphanumeric)
+            .take(API_KEY_LENGTH)
+            .map(char::from)
+            .collect();
+        format!("adk_{key}")
+    }
+}
+
+impl From<DBApiKey> for ApiKey {
+    fn from(db: DBApiKey) -> Self {
+        Self {
+            id: DBApiKey::record_id_to_uuid(&db.id),
+            advertiser_id: db
+                .advertiser_id
+                .as_ref()
+                .map(DBAdvertiser::record_id_to_uuid),
+            role: db.role,
+            date: db.date,
+            key: None,
+        }
+    }
+}
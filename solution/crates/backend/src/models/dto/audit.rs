@@ -0,0 +1,145 @@
+use serde::Serialize;
+use serde_json::Value;
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        db::{DBAdvertiser, DBAudit, DBAuditEntry, DBAuditRecord, DBModel},
+        url::{AuditQuery, Pagination},
+        AuditAction, AuditEntity, Caller, Role,
+    },
+    routes::ApiError,
+};
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Автор изменения, записанного в журнал аудита.")]
+pub struct AuditActor {
+    pub role: Role,
+
+    #[schema(nullable)]
+    /// UUID рекламодателя, если изменение сделано его ключом API.
+    pub advertiser_id: Option<Uuid>,
+
+    #[schema(nullable)]
+    /// UUID ключа API. Для ключа администратора платформы не указывается.
+    pub api_key_id: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Запись журнала аудита.")]
+pub struct AuditEntry {
+    /// Уникальный идентификатор записи (UUID).
+    pub audit_entry_id: Uuid,
+
+    pub action: AuditAction,
+
+    pub entity: AuditEntity,
+
+    #[schema(nullable)]
+    /// UUID изменённой сущности. Для ML скора указывается UUID клиента,
+    /// для текущего дня не указывается.
+    pub entity_id: Option<Uuid>,
+
+    #[schema(nullable)]
+    /// UUID рекламодателя, к данным которого относится изменение.
+    pub advertiser_id: Option<Uuid>,
+
+    pub actor: AuditActor,
+
+    #[schema(examples(1), minimum = 1)]
+    /// День, в который сделано изменение.
+    pub date: i32,
+
+    #[schema(value_type = Option<Object>, nullable)]
+    /// Значения изменённых полей до изменения.
+    pub before: Option<Value>,
+
+    #[schema(value_type = Option<Object>, nullable)]
+    /// Значения изменённых полей после изменения.
+    pub after: Option<Value>,
+}
+
+impl AuditEntry {
+    pub async fn record(
+        caller: &Caller,
+        records: Vec<DBAuditRecord>,
+        db: &SurrealDB,
+    ) -> Result<(), ApiError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        Ok(DBAuditEntry::create(DBAudit::new(records, caller), db).await?)
+    }
+
+    /// Записывает в журнал действие без снимков сущности (например, загрузку изображения).
+    pub async fn record_action(
+        caller: &Caller,
+        action: AuditAction,
+        entity_id: Uuid,
+        advertiser_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<(), ApiError> {
+        let record = DBAuditRecord::new(action, Some(entity_id), Some(advertiser_id));
+        Self::record(caller, vec![record], db).await
+    }
+
+    pub async fn list(
+        query: AuditQuery,
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        let size: i64 = match pagination.size {
+            Some(size) if size < 57 => size,
+            Some(..) => 57,
+            None => 7,
+        };
+
+        if size == 0 {
+            return Ok(vec![]);
+        }
+
+        let offset: i64 = pagination.page.unwrap_or(0) * size;
+        Ok(DBAuditEntry::list(
+            query.entity,
+            query.entity_id,
+            query.advertiser_id,
+            query.from,
+            query.to,
+            size,
+            offset,
+            db,
+        )
+        .await?
+        .into_iter()
+        .map(Self::from)
+        .collect())
+    }
+}
+
+impl From<DBAuditEntry> for AuditEntry {
+    fn from(db: DBAuditEntry) -> Self {
+        Self {
+            audit_entry_id: DBAuditEntry::record_id_to_uuid(&db.id),
+            action: db.action,
+            entity: db.entity,
+            entity_id: db.entity_id,
+            advertiser_id: db
+                .advertiser_id
+                .as_ref()
+                .map(DBAdvertiser::record_id_to_uuid),
+            actor: AuditActor {
+                role: db.actor_role,
+                advertiser_id: db
+                    .actor_advertiser_id
+                    .as_ref()
+                    .map(DBAdvertiser::record_id_to_uuid),
+                api_key_id: db.actor_api_key_id,
+            },
+            date: db.date,
+            before: db.before,
+            after: db.after,
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        db::{
+            DBAdvertiser, DBBillingAccount, DBCampaign, DBInvoice, DBInvoiceLine, DBLedgerEntry,
+            DBModel,
+        },
+        url::{InvoiceQuery, Pagination},
+        LedgerEntryKind,
+    },
+    routes::ApiError,
+    utils::{validation::check_positive_money, RoundToDigits},
+};
+
+use super::Advertiser;
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
+#[schema(description = "Объект для пополнения предоплаченного баланса рекламодателя.")]
+pub struct TopUp {
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(100), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Сумма пополнения в валюте рекламодателя.
+    pub amount: Decimal,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Запись журнала списаний и пополнений рекламодателя.")]
+pub struct LedgerEntry {
+    /// Уникальный идентификатор записи (UUID).
+    pub ledger_entry_id: Uuid,
+
+    /// UUID рекламодателя.
+    pub advertiser_id: Uuid,
+
+    #[schema(nullable)]
+    /// UUID рекламной кампании, за которую произведено списание. Для пополнений не указывается.
+    pub campaign_id: Option<Uuid>,
+
+    pub kind: LedgerEntryKind,
+
+    #[schema(examples(1), minimum = 1)]
+    /// День, в который создана запись.
+    pub date: i32,
+
+    #[serde(with = "rust_decimal::serde::float_option")]
+    #[schema(nullable, examples(1))]
+    /// Цена за единицу (показ, переход или конверсию). Для пополнений не указывается.
+    pub unit_price: Option<Decimal>,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(1))]
+    /// Сумма списания или пополнения.
+    pub amount: Decimal,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Предоплаченный баланс рекламодателя.")]
+pub struct BillingBalance {
+    /// UUID рекламодателя.
+    pub advertiser_id: Uuid,
+
+    /// Работает ли рекламодатель по предоплате. Рекламодатель переходит на предоплату
+    /// после первого пополнения, до этого показы его объявлений балансом не ограничиваются.
+    pub prepaid: bool,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(100))]
+    /// Сумма пополнений за вычетом всех списаний, в том числе сделанных до первого пополнения.
+    pub balance: Decimal,
+
+    #[schema(examples("RUB"))]
+    /// Валюта рекламодателя (код ISO 4217).
+    pub currency: String,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Строка счёта: списания одного типа по одной цене в рамках рекламной кампании."
+)]
+pub struct InvoiceLine {
+    /// UUID рекламной кампании.
+    pub campaign_id: Uuid,
+
+    pub kind: LedgerEntryKind,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(1))]
+    /// Цена за единицу.
+    pub unit_price: Decimal,
+
+    #[schema(examples(10), minimum = 0)]
+    /// Количество оплаченных показов, переходов или конверсий.
+    pub quantity: u64,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(10))]
+    /// Сумма списаний по строке.
+    pub amount: Decimal,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Счёт рекламодателя за период.")]
+pub struct Invoice {
+    /// UUID рекламодателя.
+    pub advertiser_id: Uuid,
+
+    #[schema(examples(1), minimum = 1)]
+    /// Первый день расчётного периода (включительно).
+    pub from: i32,
+
+    #[schema(examples(30), minimum = 1)]
+    /// Последний день расчётного периода (включительно).
+    pub to: i32,
+
+    /// Строки счёта, упорядоченные по кампании, типу списания и цене.
+    pub lines: Vec<InvoiceLine>,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(10))]
+    /// Итоговая сумма списаний за период.
+    pub total: Decimal,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(100))]
+    /// Сумма пополнений баланса за период.
+    pub top_ups: Decimal,
+
+    #[schema(examples("RUB"))]
+    /// Валюта рекламодателя (код ISO 4217), в которой выставлен счёт.
+    pub currency: String,
+}
+
+impl LedgerEntry {
+    pub async fn top_up(
+        advertiser_id: Uuid,
+        top_up: TopUp,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        let amount = top_up.amount.round_to_digits(2);
+        Ok(DBLedgerEntry::top_up(advertiser_id, amount, db)
+            .await?
+            .into())
+    }
+
+    pub async fn list(
+        advertiser_id: Uuid,
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+
+        let size: i64 = match pagination.size {
+            Some(size) if size < 57 => size,
+            Some(..) => 57,
+            None => 7,
+        };
+
+        if size == 0 {
+            return Ok(vec![]);
+        }
+
+        let offset: i64 = pagination.page.unwrap_or(0) * size;
+        Ok(DBLedgerEntry::list(advertiser_id, size, offset, db)
+            .await?
+            .into_iter()
+            .map(Self::from)
+            .collect())
+    }
+}
+
+impl BillingBalance {
+    pub async fn get(advertiser_id: Uuid, db: &SurrealDB) -> Result<Self, ApiError> {
+        let advertiser = Advertiser::get_by_id(advertiser_id, db).await?;
+        let account = DBBillingAccount::get(advertiser_id, db).await?;
+        Ok(Self {
+            advertiser_id,
+            prepaid: account.as_ref().is_some_and(|account| account.prepaid),
+            balance: account.map_or(Decimal::ZERO, |account| account.balance.round_to_digits(2)),
+            currency: advertiser.currency,
+        })
+    }
+}
+
+impl Invoice {
+    pub async fn get(
+        advertiser_id: Uuid,
+        query: &InvoiceQuery,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        let advertiser = Advertiser::get_by_id(advertiser_id, db).await?;
+        let invoice = DBInvoice::get(advertiser_id, query.from, query.to, db).await?;
+        Ok(Self {
+            advertiser_id,
+            from: query.from,
+            to: query.to,
+            lines: invoice.lines.into_iter().map(InvoiceLine::from).collect(),
+            total: invoice.total.round_to_digits(2),
+            top_ups: invoice.top_ups.round_to_digits(2),
+            currency: advertiser.currency,
+        })
+    }
+}
+
+impl From<DBLedgerEntry> for LedgerEntry {
+    fn from(db: DBLedgerEntry) -> Self {
+        Self {
+            ledger_entry_id: DBLedgerEntry::record_id_to_uuid(&db.id),
+            advertiser_id: DBAdvertiser::record_id_to_uuid(&db.advertiser_id),
+            campaign_id: db.campaign_id.as_ref().map(DBCampaign::record_id_to_uuid),
+            kind: db.kind,
+            date: db.date,
+            unit_price: db.unit_price.map(|price| price.round_to_digits(2)),
+            amount: db.amount.round_to_digits(2),
+        }
+    }
+}
+
+impl From<DBInvoiceLine> for InvoiceLine {
+    fn from(db: DBInvoiceLine) -> Self {
+        Self {
+            campaign_id: DBCampaign::record_id_to_uuid(&db.campaign_id),
+            kind: db.kind,
+            unit_price: db.unit_price.round_to_digits(2),
+            quantity: db.quantity,
+            amount: db.amount.round_to_digits(2),
+        }
+    }
+}
@@ -1,17 +1,30 @@
+use actix_web::http::StatusCode;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use surrealdb_helper::SurrealDB;
 use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
 use crate::{
+    config,
     models::{
-        db::{DBAdvertiser, DBCampaign, DBModel, DBTargeting},
+        db::{
+            DBAdvertiser, DBAgeRange, DBAudit, DBAuditRecord, DBCampaign, DBCampaignStateChange,
+            DBCampaignUpdate, DBClient, DBCreative, DBFrequencyCap, DBModel, DBSegment,
+            DBTargeting, DBTargetingRule,
+        },
         url::Pagination,
+        validate_attribute_name, AttributeValue, AuditAction, Caller, CampaignState, RuleOperator,
         TargetingGender,
     },
     routes::ApiError,
-    utils::{validation::check_profanity, RoundToDigits},
+    utils::{
+        minio::Minio,
+        validation::{check_positive_money, check_profanity},
+        RoundToDigits,
+    },
 };
 
 #[derive(PartialEq)]
@@ -21,7 +34,7 @@ pub enum CampaignStatus {
     Ended,
 }
 
-use super::{Advertiser, Time};
+use super::{Advertiser, Segment, Time};
 #[derive(Deserialize, Serialize, ToSchema, Debug)]
 #[schema(description = "Объект, представляющий рекламную кампанию.")]
 pub struct Campaign {
@@ -32,6 +45,11 @@ pub struct Campaign {
     /// UUID рекламодателя, которому принадлежит кампания.
     pub advertiser_id: Uuid,
 
+    #[schema(examples(CampaignState::ACTIVE))]
+    /// Статус кампании, заданный рекламодателем (ACTIVE, PAUSED или ARCHIVED).
+    /// Приостановленные и архивированные кампании не участвуют в показах.
+    pub state: CampaignState,
+
     #[serde(flatten)]
     pub inner: CreateCampaign,
 }
@@ -50,16 +68,51 @@ pub struct CreateCampaign {
     /// Задаёт лимит переходов для рекламного объявления.
     pub clicks_limit: i32,
 
+    #[serde(with = "rust_decimal::serde::float")]
     #[schema(examples(1), exclusive_minimum = 0)]
-    #[validate(range(exclusive_min = 0.0))]
+    #[validate(custom(function = "check_positive_money"))]
     /// Стоимость одного показа объявления.
-    pub cost_per_impression: f64,
+    pub cost_per_impression: Decimal,
 
+    #[serde(with = "rust_decimal::serde::float")]
     #[schema(examples(1), exclusive_minimum = 0)]
-    #[validate(range(exclusive_min = 0.0))]
-
+    #[validate(custom(function = "check_positive_money"))]
     /// Стоимость одного перехода (клика) по объявлению.
-    pub cost_per_click: f64,
+    pub cost_per_click: Decimal,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "rust_decimal::serde::float_option"
+    )]
+    #[schema(nullable, examples(10), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Стоимость одной конверсии (целевого действия после перехода). Если не указана,
+    /// конверсии учитываются в статистике, но не оплачиваются.
+    pub cost_per_conversion: Option<Decimal>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "rust_decimal::serde::float_option"
+    )]
+    #[schema(nullable, examples(100), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Общий бюджет рекламной кампании (в той же валюте, что и стоимость показа и перехода).
+    /// Бюджет ограничивает показы: кампания не показывается, если следующий показ превысит бюджет.
+    /// Переходы и конверсии по уже показанным объявлениям списываются и после этого, поэтому
+    /// расход может превысить бюджет на их стоимость.
+    pub total_budget: Option<Decimal>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "rust_decimal::serde::float_option"
+    )]
+    #[schema(nullable, examples(10), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Дневной бюджет рекламной кампании. Как и общий бюджет, ограничивает только показы.
+    pub daily_budget: Option<Decimal>,
 
     #[validate(custom(function = "check_profanity"))]
     /// Название рекламного объявления.
@@ -79,9 +132,22 @@ pub struct CreateCampaign {
     /// День окончания показа рекламного объявления (включительно).
     pub end_date: i32,
 
+    #[serde(default)]
     #[schema(examples(Targeting::example))]
     #[validate(nested)]
     pub targeting: Targeting,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    /// UUID сегмента аудитории рекламодателя. Если указан, вместо `targeting`
+    /// используется таргетинг сегмента.
+    pub segment_id: Option<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(FrequencyCap::example))]
+    #[validate(nested)]
+    /// Ограничения частоты показов объявления одному клиенту.
+    pub frequency_cap: Option<FrequencyCap>,
 }
 impl CreateCampaign {
     fn validate_custom(&self) -> Result<(), ValidationError> {
@@ -91,6 +157,12 @@ impl CreateCampaign {
             ));
         }
 
+        if self.daily_budget.unwrap_or(Decimal::ZERO) > self.total_budget.unwrap_or(Decimal::MAX) {
+            return Err(ValidationError::new(
+                "`daily_budget` can't be greater than `total_budget`",
+            ));
+        }
+
         let current_date = Time::get().current_date;
         if self.start_date < current_date {
             return Err(ValidationError::new("`start_date` can't be in the past"));
@@ -105,6 +177,12 @@ impl CreateCampaign {
             ));
         }
 
+        if self.segment_id.is_some() && !self.targeting.is_empty() {
+            return Err(ValidationError::new(
+                "`targeting` and `segment_id` can't be set at the same time",
+            ));
+        }
+
         Ok(())
     }
 }
@@ -113,16 +191,29 @@ impl Campaign {
     pub async fn create(
         advertiser_id: Uuid,
         campaign: CreateCampaign,
+        caller: &Caller,
         db: &SurrealDB,
     ) -> Result<Self, ApiError> {
         Advertiser::get_by_id(advertiser_id, db).await?;
+        if let Some(segment_id) = campaign.segment_id {
+            Segment::get_by_id(advertiser_id, segment_id, db).await?;
+        }
 
         let campaign = Self {
             id: Uuid::now_v7(),
             advertiser_id,
+            state: CampaignState::ACTIVE,
             inner: campaign,
         };
-        Ok((&DBCampaign::create(campaign, db).await?).into())
+        let record = DBAuditRecord::new(
+            AuditAction::CampaignCreated,
+            Some(campaign.id),
+            Some(advertiser_id),
+        )
+        .with_after(&campaign);
+        let audit = DBAudit::new(vec![record], caller);
+
+        Ok((&DBCampaign::create(campaign, audit, db).await?).into())
     }
 
     pub async fn list(
@@ -179,11 +270,18 @@ impl Campaign {
         advertiser_id: Uuid,
         campaign_id: Uuid,
         campaign_update: CampaignUpdate,
+        caller: &Caller,
         db: &SurrealDB,
     ) -> Result<Self, ApiError> {
-        let campaign_status = Self::get_by_id(advertiser_id, campaign_id, db)
-            .await?
-            .get_status();
+        let campaign = Self::get_by_id(advertiser_id, campaign_id, db).await?;
+        if campaign.state == CampaignState::ARCHIVED {
+            return Err(ApiError::Custom {
+                error: "campaign_archived".into(),
+                status_code: StatusCode::CONFLICT,
+                message: "Archived campaign can't be updated".into(),
+            });
+        }
+        let campaign_status = campaign.get_status();
         let unable_to_change_field_err =
             |field_name: &str| Err(ApiError::CampaignStarted(field_name.to_string()));
 
@@ -204,21 +302,66 @@ impl Campaign {
         if campaign_update.targeting.is_some() && campaign_status != CampaignStatus::NotStarted {
             return unable_to_change_field_err("targeting");
         }
+        if campaign_update.segment_id.is_some() && campaign_status != CampaignStatus::NotStarted {
+            return unable_to_change_field_err("segment_id");
+        }
 
-        let campaign = DBCampaign::update(advertiser_id, campaign_id, campaign_update, db)
-            .await?
-            .unwrap();
+        if let Some(segment_id) = campaign_update.segment_id {
+            Segment::get_by_id(advertiser_id, segment_id, db).await?;
+        } else if campaign_update.targeting.is_some() && campaign.inner.segment_id.is_some() {
+            return Err(ApiError::InvalidInput(
+                "`targeting` can't be set for a campaign that uses a segment".into(),
+            ));
+        }
 
-        Ok((&campaign).into())
+        let daily_budget = campaign_update.daily_budget.or(campaign.inner.daily_budget);
+        let total_budget = campaign_update.total_budget.or(campaign.inner.total_budget);
+        if daily_budget.unwrap_or(Decimal::ZERO) > total_budget.unwrap_or(Decimal::MAX) {
+            return Err(ApiError::InvalidInput(
+                "`daily_budget` can't be greater than `total_budget`".into(),
+            ));
+        }
+
+        let campaign_update = DBCampaignUpdate::from(campaign_update);
+        let fields = campaign_update.fields();
+
+        // В журнал попадают только поля, переданные в обновлении
+        let before = campaign.snapshot(&fields);
+        let after =
+            Self::from(&campaign_update.apply(DBCampaign::from(campaign))).snapshot(&fields);
+        let record = DBAuditRecord::new(
+            AuditAction::CampaignUpdated,
+            Some(campaign_id),
+            Some(advertiser_id),
+        )
+        .with_before(before)
+        .with_after(after);
+        let audit = DBAudit::new(vec![record], caller);
+
+        Ok(
+            (&DBCampaign::update(advertiser_id, campaign_id, campaign_update, audit, db)
+                .await?
+                .unwrap())
+                .into(),
+        )
     }
 
     pub async fn delete(
         advertiser_id: Uuid,
         campaign_id: Uuid,
+        caller: &Caller,
         db: &SurrealDB,
     ) -> Result<Self, ApiError> {
-        Advertiser::get_by_id(advertiser_id, db).await?;
-        match DBCampaign::delete(advertiser_id, campaign_id, db).await? {
+        let campaign = Self::get_by_id(advertiser_id, campaign_id, db).await?;
+        let record = DBAuditRecord::new(
+            AuditAction::CampaignDeleted,
+            Some(campaign_id),
+            Some(advertiser_id),
+        )
+        .with_before(&campaign);
+        let audit = DBAudit::new(vec![record], caller);
+
+        match DBCampaign::delete(advertiser_id, campaign_id, audit, db).await? {
             None => Err(ApiError::NotFound(format!(
                 "Campaign with UUID `{}`",
                 campaign_id,
@@ -227,6 +370,151 @@ impl Campaign {
         }
     }
 
+    /// Снимок значений указанных полей кампании для журнала аудита.
+    fn snapshot(&self, fields: &[String]) -> Value {
+        let campaign = match serde_json::to_value(self) {
+            Ok(Value::Object(campaign)) => campaign,
+            _ => Map::new(),
+        };
+        Value::Object(
+            fields
+                .iter()
+                .map(|field| {
+                    let value = campaign.get(field).cloned().unwrap_or(Value::Null);
+                    (field.clone(), value)
+                })
+                .collect(),
+        )
+    }
+
+    pub async fn restore(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        caller: &Caller,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        let campaign = match DBCampaign::get_deleted(advertiser_id, campaign_id, db).await? {
+            Some(campaign) => campaign,
+            None if DBCampaign::get(advertiser_id, campaign_id, db)
+                .await?
+                .is_some() =>
+            {
+                return Err(ApiError::Custom {
+                    error: "campaign_not_deleted".into(),
+                    status_code: StatusCode::CONFLICT,
+                    message: "Campaign is not deleted".into(),
+                })
+            }
+            None => {
+                return Err(ApiError::NotFound(format!(
+                    "Campaign with UUID `{}`",
+                    campaign_id,
+                )))
+            }
+        };
+
+        let retention_days = *config::CAMPAIGN_RETENTION_DAYS;
+        let deleted_at = campaign.deleted_at.unwrap_or_default();
+        if Time::get().current_date - deleted_at > retention_days {
+            return Err(ApiError::Custom {
+                error: "retention_expired".into(),
+                status_code: StatusCode::GONE,
+                message: format!(
+                    "Campaign was deleted more than {retention_days} days ago and can't be restored"
+                ),
+            });
+        }
+
+        let record = DBAuditRecord::new(
+            AuditAction::CampaignRestored,
+            Some(campaign_id),
+            Some(advertiser_id),
+        )
+        .with_after(Self::from(&campaign));
+        let audit = DBAudit::new(vec![record], caller);
+        let campaign = DBCampaign::restore(advertiser_id, campaign_id, audit, db)
+            .await?
+            .unwrap();
+
+        Ok((&campaign).into())
+    }
+
+    /// Окончательно удаляет кампании, срок хранения которых после удаления истёк,
+    /// вместе с их статистикой и изображениями.
+    pub async fn purge_deleted(db: &SurrealDB, minio: &Minio) -> Result<(), ApiError> {
+        let campaigns =
+            DBCampaign::list_retention_expired(*config::CAMPAIGN_RETENTION_DAYS, db).await?;
+        if campaigns.is_empty() {
+            return Ok(());
+        }
+
+        for campaign in &campaigns {
+            let advertiser_id = DBAdvertiser::record_id_to_uuid(&campaign.advertiser_id);
+            let campaign_id = DBCampaign::record_id_to_uuid(&campaign.id);
+            minio.remove_image(&advertiser_id, &campaign_id).await?;
+            for creative in DBCreative::list(campaign_id, None, 0, db).await? {
+                minio
+                    .remove_creative_image(
+                        &advertiser_id,
+                        &campaign_id,
+                        &DBCreative::record_id_to_uuid(&creative.id),
+                    )
+                    .await?;
+            }
+        }
+
+        DBCampaign::purge(campaigns.into_iter().map(|c| c.id).collect(), db).await?;
+        Ok(())
+    }
+
+    pub async fn set_state(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        state: CampaignState,
+        caller: &Caller,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        let campaign = Self::get_by_id(advertiser_id, campaign_id, db).await?;
+        if !campaign.state.can_transition_to(state) {
+            return Err(ApiError::Custom {
+                error: "invalid_state_transition".into(),
+                status_code: StatusCode::CONFLICT,
+                message: format!(
+                    "Campaign state can't be changed from {} to {}",
+                    campaign.state, state
+                ),
+            });
+        }
+
+        let record = DBAuditRecord::new(
+            AuditAction::for_state(state),
+            Some(campaign_id),
+            Some(advertiser_id),
+        )
+        .with_before(json!({ "state": campaign.state }))
+        .with_after(json!({ "state": state }));
+        let audit = DBAudit::new(vec![record], caller);
+        let campaign = DBCampaign::set_state(advertiser_id, campaign_id, state, audit, db)
+            .await?
+            .unwrap();
+
+        Ok((&campaign).into())
+    }
+
+    pub async fn state_history(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Vec<CampaignStateChange>, ApiError> {
+        Self::get_by_id(advertiser_id, campaign_id, db).await?;
+        Ok(DBCampaign::state_history(advertiser_id, campaign_id, db)
+            .await?
+            .into_iter()
+            .map(CampaignStateChange::from)
+            .collect())
+    }
+
     pub fn get_status(&self) -> CampaignStatus {
         match (
             self.inner.start_date,
@@ -240,6 +528,27 @@ impl Campaign {
     }
 }
 
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Запись истории изменения статуса рекламной кампании.")]
+pub struct CampaignStateChange {
+    #[schema(examples(CampaignState::PAUSED))]
+    /// Статус, который был установлен.
+    pub state: CampaignState,
+
+    #[schema(examples(1), minimum = 0)]
+    /// День, в который был установлен статус.
+    pub date: i32,
+}
+
+impl From<DBCampaignStateChange> for CampaignStateChange {
+    fn from(db: DBCampaignStateChange) -> Self {
+        Self {
+            state: db.state,
+            date: db.date,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
 #[schema(
     description = "Объект для обновления параметров кампании, которые разрешено изменять до старта кампании."
@@ -256,15 +565,35 @@ pub struct CampaignUpdate {
     /// Задаёт лимит переходов для рекламного объявления.
     pub clicks_limit: Option<i32>,
 
+    #[serde(default, with = "rust_decimal::serde::float_option")]
     #[schema(examples(1), exclusive_minimum = 0)]
-    #[validate(range(exclusive_min = 0.0))]
+    #[validate(custom(function = "check_positive_money"))]
     /// Новая стоимость одного показа объявления.
-    pub cost_per_impression: Option<f64>,
+    pub cost_per_impression: Option<Decimal>,
 
+    #[serde(default, with = "rust_decimal::serde::float_option")]
     #[schema(examples(1), exclusive_minimum = 0)]
-    #[validate(range(exclusive_min = 0.0))]
+    #[validate(custom(function = "check_positive_money"))]
     /// Новая стоимость одного перехода (клика) по объявлению.
-    pub cost_per_click: Option<f64>,
+    pub cost_per_click: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::float_option")]
+    #[schema(examples(10), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Новая стоимость одной конверсии.
+    pub cost_per_conversion: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::float_option")]
+    #[schema(examples(100), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Новый общий бюджет рекламной кампании.
+    pub total_budget: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::float_option")]
+    #[schema(examples(10), exclusive_minimum = 0)]
+    #[validate(custom(function = "check_positive_money"))]
+    /// Новый дневной бюджет рекламной кампании.
+    pub daily_budget: Option<Decimal>,
 
     #[validate(custom(function = "check_profanity"))]
     /// Новое название рекламного объявления.
@@ -287,6 +616,15 @@ pub struct CampaignUpdate {
     #[schema(examples(Targeting::example))]
     #[validate(nested)]
     pub targeting: Option<Targeting>,
+
+    /// UUID сегмента аудитории, таргетинг которого будет использоваться вместо
+    /// собственного таргетинга кампании.
+    pub segment_id: Option<Uuid>,
+
+    #[schema(examples(FrequencyCap::example))]
+    #[validate(nested)]
+    /// Новые ограничения частоты показов. Их можно изменять и после старта кампании.
+    pub frequency_cap: Option<FrequencyCap>,
 }
 impl CampaignUpdate {
     pub fn validate_custom(&self) -> Result<(), ValidationError> {
@@ -296,6 +634,12 @@ impl CampaignUpdate {
             ));
         }
 
+        if self.daily_budget.unwrap_or(Decimal::ZERO) > self.total_budget.unwrap_or(Decimal::MAX) {
+            return Err(ValidationError::new(
+                "`daily_budget` can't be greater than `total_budget`",
+            ));
+        }
+
         let current_date = Time::get().current_date;
         if self.start_date.unwrap_or(current_date) < current_date {
             return Err(ValidationError::new("`start_date` can't be in the past"));
@@ -310,11 +654,17 @@ impl CampaignUpdate {
             ));
         }
 
+        if self.segment_id.is_some() && self.targeting.is_some() {
+            return Err(ValidationError::new(
+                "`targeting` and `segment_id` can't be set at the same time",
+            ));
+        }
+
         Ok(())
     }
 }
 
-#[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
+#[derive(Deserialize, Serialize, ToSchema, Validate, Default, Debug)]
 #[schema(description = "Объект, описывающий настройки таргетирования для рекламной кампании.")]
 #[validate(schema(function = "Self::validate_custom"))]
 pub struct Targeting {
@@ -340,13 +690,180 @@ pub struct Targeting {
     #[validate(custom(function = "check_profanity"))]
     /// Локация аудитории, для которой будет показано объявление.
     pub location: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(json!(["Moscow", "Kazan"])))]
+    #[validate(length(max = 100))]
+    /// Список локаций, для клиентов из которых будет показано объявление.
+    pub locations: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(json!(["Tver"])))]
+    #[validate(length(max = 100))]
+    /// Список локаций, для клиентов из которых объявление не будет показано.
+    pub excluded_locations: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    #[validate(length(max = 20), nested)]
+    /// Возрастные диапазоны аудитории. Объявление будет показано клиенту, если его возраст
+    /// попадает хотя бы в один из диапазонов.
+    pub age_ranges: Option<Vec<AgeRange>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    #[validate(length(max = 1000))]
+    /// UUID клиентов, которым разрешено показывать объявление. Если список не пуст,
+    /// объявление будет показано только этим клиентам.
+    pub allowed_clients: Option<Vec<Uuid>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    #[validate(length(max = 1000))]
+    /// UUID клиентов, которым объявление не будет показано.
+    pub denied_clients: Option<Vec<Uuid>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(json!([{"attribute": "device", "op": "equals", "value": "ios"}])))]
+    #[validate(length(max = 20), nested)]
+    /// Правила таргетинга по пользовательским атрибутам клиента.
+    /// Объявление будет показано клиенту, только если выполнены все правила.
+    pub rules: Option<Vec<TargetingRule>>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Clone, Debug)]
+#[schema(description = "Правило таргетинга по пользовательскому атрибуту клиента.")]
+#[validate(schema(function = "Self::validate_custom"))]
+pub struct TargetingRule {
+    #[schema(examples("device"))]
+    #[validate(custom(function = "validate_attribute_name"))]
+    /// Название атрибута клиента.
+    pub attribute: String,
+
+    /// Оператор сравнения.
+    pub op: RuleOperator,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, value_type = Object)]
+    /// Значение для операторов `equals` и `contains`.
+    pub value: Option<AttributeValue>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, value_type = Vec<Object>)]
+    #[validate(length(min = 1, max = 100))]
+    /// Допустимые значения для оператора `in`.
+    pub values: Option<Vec<AttributeValue>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    /// Нижняя граница для оператора `range` (включительно).
+    pub min: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable)]
+    /// Верхняя граница для оператора `range` (включительно).
+    pub max: Option<f64>,
+}
+
+impl TargetingRule {
+    pub fn validate_custom(&self) -> Result<(), ValidationError> {
+        match self.op {
+            RuleOperator::Equals if self.value.is_none() => Err(ValidationError::new(
+                "`value` is required for the `equals` operator",
+            )),
+            RuleOperator::In if self.values.is_none() => Err(ValidationError::new(
+                "`values` is required for the `in` operator",
+            )),
+            RuleOperator::Range if self.min.is_none() && self.max.is_none() => Err(
+                ValidationError::new("`min` or `max` is required for the `range` operator"),
+            ),
+            RuleOperator::Range if self.min > self.max && self.max.is_some() => {
+                Err(ValidationError::new("`min` can't be greater than `max`"))
+            }
+            RuleOperator::Contains if !matches!(self.value, Some(AttributeValue::String(_))) => {
+                Err(ValidationError::new(
+                    "`value` must be a string for the `contains` operator",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<DBTargetingRule> for TargetingRule {
+    fn from(db: DBTargetingRule) -> Self {
+        Self {
+            attribute: db.attribute,
+            op: db.op,
+            value: db.value,
+            values: db.values,
+            min: db.min,
+            max: db.max,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Clone, Debug)]
+#[schema(description = "Объект, описывающий возрастной диапазон аудитории.")]
+#[validate(schema(function = "Self::validate_custom"))]
+pub struct AgeRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(18), minimum = 0)]
+    #[validate(range(min = 0))]
+    /// Минимальный возраст (включительно).
+    pub age_from: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(24))]
+    #[validate(range(min = 0, max = 200))]
+    /// Максимальный возраст (включительно).
+    pub age_to: Option<i32>,
+}
+
+impl AgeRange {
+    pub fn validate_custom(&self) -> Result<(), ValidationError> {
+        if self.age_from.is_some() && self.age_to.is_some() && self.age_from > self.age_to {
+            return Err(ValidationError::new(
+                "`age_from` must not be greater than `age_to`",
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl Targeting {
     pub fn validate_custom(&self) -> Result<(), ValidationError> {
         if self.age_from.is_some() && self.age_to.is_some() && self.age_from > self.age_to {
             return Err(ValidationError::new(
-                "`age_from` must be less than `age_to`",
+                "`age_from` must not be greater than `age_to`",
+            ));
+        }
+
+        let locations = self.locations.iter().flatten();
+        let excluded_locations = self.excluded_locations.iter().flatten();
+        for location in locations.clone().chain(excluded_locations.clone()) {
+            check_profanity(location)?;
+        }
+        if locations.clone().any(|location| {
+            excluded_locations
+                .clone()
+                .any(|excluded| excluded == location)
+        }) {
+            return Err(ValidationError::new(
+                "`locations` and `excluded_locations` can't intersect",
+            ));
+        }
+
+        let denied_clients = self.denied_clients.iter().flatten().collect::<Vec<_>>();
+        if self
+            .allowed_clients
+            .iter()
+            .flatten()
+            .any(|client| denied_clients.contains(&client))
+        {
+            return Err(ValidationError::new(
+                "`allowed_clients` and `denied_clients` can't intersect",
             ));
         }
 
@@ -359,13 +876,30 @@ impl Targeting {
             age_from: Some(0),
             age_to: Some(0),
             location: Some("string".to_string()),
+            locations: None,
+            excluded_locations: None,
+            age_ranges: None,
+            allowed_clients: None,
+            denied_clients: None,
+            rules: None,
         }
     }
 
     pub fn is_none(targeting: &Option<Self>) -> bool {
-        targeting.as_ref().map_or(true, |t| {
-            t.gender.is_none() && t.age_from.is_none() && t.age_to.is_none() && t.location.is_none()
-        })
+        targeting.as_ref().is_none_or(Self::is_empty)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gender.is_none()
+            && self.age_from.is_none()
+            && self.age_to.is_none()
+            && self.location.is_none()
+            && self.locations.is_none()
+            && self.excluded_locations.is_none()
+            && self.age_ranges.is_none()
+            && self.allowed_clients.is_none()
+            && self.denied_clients.is_none()
+            && self.rules.is_none()
     }
 }
 
@@ -376,6 +910,73 @@ impl From<DBTargeting> for Targeting {
             age_from: db.age_from,
             age_to: db.age_to,
             location: db.location,
+            locations: db.locations,
+            excluded_locations: db.excluded_locations,
+            age_ranges: db
+                .age_ranges
+                .map(|ranges| ranges.into_iter().map(AgeRange::from).collect()),
+            allowed_clients: db
+                .allowed_clients
+                .map(|clients| clients.iter().map(DBClient::record_id_to_uuid).collect()),
+            denied_clients: db
+                .denied_clients
+                .map(|clients| clients.iter().map(DBClient::record_id_to_uuid).collect()),
+            rules: db
+                .rules
+                .map(|rules| rules.into_iter().map(TargetingRule::from).collect()),
+        }
+    }
+}
+
+impl From<DBAgeRange> for AgeRange {
+    fn from(db: DBAgeRange) -> Self {
+        Self {
+            age_from: db.age_from,
+            age_to: db.age_to,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Clone, Debug)]
+#[schema(
+    description = "Объект, описывающий ограничения частоты показов рекламной кампании одному клиенту."
+)]
+pub struct FrequencyCap {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(3), minimum = 1)]
+    #[validate(range(min = 1))]
+    /// Максимальное количество показов объявления одному клиенту за день.
+    pub per_day: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(10), minimum = 1)]
+    #[validate(range(min = 1))]
+    /// Максимальное количество показов объявления одному клиенту за всё время кампании.
+    pub lifetime: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable, examples(1), minimum = 1)]
+    #[validate(range(min = 1))]
+    /// Минимальное количество дней между повторными показами объявления одному клиенту.
+    pub min_days_between: Option<i32>,
+}
+
+impl FrequencyCap {
+    pub fn example() -> Self {
+        Self {
+            per_day: Some(3),
+            lifetime: Some(10),
+            min_days_between: Some(1),
+        }
+    }
+}
+
+impl From<DBFrequencyCap> for FrequencyCap {
+    fn from(db: DBFrequencyCap) -> Self {
+        Self {
+            per_day: db.per_day,
+            lifetime: db.lifetime,
+            min_days_between: db.min_days_between,
         }
     }
 }
@@ -385,16 +986,22 @@ impl From<&DBCampaign> for Campaign {
         Self {
             id: DBCampaign::record_id_to_uuid(&db.id),
             advertiser_id: DBAdvertiser::record_id_to_uuid(&db.advertiser_id),
+            state: db.state,
             inner: CreateCampaign {
                 impressions_limit: db.impressions_limit,
                 clicks_limit: db.clicks_limit,
                 cost_per_impression: db.cost_per_impression.round_to_digits(2),
                 cost_per_click: db.cost_per_click.round_to_digits(2),
+                cost_per_conversion: db.cost_per_conversion.map(|c| c.round_to_digits(2)),
+                total_budget: db.total_budget.map(|b| b.round_to_digits(2)),
+                daily_budget: db.daily_budget.map(|b| b.round_to_digits(2)),
                 ad_title: db.ad_title.clone(),
                 ad_text: db.ad_text.clone(),
                 start_date: db.start_date,
                 end_date: db.end_date,
                 targeting: Targeting::from(db.targeting.clone()),
+                segment_id: db.segment_id.as_ref().map(DBSegment::record_id_to_uuid),
+                frequency_cap: db.frequency_cap.clone().map(FrequencyCap::from),
             },
         }
     }
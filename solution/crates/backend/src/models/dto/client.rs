@@ -6,8 +6,8 @@ use validator::Validate;
 
 use crate::{
     models::{
-        db::{DBClient, DBModel},
-        Gender,
+        db::{DBAudit, DBAuditRecord, DBClient, DBModel},
+        validate_attributes, Attributes, AuditAction, Caller, Gender,
     },
     routes::ApiError,
     utils::validation::check_profanity,
@@ -35,6 +35,13 @@ pub struct Client {
 
     /// Пол клиента (MALE или FEMALE).
     pub gender: Gender,
+
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    #[schema(value_type = Object, examples(json!({"interests": ["sport", "music"], "device": "ios", "subscription_tier": 2})))]
+    #[validate(length(max = 50), custom(function = "validate_attributes"))]
+    /// Произвольные атрибуты клиента (строки, числа, логические значения или списки строк),
+    /// по которым можно настраивать правила таргетинга.
+    pub attributes: Attributes,
 }
 
 impl Client {
@@ -48,8 +55,21 @@ impl Client {
         }
     }
 
-    pub async fn upsert(clients: Vec<Self>, db: &SurrealDB) -> Result<Vec<Self>, ApiError> {
-        Ok(DBClient::bulk(clients, db)
+    pub async fn upsert(
+        clients: Vec<Self>,
+        caller: &Caller,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        let records = clients
+            .iter()
+            .map(|client| {
+                DBAuditRecord::new(AuditAction::ClientUpserted, Some(client.id), None)
+                    .with_after(client)
+            })
+            .collect();
+        let audit = DBAudit::new(records, caller);
+
+        Ok(DBClient::bulk(clients, audit, db)
             .await?
             .iter()
             .map(Self::from)
@@ -65,6 +85,7 @@ impl From<&DBClient> for Client {
             age: db.age,
             location: db.location.clone(),
             gender: db.gender.clone(),
+            attributes: db.attributes.clone(),
         }
     }
 }
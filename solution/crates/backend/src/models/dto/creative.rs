@@ -0,0 +1,160 @@
+use actix_web::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        db::{DBAudit, DBAuditRecord, DBCampaign, DBCreative, DBModel},
+        url::Pagination,
+        AuditAction, Caller, CampaignState,
+    },
+    routes::ApiError,
+    utils::{minio::Minio, validation::check_profanity},
+};
+
+use super::Campaign;
+
+#[derive(Deserialize, Serialize, ToSchema, Debug)]
+#[schema(description = "Объект, представляющий креатив (вариант объявления) рекламной кампании.")]
+pub struct Creative {
+    #[serde(rename = "creative_id")]
+    /// Уникальный идентификатор креатива (UUID).
+    pub id: Uuid,
+
+    /// UUID рекламной кампании, которой принадлежит креатив.
+    pub campaign_id: Uuid,
+
+    #[serde(flatten)]
+    pub inner: CreateCreative,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
+#[schema(description = "Объект для создания нового креатива рекламной кампании.")]
+pub struct CreateCreative {
+    #[validate(custom(function = "check_profanity"))]
+    /// Название рекламного объявления в этом варианте.
+    pub ad_title: String,
+
+    #[validate(custom(function = "check_profanity"))]
+    /// Текст рекламного объявления в этом варианте.
+    pub ad_text: String,
+}
+
+impl Creative {
+    pub async fn create(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        creative: CreateCreative,
+        caller: &Caller,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        let campaign = Campaign::get_by_id(advertiser_id, campaign_id, db).await?;
+        if campaign.state == CampaignState::ARCHIVED {
+            return Err(ApiError::Custom {
+                error: "campaign_archived".into(),
+                status_code: StatusCode::CONFLICT,
+                message: "Creatives can't be added to an archived campaign".into(),
+            });
+        }
+
+        let creative = Self {
+            id: Uuid::now_v7(),
+            campaign_id,
+            inner: creative,
+        };
+        let record = DBAuditRecord::new(
+            AuditAction::CreativeCreated,
+            Some(creative.id),
+            Some(advertiser_id),
+        )
+        .with_after(&creative);
+        let audit = DBAudit::new(vec![record], caller);
+        Ok((&DBCreative::create(creative, audit, db).await?).into())
+    }
+
+    pub async fn list(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        Campaign::get_by_id(advertiser_id, campaign_id, db).await?;
+
+        let size: i64 = match pagination.size {
+            Some(size) if size < 57 => size,
+            Some(..) => 57,
+            None => 7,
+        };
+
+        if size == 0 {
+            return Ok(vec![]);
+        }
+
+        let offset: i64 = pagination.page.unwrap_or(0) * size;
+        Ok(DBCreative::list(campaign_id, Some(size), offset, db)
+            .await?
+            .iter()
+            .map(Self::from)
+            .collect())
+    }
+
+    pub async fn get_by_id(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        creative_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Campaign::get_by_id(advertiser_id, campaign_id, db).await?;
+        match DBCreative::get(campaign_id, creative_id, db).await? {
+            None => Err(ApiError::NotFound(format!(
+                "Creative with UUID `{}`",
+                creative_id,
+            ))),
+            Some(creative) => Ok((&creative).into()),
+        }
+    }
+
+    pub async fn delete(
+        advertiser_id: Uuid,
+        campaign_id: Uuid,
+        creative_id: Uuid,
+        caller: &Caller,
+        db: &SurrealDB,
+        minio: &Minio,
+    ) -> Result<Self, ApiError> {
+        let creative = Self::get_by_id(advertiser_id, campaign_id, creative_id, db).await?;
+
+        minio
+            .remove_creative_image(&advertiser_id, &campaign_id, &creative_id)
+            .await?;
+
+        let record = DBAuditRecord::new(
+            AuditAction::CreativeDeleted,
+            Some(creative_id),
+            Some(advertiser_id),
+        )
+        .with_before(&creative);
+        let audit = DBAudit::new(vec![record], caller);
+        let creative = DBCreative::delete(campaign_id, creative_id, audit, db)
+            .await?
+            .unwrap();
+
+        Ok((&creative).into())
+    }
+}
+
+impl From<&DBCreative> for Creative {
+    fn from(db: &DBCreative) -> Self {
+        Self {
+            id: DBCreative::record_id_to_uuid(&db.id),
+            campaign_id: DBCampaign::record_id_to_uuid(&db.campaign_id),
+            inner: CreateCreative {
+                ad_title: db.ad_title.clone(),
+                ad_text: db.ad_text.clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use arrow_array::{
+    ArrayRef, BooleanArray, Decimal128Array, Float32Array, Int32Array, RecordBatch, StringArray,
+    UInt64Array,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        db::{stats::DBInteractionStats, DBCampaign, DBClient, DBCreative, DBModel},
+        dto::{Advertiser, Campaign, PeriodStats, Stats},
+        url::{ExportFormat, StatsRangeQuery},
+    },
+    routes::ApiError,
+    utils::export::{export_response, ExportRecord},
+};
+
+/// Строка выгрузки статистики за период.
+#[derive(Serialize, Debug)]
+pub struct StatsRecord {
+    pub start_date: i32,
+    pub end_date: i32,
+    pub impressions_count: u64,
+    pub clicks_count: u64,
+    pub conversion: f32,
+    pub spent_impressions: Decimal,
+    pub spent_clicks: Decimal,
+    pub spent_conversions: Decimal,
+    pub spent_total: Decimal,
+    pub conversions_count: u64,
+    pub conversion_value: Decimal,
+    pub cost_per_acquisition: Decimal,
+}
+
+/// Строка выгрузки взаимодействия клиента с рекламной кампанией.
+#[derive(Serialize, Debug)]
+pub struct InteractionRecord {
+    pub client_id: Uuid,
+    pub campaign_id: Uuid,
+    pub creative_id: Option<Uuid>,
+    pub impressed: i32,
+    pub clicked: Option<i32>,
+    pub converted: Option<i32>,
+    pub impressions_count: u64,
+    pub spent_impressions: Decimal,
+    pub spent_clicks: Decimal,
+    pub spent_conversions: Decimal,
+    pub spent_total: Decimal,
+    /// Затраты рассчитаны по текущим ценам кампании, так как цены взаимодействия
+    /// не были сохранены при его записи.
+    pub spent_estimated: bool,
+    pub conversion_value: Option<Decimal>,
+}
+
+/// Точность и масштаб денежных колонок Parquet: суммы хранятся в копейках без потери точности.
+const MONEY_PRECISION: u8 = 38;
+const MONEY_SCALE: i8 = 2;
+
+fn money_field(name: &str, nullable: bool) -> Field {
+    Field::new(
+        name,
+        DataType::Decimal128(MONEY_PRECISION, MONEY_SCALE),
+        nullable,
+    )
+}
+
+fn money_array(values: impl Iterator<Item = Option<Decimal>>) -> Result<ArrayRef, ArrowError> {
+    let array = Decimal128Array::from_iter(values.map(|value| {
+        value.map(|mut value| {
+            value.rescale(MONEY_SCALE as u32);
+            value.mantissa()
+        })
+    }))
+    .with_precision_and_scale(MONEY_PRECISION, MONEY_SCALE)?;
+    Ok(Arc::new(array))
+}
+
+pub struct Export;
+
+impl Export {
+    pub async fn campaign_daily(
+        campaign_id: Uuid,
+        query: &StatsRangeQuery,
+        format: ExportFormat,
+        db: &SurrealDB,
+    ) -> Result<HttpResponse, ApiError> {
+        let records = Stats::campaign_daily(campaign_id, query, db).await?;
+        Self::daily(format, format!("campaign_{campaign_id}_daily"), records)
+    }
+
+    pub async fn advertiser_daily(
+        advertiser_id: Uuid,
+        query: &StatsRangeQuery,
+        format: ExportFormat,
+        db: &SurrealDB,
+    ) -> Result<HttpResponse, ApiError> {
+        let records = Stats::advertiser_daily(advertiser_id, query, false, db).await?;
+        Self::daily(format, format!("advertiser_{advertiser_id}_daily"), records)
+    }
+
+    pub async fn campaign_interactions(
+        campaign_id: Uuid,
+        format: ExportFormat,
+        db: &SurrealDB,
+    ) -> Result<HttpResponse, ApiError> {
+        Campaign::get_by_id_unchecked(campaign_id, db).await?;
+
+        let db = db.clone();
+        export_response(
+            format,
+            format!("campaign_{campaign_id}_interactions"),
+            move |limit, offset| {
+                let db = db.clone();
+                async move {
+                    Ok(
+                        DBInteractionStats::list_by_campaign(campaign_id, Some(limit), offset, &db)
+                            .await?
+                            .iter()
+                            .map(InteractionRecord::from)
+                            .collect(),
+                    )
+                }
+            },
+        )
+    }
+
+    pub async fn advertiser_interactions(
+        advertiser_id: Uuid,
+        format: ExportFormat,
+        db: &SurrealDB,
+    ) -> Result<HttpResponse, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+
+        let db = db.clone();
+        export_response(
+            format,
+            format!("advertiser_{advertiser_id}_interactions"),
+            move |limit, offset| {
+                let db = db.clone();
+                async move {
+                    Ok(DBInteractionStats::list_by_advertiser(
+                        advertiser_id,
+                        Some(limit),
+                        offset,
+                        &db,
+                    )
+                    .await?
+                    .iter()
+                    .map(InteractionRecord::from)
+                    .collect())
+                }
+            },
+        )
+    }
+
+    fn daily(
+        format: ExportFormat,
+        filename: String,
+        records: Vec<PeriodStats>,
+    ) -> Result<HttpResponse, ApiError> {
+        let mut records: Vec<StatsRecord> = records.into_iter().map(StatsRecord::from).collect();
+        export_response(format, filename, move |limit, _| {
+            let page: Vec<StatsRecord> =
+                records.drain(..records.len().min(limit as usize)).collect();
+            async move { Ok(page) }
+        })
+    }
+}
+
+impl From<PeriodStats> for StatsRecord {
+    fn from(period: PeriodStats) -> Self {
+        Self {
+            start_date: period.start_date,
+            end_date: period.end_date,
+            impressions_count: period.stats.impressions_count,
+            clicks_count: period.stats.clicks_count,
+            conversion: period.stats.conversion,
+            spent_impressions: period.stats.spent_impressions,
+            spent_clicks: period.stats.spent_clicks,
+            spent_conversions: period.stats.spent_conversions,
+            spent_total: period.stats.spent_total,
+            conversions_count: period.stats.conversions_count,
+            conversion_value: period.stats.conversion_value,
+            cost_per_acquisition: period.stats.cost_per_acquisition,
+        }
+    }
+}
+
+impl From<&DBInteractionStats> for InteractionRecord {
+    fn from(db: &DBInteractionStats) -> Self {
+        let stats = db.stats();
+        Self {
+            client_id: DBClient::record_id_to_uuid(&db.client_id),
+            campaign_id: DBCampaign::record_id_to_uuid(&db.campaign_id),
+            creative_id: db.creative_id.as_ref().map(DBCreative::record_id_to_uuid),
+            impressed: db.impressed,
+            clicked: db.clicked,
+            converted: db.converted,
+            impressions_count: stats.impressions_count,
+            spent_impressions: stats.spent_impressions,
+            spent_clicks: stats.spent_clicks,
+            spent_conversions: stats.spent_conversions,
+            spent_total: stats.spent_total,
+            spent_estimated: db.prices_estimated,
+            conversion_value: db.conversion_value,
+        }
+    }
+}
+
+impl ExportRecord for StatsRecord {
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("start_date", DataType::Int32, false),
+            Field::new("end_date", DataType::Int32, false),
+            Field::new("impressions_count", DataType::UInt64, false),
+            Field::new("clicks_count", DataType::UInt64, false),
+            Field::new("conversion", DataType::Float32, false),
+            money_field("spent_impressions", false),
+            money_field("spent_clicks", false),
+            money_field("spent_conversions", false),
+            money_field("spent_total", false),
+            Field::new("conversions_count", DataType::UInt64, false),
+            money_field("conversion_value", false),
+            money_field("cost_per_acquisition", false),
+        ]))
+    }
+
+    fn to_record_batch(records: &[Self]) -> Result<RecordBatch, ArrowError> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from_iter_values(
+                records.iter().map(|record| record.start_date),
+            )),
+            Arc::new(Int32Array::from_iter_values(
+                records.iter().map(|record| record.end_date),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                records.iter().map(|record| record.impressions_count),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                records.iter().map(|record| record.clicks_count),
+            )),
+            Arc::new(Float32Array::from_iter_values(
+                records.iter().map(|record| record.conversion),
+            )),
+            money_array(records.iter().map(|record| Some(record.spent_impressions)))?,
+            money_array(records.iter().map(|record| Some(record.spent_clicks)))?,
+            money_array(records.iter().map(|record| Some(record.spent_conversions)))?,
+            money_array(records.iter().map(|record| Some(record.spent_total)))?,
+            Arc::new(UInt64Array::from_iter_values(
+                records.iter().map(|record| record.conversions_count),
+            )),
+            money_array(records.iter().map(|record| Some(record.conversion_value)))?,
+            money_array(
+                records
+                    .iter()
+                    .map(|record| Some(record.cost_per_acquisition)),
+            )?,
+        ];
+        RecordBatch::try_new(Self::schema(), columns)
+    }
+}
+
+impl ExportRecord for InteractionRecord {
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("client_id", DataType::Utf8, false),
+            Field::new("campaign_id", DataType::Utf8, false),
+            Field::new("creative_id", DataType::Utf8, true),
+            Field::new("impressed", DataType::Int32, false),
+            Field::new("clicked", DataType::Int32, true),
+            Field::new("converted", DataType::Int32, true),
+            Field::new("impressions_count", DataType::UInt64, false),
+            money_field("spent_impressions", false),
+            money_field("spent_clicks", false),
+            money_field("spent_conversions", false),
+            money_field("spent_total", false),
+            Field::new("spent_estimated", DataType::Boolean, false),
+            money_field("conversion_value", true),
+        ]))
+    }
+
+    fn to_record_batch(records: &[Self]) -> Result<RecordBatch, ArrowError> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|record| record.client_id.to_string()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|record| record.campaign_id.to_string()),
+            )),
+            Arc::new(StringArray::from_iter(
+                records
+                    .iter()
+                    .map(|record| record.creative_id.map(|id| id.to_string())),
+            )),
+            Arc::new(Int32Array::from_iter_values(
+                records.iter().map(|record| record.impressed),
+            )),
+            Arc::new(Int32Array::from_iter(
+                records.iter().map(|record| record.clicked),
+            )),
+            Arc::new(Int32Array::from_iter(
+                records.iter().map(|record| record.converted),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                records.iter().map(|record| record.impressions_count),
+            )),
+            money_array(records.iter().map(|record| Some(record.spent_impressions)))?,
+            money_array(records.iter().map(|record| Some(record.spent_clicks)))?,
+            money_array(records.iter().map(|record| Some(record.spent_conversions)))?,
+            money_array(records.iter().map(|record| Some(record.spent_total)))?,
+            Arc::new(BooleanArray::from_iter(
+                records.iter().map(|record| Some(record.spent_estimated)),
+            )),
+            money_array(records.iter().map(|record| record.conversion_value))?,
+        ];
+        RecordBatch::try_new(Self::schema(), columns)
+    }
+}
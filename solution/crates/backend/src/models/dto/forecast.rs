@@ -0,0 +1,126 @@
+use serde::Serialize;
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::db::{DBForecast, DBTargeting},
+    routes::ApiError,
+    utils::RoundToDigits,
+};
+
+use super::{Advertiser, CreateCampaign, Segment};
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Прогноз охвата рекламной кампании до её запуска.")]
+pub struct CampaignForecast {
+    #[schema(examples(100), minimum = 0)]
+    /// Количество клиентов, подходящих под таргетинг кампании.
+    pub clients_count: u64,
+
+    #[schema(examples(2), minimum = 0)]
+    /// Количество активных кампаний, таргетинг которых пересекается с аудиторией кампании.
+    pub competing_campaigns_count: u64,
+
+    #[schema(examples(50), minimum = 0)]
+    /// Ожидаемое количество показов объявления за день.
+    pub estimated_daily_impressions: f64,
+
+    #[schema(examples(5), minimum = 0)]
+    /// Ожидаемое количество переходов (кликов) по объявлению за день.
+    pub estimated_daily_clicks: f64,
+}
+
+impl CampaignForecast {
+    pub async fn for_campaign(
+        advertiser_id: Uuid,
+        campaign: CreateCampaign,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        let targeting = match campaign.segment_id {
+            Some(segment_id) => {
+                Segment::get_by_id(advertiser_id, segment_id, db)
+                    .await?
+                    .inner
+                    .targeting
+            }
+            None => campaign.targeting,
+        };
+
+        let forecast = DBForecast::for_targeting(DBTargeting::from(targeting), db).await?;
+        let per_day_cap = campaign
+            .frequency_cap
+            .and_then(|frequency_cap| frequency_cap.per_day);
+
+        Ok(Self::estimate(forecast, per_day_cap))
+    }
+
+    /// Оценивает дневной объём показов по истории ежедневной статистики кампаний.
+    ///
+    /// Среднее количество показов на клиента в день делится поровну между кампанией
+    /// и конкурирующими с ней кампаниями. Если истории ещё нет, считается, что
+    /// каждый клиент запрашивает рекламу один раз в день.
+    fn estimate(forecast: DBForecast, per_day_cap: Option<i32>) -> Self {
+        let client_days = forecast.history_days * forecast.clients_total;
+        let impressions_per_client = match client_days {
+            0 => 1.0,
+            client_days => forecast.history_impressions as f64 / client_days as f64,
+        };
+
+        let mut impressions_share =
+            impressions_per_client / (forecast.competing_campaigns_count + 1) as f64;
+        if let Some(per_day_cap) = per_day_cap {
+            impressions_share = impressions_share.min(per_day_cap as f64);
+        }
+
+        let click_rate = match forecast.history_impressions {
+            0 => 0.0,
+            impressions => forecast.history_clicks as f64 / impressions as f64,
+        };
+
+        let estimated_daily_impressions = forecast.clients_count as f64 * impressions_share;
+        Self {
+            clients_count: forecast.clients_count,
+            competing_campaigns_count: forecast.competing_campaigns_count,
+            estimated_daily_impressions: estimated_daily_impressions.round_to_digits(2),
+            estimated_daily_clicks: (estimated_daily_impressions * click_rate).round_to_digits(2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::models::db::DBForecast;
+
+    use super::CampaignForecast;
+
+    fn db_forecast(history_days: u64, history_impressions: u64, history_clicks: u64) -> DBForecast {
+        DBForecast {
+            clients_count: 10,
+            clients_total: 20,
+            competing_campaigns_count: 1,
+            history_days,
+            history_impressions,
+            history_clicks,
+        }
+    }
+
+    #[rstest]
+    #[case::no_history(db_forecast(0, 0, 0), None, 5.0, 0.0)]
+    #[case::history(db_forecast(2, 160, 16), None, 20.0, 2.0)]
+    #[case::per_day_cap(db_forecast(2, 160, 16), Some(1), 10.0, 1.0)]
+    fn estimate(
+        #[case] forecast: DBForecast,
+        #[case] per_day_cap: Option<i32>,
+        #[case] impressions: f64,
+        #[case] clicks: f64,
+    ) {
+        let forecast = CampaignForecast::estimate(forecast, per_day_cap);
+
+        assert_eq!(forecast.estimated_daily_impressions, impressions);
+        assert_eq!(forecast.estimated_daily_clicks, clicks);
+    }
+}
@@ -5,7 +5,10 @@ use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
-    models::db::{DBAdvertiser, DBClient, DBMLScore, DBModel},
+    models::{
+        db::{DBAdvertiser, DBAudit, DBAuditRecord, DBClient, DBMLScore, DBModel},
+        AuditAction, Caller,
+    },
     routes::ApiError,
 };
 
@@ -27,11 +30,19 @@ pub struct MLScore {
 }
 
 impl MLScore {
-    pub async fn upsert(self, db: &SurrealDB) -> Result<Self, ApiError> {
+    pub async fn upsert(self, caller: &Caller, db: &SurrealDB) -> Result<Self, ApiError> {
         Advertiser::get_by_id(self.advertiser_id, db).await?;
         Client::get_by_id(self.client_id, db).await?;
 
-        Ok(DBMLScore::upsert(self, db).await?.into())
+        let record = DBAuditRecord::new(
+            AuditAction::MlScoreUpserted,
+            Some(self.client_id),
+            Some(self.advertiser_id),
+        )
+        .with_after(&self);
+        let audit = DBAudit::new(vec![record], caller);
+
+        Ok(DBMLScore::upsert(self, audit, db).await?.into())
     }
 }
 
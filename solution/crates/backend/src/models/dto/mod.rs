@@ -1,15 +1,38 @@
 mod ad;
+mod ad_decision;
 mod advertiser;
+mod api_key;
+mod audit;
+mod billing;
 mod campaign;
 mod client;
+mod creative;
+mod export;
+mod forecast;
 mod ml_score;
+mod segment;
 mod stats;
+mod stats_rebuild;
 mod time;
 
-pub use ad::Ad;
+pub use ad::{Ad, AdResponse};
+pub use ad_decision::{AdDecision, AdPredicates, ScoreBreakdown};
 pub use advertiser::Advertiser;
-pub use campaign::{Campaign, CampaignUpdate, CreateCampaign, Targeting};
+pub use api_key::{ApiKey, CreateApiKey};
+pub use audit::{AuditActor, AuditEntry};
+pub use billing::{BillingBalance, Invoice, InvoiceLine, LedgerEntry, TopUp};
+pub use campaign::{
+    AgeRange, Campaign, CampaignStateChange, CampaignUpdate, CreateCampaign, FrequencyCap,
+    Targeting, TargetingRule,
+};
 pub use client::Client;
+pub use creative::{CreateCreative, Creative};
+pub use export::Export;
+pub use forecast::CampaignForecast;
 pub use ml_score::MLScore;
-pub use stats::Stats;
+pub use segment::{CreateSegment, Segment, SegmentReach, SegmentUpdate};
+pub use stats::{
+    AudienceSegmentStats, AudienceStats, CampaignStats, CreativeStats, PeriodStats, Stats,
+};
+pub use stats_rebuild::{StatsDiff, StatsOwner, StatsOwnerRef, StatsPeriod, StatsRebuildReport};
 pub use time::Time;
@@ -0,0 +1,229 @@
+use actix_web::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map};
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        db::{DBAdvertiser, DBAudit, DBAuditRecord, DBModel, DBSegment},
+        url::Pagination,
+        AuditAction, Caller,
+    },
+    routes::ApiError,
+    utils::validation::check_profanity,
+};
+
+use super::{Advertiser, Targeting, Time};
+
+#[derive(Deserialize, Serialize, ToSchema, Debug)]
+#[schema(description = "Объект, представляющий сохранённый сегмент аудитории.")]
+pub struct Segment {
+    #[serde(rename = "segment_id")]
+    /// Уникальный идентификатор сегмента (UUID).
+    pub id: Uuid,
+
+    /// UUID рекламодателя, которому принадлежит сегмент.
+    pub advertiser_id: Uuid,
+
+    #[serde(flatten)]
+    pub inner: CreateSegment,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
+#[schema(description = "Объект для создания нового сегмента аудитории.")]
+pub struct CreateSegment {
+    #[validate(length(min = 1, max = 256), custom(function = "check_profanity"))]
+    /// Название сегмента.
+    pub name: String,
+
+    #[schema(examples(Targeting::example))]
+    #[validate(nested)]
+    pub targeting: Targeting,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate, Debug)]
+#[schema(
+    description = "Объект для обновления сегмента аудитории. Изменения применяются ко всем кампаниям, использующим сегмент. \
+        Таргетинг нельзя изменить, если сегмент использует уже начавшаяся кампания."
+)]
+pub struct SegmentUpdate {
+    #[validate(length(min = 1, max = 256), custom(function = "check_profanity"))]
+    /// Новое название сегмента.
+    pub name: Option<String>,
+
+    #[schema(examples(Targeting::example))]
+    #[validate(nested)]
+    pub targeting: Option<Targeting>,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Оценка охвата сегмента аудитории.")]
+pub struct SegmentReach {
+    /// UUID сегмента.
+    pub segment_id: Uuid,
+
+    /// Количество клиентов, подходящих под таргетинг сегмента.
+    pub clients_count: i64,
+}
+
+impl Segment {
+    pub async fn create(
+        advertiser_id: Uuid,
+        segment: CreateSegment,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+
+        let segment = Self {
+            id: Uuid::now_v7(),
+            advertiser_id,
+            inner: segment,
+        };
+        Ok((&DBSegment::create(segment, db).await?).into())
+    }
+
+    pub async fn list(
+        advertiser_id: Uuid,
+        pagination: Pagination,
+        db: &SurrealDB,
+    ) -> Result<Vec<Self>, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+
+        let size: i64 = match pagination.size {
+            Some(size) if size < 57 => size,
+            Some(..) => 57,
+            None => 7,
+        };
+
+        if size == 0 {
+            return Ok(vec![]);
+        }
+
+        let offset: i64 = pagination.page.unwrap_or(0) * size;
+        Ok(DBSegment::list(advertiser_id, size, offset, db)
+            .await?
+            .iter()
+            .map(Self::from)
+            .collect())
+    }
+
+    pub async fn get_by_id(
+        advertiser_id: Uuid,
+        segment_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        match DBSegment::get(advertiser_id, segment_id, db).await? {
+            None => Err(ApiError::NotFound(format!(
+                "Segment with UUID `{}`",
+                segment_id,
+            ))),
+            Some(segment) => Ok((&segment).into()),
+        }
+    }
+
+    pub async fn update(
+        advertiser_id: Uuid,
+        segment_id: Uuid,
+        segment_update: SegmentUpdate,
+        caller: &Caller,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        let segment = Self::get_by_id(advertiser_id, segment_id, db).await?;
+
+        // Таргетинг начавшихся кампаний менять нельзя, в том числе через их сегмент
+        if segment_update.targeting.is_some()
+            && DBSegment::started_campaigns_count(segment_id, Time::get().current_date, db).await?
+                > 0
+        {
+            return Err(ApiError::CampaignStarted("targeting".into()));
+        }
+
+        // В журнал попадают только поля, переданные в обновлении
+        let mut before = Map::new();
+        let mut after = Map::new();
+        if let Some(name) = &segment_update.name {
+            before.insert("name".into(), json!(segment.inner.name));
+            after.insert("name".into(), json!(name));
+        }
+        if let Some(targeting) = &segment_update.targeting {
+            before.insert("targeting".into(), json!(segment.inner.targeting));
+            after.insert("targeting".into(), json!(targeting));
+        }
+        let record = DBAuditRecord::new(
+            AuditAction::SegmentUpdated,
+            Some(segment_id),
+            Some(advertiser_id),
+        )
+        .with_before(before)
+        .with_after(after);
+        let audit = DBAudit::new(vec![record], caller);
+
+        let segment = DBSegment::update(advertiser_id, segment_id, segment_update, audit, db)
+            .await?
+            .unwrap();
+
+        Ok((&segment).into())
+    }
+
+    pub async fn delete(
+        advertiser_id: Uuid,
+        segment_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Self, ApiError> {
+        Self::get_by_id(advertiser_id, segment_id, db).await?;
+
+        let campaigns_count = DBSegment::campaigns_count(segment_id, db).await?;
+        if campaigns_count > 0 {
+            return Err(ApiError::Custom {
+                error: "segment_in_use".into(),
+                status_code: StatusCode::CONFLICT,
+                message: format!("Segment is used by {campaigns_count} campaign(s)"),
+            });
+        }
+
+        let segment = DBSegment::delete(advertiser_id, segment_id, db)
+            .await?
+            .unwrap();
+
+        Ok((&segment).into())
+    }
+
+    pub async fn reach(
+        advertiser_id: Uuid,
+        segment_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<SegmentReach, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        let segment = match DBSegment::get(advertiser_id, segment_id, db).await? {
+            None => {
+                return Err(ApiError::NotFound(format!(
+                    "Segment with UUID `{}`",
+                    segment_id,
+                )))
+            }
+            Some(segment) => segment,
+        };
+
+        Ok(SegmentReach {
+            segment_id,
+            clients_count: segment.reach(db).await?,
+        })
+    }
+}
+
+impl From<&DBSegment> for Segment {
+    fn from(db: &DBSegment) -> Self {
+        Self {
+            id: DBSegment::record_id_to_uuid(&db.id),
+            advertiser_id: DBAdvertiser::record_id_to_uuid(&db.advertiser_id),
+            inner: CreateSegment {
+                name: db.name.clone(),
+                targeting: Targeting::from(db.targeting.clone()),
+            },
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
 use serde::Serialize;
 use surrealdb_helper::SurrealDB;
 use utoipa::ToSchema;
@@ -5,8 +8,15 @@ use uuid::Uuid;
 
 use crate::{
     models::{
-        db::stats::{DBStatsAdvertiser, DBStatsBasic, DBStatsCampaign, DBStatsModel},
+        db::{
+            stats::{
+                DBAudienceContribution, DBStatsAdvertiser, DBStatsBasic, DBStatsCampaign,
+                DBStatsDaily, DBStatsModel,
+            },
+            DBCampaign, DBCreative, DBModel,
+        },
         dto::{Advertiser, Campaign},
+        url::StatsRangeQuery,
     },
     routes::ApiError,
 };
@@ -30,17 +40,161 @@ pub struct Stats {
     /// Коэффициент конверсии, вычисляемый как (clicks_count / impressions_count * 100) в процентах.
     pub conversion: f32,
 
+    #[serde(with = "rust_decimal::serde::float")]
     #[schema(examples(0), minimum = 0)]
     /// Сумма денег, потраченная на показы рекламного объявления.
-    pub spent_impressions: f64,
+    pub spent_impressions: Decimal,
 
+    #[serde(with = "rust_decimal::serde::float")]
     #[schema(examples(0), minimum = 0)]
     /// Сумма денег, потраченная на переходы (клики) по рекламному объявлению.
-    pub spent_clicks: f64,
+    pub spent_clicks: Decimal,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(0), minimum = 0)]
+    /// Сумма денег, потраченная на конверсии.
+    pub spent_conversions: Decimal,
 
+    #[serde(with = "rust_decimal::serde::float")]
     #[schema(examples(0), minimum = 0)]
-    /// Общая сумма денег, потраченная на кампанию (показы и клики).
-    pub spent_total: f64,
+    /// Общая сумма денег, потраченная на кампанию (показы, клики и конверсии).
+    pub spent_total: Decimal,
+
+    #[schema(examples(0), minimum = 0)]
+    /// Общее количество уникальных конверсий (целевых действий после перехода).
+    pub conversions_count: u64,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(0), minimum = 0)]
+    /// Суммарная ценность конверсий, переданная рекламодателем.
+    pub conversion_value: Decimal,
+
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(examples(0), minimum = 0)]
+    /// Стоимость привлечения, вычисляемая как (spent_total / conversions_count).
+    /// Равна 0, если конверсий не было.
+    pub cost_per_acquisition: Decimal,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Объект, содержащий агрегированную статистику для креатива рекламной кампании."
+)]
+pub struct CreativeStats {
+    /// UUID креатива.
+    pub creative_id: Uuid,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Объект, содержащий агрегированную статистику для рекламной кампании рекламодателя."
+)]
+pub struct CampaignStats {
+    /// UUID рекламной кампании.
+    pub campaign_id: Uuid,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Объект, содержащий агрегированную статистику за период (день, неделю или месяц)."
+)]
+pub struct PeriodStats {
+    #[schema(examples(1), minimum = 1)]
+    /// Первый день периода.
+    pub start_date: i32,
+
+    #[schema(examples(1), minimum = 1)]
+    /// Последний день периода.
+    pub end_date: i32,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Статистика за период по каждой рекламной кампании рекламодателя.
+    /// Возвращается только при запросе разбивки по кампаниям.
+    pub campaigns: Option<Vec<CampaignStats>>,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Объект, содержащий агрегированную статистику для группы клиентов с одинаковым значением признака."
+)]
+pub struct AudienceSegmentStats {
+    #[schema(examples("MALE", "25-34", "Moscow"))]
+    /// Значение признака: пол, возрастная группа или местоположение клиентов.
+    pub segment: String,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(
+    description = "Объект, содержащий статистику, разбитую по полу, возрасту и местоположению клиентов, взаимодействовавших с объявлениями."
+)]
+pub struct AudienceStats {
+    /// Статистика по полу клиентов.
+    pub gender: Vec<AudienceSegmentStats>,
+
+    /// Статистика по возрастным группам клиентов (`0-17`, `18-24`, `25-34`, `35-44`, `45-54`, `55-64`, `65+`).
+    pub age: Vec<AudienceSegmentStats>,
+
+    /// Статистика по местоположению клиентов.
+    pub location: Vec<AudienceSegmentStats>,
+}
+
+impl AudienceStats {
+    fn from_contributions(contributions: Vec<DBAudienceContribution>) -> Self {
+        Self {
+            gender: Self::group_by(&contributions, |contribution| contribution.gender.clone()),
+            age: Self::group_by(&contributions, |contribution| {
+                contribution.age.map(|age| age_bucket(age).to_string())
+            }),
+            location: Self::group_by(&contributions, |contribution| contribution.location.clone()),
+        }
+    }
+
+    fn group_by(
+        contributions: &[DBAudienceContribution],
+        key: impl Fn(&DBAudienceContribution) -> Option<String>,
+    ) -> Vec<AudienceSegmentStats> {
+        let mut segments: BTreeMap<String, Vec<DBStatsBasic>> = BTreeMap::new();
+        for contribution in contributions {
+            if let Some(segment) = key(contribution) {
+                segments
+                    .entry(segment)
+                    .or_default()
+                    .push(contribution.stats());
+            }
+        }
+
+        segments
+            .into_iter()
+            .map(|(segment, stats)| AudienceSegmentStats {
+                segment,
+                stats: Stats::sum(&stats),
+            })
+            .collect()
+    }
+}
+
+fn age_bucket(age: i32) -> &'static str {
+    match age {
+        ..18 => "0-17",
+        18..25 => "18-24",
+        25..35 => "25-34",
+        35..45 => "35-44",
+        45..55 => "45-54",
+        55..65 => "55-64",
+        _ => "65+",
+    }
 }
 
 impl Stats {
@@ -55,7 +209,11 @@ impl Stats {
         }
     }
 
-    pub async fn campaign_daily(campaign_id: Uuid, db: &SurrealDB) -> Result<Vec<Self>, ApiError> {
+    pub async fn campaign_daily(
+        campaign_id: Uuid,
+        query: &StatsRangeQuery,
+        db: &SurrealDB,
+    ) -> Result<Vec<PeriodStats>, ApiError> {
         let campaign = Campaign::get_by_id_unchecked(campaign_id, db).await?;
         if campaign.get_status() == CampaignStatus::NotStarted {
             return Ok(vec![]);
@@ -65,10 +223,45 @@ impl Stats {
                 "Stats for campaign with UUID `{}`",
                 campaign_id,
             ))),
-            Some(stats) => Ok(Self::from_db_daily(stats)),
+            Some(stats) => Ok(Self::rollup(stats.daily(), query)
+                .into_iter()
+                .map(|((start_date, end_date), stats)| PeriodStats {
+                    start_date,
+                    end_date,
+                    stats,
+                    campaigns: None,
+                })
+                .collect()),
         }
     }
 
+    pub async fn campaign_creatives(
+        campaign_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<Vec<CreativeStats>, ApiError> {
+        Campaign::get_by_id_unchecked(campaign_id, db).await?;
+        Ok(DBCreative::list(campaign_id, None, 0, db)
+            .await?
+            .iter()
+            .filter_map(|creative| {
+                creative.stats.as_ref().map(|stats| CreativeStats {
+                    creative_id: DBCreative::record_id_to_uuid(&creative.id),
+                    stats: Self::from(stats),
+                })
+            })
+            .collect())
+    }
+
+    pub async fn campaign_audience(
+        campaign_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<AudienceStats, ApiError> {
+        Campaign::get_by_id_unchecked(campaign_id, db).await?;
+        Ok(AudienceStats::from_contributions(
+            DBAudienceContribution::list_by_campaign(campaign_id, db).await?,
+        ))
+    }
+
     pub async fn advertiser(advertiser_id: Uuid, db: &SurrealDB) -> Result<Self, ApiError> {
         Advertiser::get_by_id(advertiser_id, db).await?;
         match DBStatsAdvertiser::get(advertiser_id, db).await? {
@@ -82,24 +275,85 @@ impl Stats {
 
     pub async fn advertiser_daily(
         advertiser_id: Uuid,
+        query: &StatsRangeQuery,
+        by_campaign: bool,
         db: &SurrealDB,
-    ) -> Result<Vec<Self>, ApiError> {
+    ) -> Result<Vec<PeriodStats>, ApiError> {
         Advertiser::get_by_id(advertiser_id, db).await?;
-        match DBStatsAdvertiser::get(advertiser_id, db).await? {
-            None => Err(ApiError::NotFound(format!(
-                "Stats for campaign with UUID `{}`",
-                advertiser_id,
-            ))),
-            Some(stats) => Ok(Self::from_db_daily(stats)),
+        let stats = match DBStatsAdvertiser::get(advertiser_id, db).await? {
+            None => {
+                return Err(ApiError::NotFound(format!(
+                    "Stats for campaign with UUID `{}`",
+                    advertiser_id,
+                )))
+            }
+            Some(stats) => stats,
+        };
+
+        let mut campaigns: BTreeMap<(i32, i32), Vec<CampaignStats>> = BTreeMap::new();
+        if by_campaign {
+            for campaign_stats in DBStatsCampaign::list_by_advertiser(advertiser_id, db).await? {
+                let campaign_id = DBCampaign::record_id_to_uuid(&campaign_stats.campaign_id);
+                for (period, stats) in Self::rollup(campaign_stats.daily(), query) {
+                    campaigns
+                        .entry(period)
+                        .or_default()
+                        .push(CampaignStats { campaign_id, stats });
+                }
+            }
         }
+
+        Ok(Self::rollup(stats.daily(), query)
+            .into_iter()
+            .map(|(period, stats)| PeriodStats {
+                start_date: period.0,
+                end_date: period.1,
+                stats,
+                campaigns: by_campaign.then(|| campaigns.remove(&period).unwrap_or_default()),
+            })
+            .collect())
+    }
+
+    pub async fn advertiser_audience(
+        advertiser_id: Uuid,
+        db: &SurrealDB,
+    ) -> Result<AudienceStats, ApiError> {
+        Advertiser::get_by_id(advertiser_id, db).await?;
+        Ok(AudienceStats::from_contributions(
+            DBAudienceContribution::list_by_advertiser(advertiser_id, db).await?,
+        ))
     }
 
     pub fn from_db_total(db: impl DBStatsModel) -> Self {
         Self::from(&db.total())
     }
 
-    pub fn from_db_daily(db: impl DBStatsModel) -> Vec<Self> {
-        db.daily().iter().map(Self::from).collect()
+    /// Группирует ежедневную статистику из диапазона `query` по периодам
+    /// и суммирует её внутри каждого периода. Записи без даты не попадают
+    /// ни в один период и учитываются только в общей статистике.
+    fn rollup(daily: Vec<DBStatsDaily>, query: &StatsRangeQuery) -> BTreeMap<(i32, i32), Self> {
+        let granularity = query.granularity.unwrap_or_default();
+
+        let mut periods: BTreeMap<(i32, i32), Vec<DBStatsBasic>> = BTreeMap::new();
+        for (date, stats) in daily
+            .into_iter()
+            .filter_map(|daily| Some((daily.date?, daily.stats)))
+            .filter(|(date, _)| query.contains(*date))
+        {
+            periods
+                .entry(granularity.period(date))
+                .or_default()
+                .push(stats);
+        }
+
+        periods
+            .into_iter()
+            .map(|(period, stats)| (period, Self::sum(&stats)))
+            .collect()
+    }
+
+    fn sum(stats: &[DBStatsBasic]) -> Self {
+        Self::from(&DBStatsBasic::sum(stats))
     }
 }
 
@@ -111,7 +365,29 @@ impl From<&DBStatsBasic> for Stats {
             conversion: db.conversion,
             spent_impressions: db.spent_impressions,
             spent_clicks: db.spent_clicks,
+            spent_conversions: db.spent_conversions,
             spent_total: db.spent_total,
+            conversions_count: db.conversions_count,
+            conversion_value: db.conversion_value,
+            cost_per_acquisition: db.cost_per_acquisition,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::age_bucket;
+
+    #[rstest]
+    #[case(0, "0-17")]
+    #[case(17, "0-17")]
+    #[case(18, "18-24")]
+    #[case(34, "25-34")]
+    #[case(64, "55-64")]
+    #[case(65, "65+")]
+    fn age_bucket_bounds(#[case] age: i32, #[case] expected: &str) {
+        assert_eq!(age_bucket(age), expected);
+    }
+}
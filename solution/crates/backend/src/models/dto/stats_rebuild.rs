@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use surrealdb::RecordId;
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        db::{
+            stats::{
+                DBStats, DBStatsAdvertiser, DBStatsBasic, DBStatsCampaign, DBStatsDaily,
+                DBStatsModel, DBStatsSnapshot,
+            },
+            DBAdvertiser, DBCampaign, DBModel,
+        },
+        dto::Stats,
+    },
+    routes::ApiError,
+};
+
+#[derive(Serialize, ToSchema, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+/// Объект, статистика которого была пересчитана.
+pub enum StatsOwner {
+    Campaign,
+    Advertiser,
+}
+
+#[derive(Serialize, ToSchema, PartialEq, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+/// Часть статистики, которая была пересчитана.
+pub enum StatsPeriod {
+    /// Статистика за текущий день.
+    Current,
+    /// Статистика за всё время.
+    Total,
+    /// Статистика за один из прошедших дней.
+    Daily,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Расхождение между сохранённой и пересчитанной статистикой.")]
+pub struct StatsDiff {
+    /// Тип объекта, которому принадлежит статистика.
+    pub owner: StatsOwner,
+
+    /// UUID рекламной кампании или рекламодателя.
+    pub owner_id: Uuid,
+
+    /// Часть статистики, в которой найдено расхождение.
+    pub period: StatsPeriod,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(examples(1), minimum = 1)]
+    /// День, к которому относится статистика. Указывается только для `daily`.
+    pub date: Option<i32>,
+
+    /// Сохранённая статистика.
+    pub before: Stats,
+
+    /// Статистика, пересчитанная по истории взаимодействий.
+    pub after: Stats,
+}
+
+#[derive(Serialize, ToSchema, PartialEq, Clone, Copy, Debug)]
+#[schema(description = "Рекламная кампания или рекламодатель, к которым относится статистика.")]
+pub struct StatsOwnerRef {
+    /// Тип объекта, которому принадлежит статистика.
+    pub owner: StatsOwner,
+
+    /// UUID рекламной кампании или рекламодателя.
+    pub owner_id: Uuid,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+#[schema(description = "Результат пересчёта статистики по истории взаимодействий.")]
+pub struct StatsRebuildReport {
+    /// Были ли изменения только вычислены, без сохранения в базу данных.
+    pub dry_run: bool,
+
+    /// Найденные расхождения. Пустой массив означает, что статистика не разошлась с историей.
+    pub diffs: Vec<StatsDiff>,
+
+    /// Статистика, в которую во время пересчёта были записаны новые взаимодействия.
+    /// Она не сохраняется, пересчёт нужно повторить.
+    pub skipped: Vec<StatsOwnerRef>,
+
+    /// Статистика, расходы которой не пересчитываются: у части конверсий не сохранена
+    /// цена, по которой они были списаны. Пересчитываются только количества.
+    pub unpriced: Vec<StatsOwnerRef>,
+}
+
+/// Статистика, накопленная по дням для одной кампании или рекламодателя.
+#[derive(Default)]
+struct DailyContributions {
+    daily: BTreeMap<i32, Vec<DBStatsBasic>>,
+    unpriced: bool,
+}
+
+impl StatsRebuildReport {
+    /// Пересчитывает `current`, `total` и `daily` статистики всех кампаний и рекламодателей
+    /// по `interacted_with` и вкладу окончательно удалённых кампаний, сохранённому при удалении.
+    /// Дни в `daily` сохраняются, пересчитываются только их значения.
+    /// Статистика и взаимодействия читаются в одной транзакции, а пересчитанная статистика
+    /// сохраняется, только если за время пересчёта она не изменилась.
+    pub async fn rebuild(dry_run: bool, db: &SurrealDB) -> Result<Self, ApiError> {
+        let snapshot = DBStatsSnapshot::take(db).await?;
+
+        let mut campaigns: HashMap<Uuid, DailyContributions> = HashMap::new();
+        let mut advertisers: HashMap<Uuid, DailyContributions> = HashMap::new();
+        for contribution in &snapshot.contributions {
+            for contributions in [
+                campaigns
+                    .entry(DBCampaign::record_id_to_uuid(&contribution.campaign_id))
+                    .or_default(),
+                advertisers
+                    .entry(DBAdvertiser::record_id_to_uuid(&contribution.advertiser_id))
+                    .or_default(),
+            ] {
+                contributions
+                    .daily
+                    .entry(contribution.date)
+                    .or_default()
+                    .push(contribution.stats());
+                contributions.unpriced |= contribution.unpriced_conversions_count > 0;
+            }
+        }
+
+        let mut report = Self {
+            dry_run,
+            diffs: vec![],
+            skipped: vec![],
+            unpriced: vec![],
+        };
+        for stats in snapshot.campaigns {
+            let owner_id = DBCampaign::record_id_to_uuid(&stats.campaign_id);
+            let contributions = campaigns.remove(&owner_id).unwrap_or_default();
+            report
+                .rebuild_stats(
+                    &stats.id,
+                    StatsOwnerRef {
+                        owner: StatsOwner::Campaign,
+                        owner_id,
+                    },
+                    stats.stats(),
+                    &contributions,
+                    snapshot.current_date,
+                    db,
+                )
+                .await?;
+        }
+        for stats in snapshot.advertisers {
+            let owner_id = DBAdvertiser::record_id_to_uuid(&stats.advertiser_id);
+            let contributions = advertisers.remove(&owner_id).unwrap_or_default();
+            report
+                .rebuild_stats(
+                    &stats.id,
+                    StatsOwnerRef {
+                        owner: StatsOwner::Advertiser,
+                        owner_id,
+                    },
+                    stats.stats(),
+                    &contributions,
+                    snapshot.current_date,
+                    db,
+                )
+                .await?;
+        }
+
+        Ok(report)
+    }
+
+    async fn rebuild_stats(
+        &mut self,
+        id: &RecordId,
+        owner: StatsOwnerRef,
+        stats: DBStats,
+        contributions: &DailyContributions,
+        current_date: i32,
+        db: &SurrealDB,
+    ) -> Result<(), ApiError> {
+        let rebuilt = Self::rebuilt_stats(&stats, contributions, current_date);
+        let diffs_count = self.diffs.len();
+        if contributions.unpriced {
+            self.unpriced.push(owner);
+        }
+
+        let mut push_diff = |period, date, before: &DBStatsBasic, after: &DBStatsBasic| {
+            if stats_differ(before, after) {
+                self.diffs.push(StatsDiff {
+                    owner: owner.owner,
+                    owner_id: owner.owner_id,
+                    period,
+                    date,
+                    before: Stats::from(before),
+                    after: Stats::from(after),
+                });
+            }
+        };
+        push_diff(StatsPeriod::Current, None, &stats.current, &rebuilt.current);
+        push_diff(StatsPeriod::Total, None, &stats.total, &rebuilt.total);
+        for (before, after) in stats.daily.iter().zip(&rebuilt.daily) {
+            push_diff(StatsPeriod::Daily, before.date, &before.stats, &after.stats);
+        }
+
+        if !self.dry_run && self.diffs.len() > diffs_count {
+            let replaced = match owner.owner {
+                StatsOwner::Campaign => {
+                    DBStatsCampaign::replace_if_unchanged(id, &stats, rebuilt, db)
+                        .await?
+                        .is_some()
+                }
+                StatsOwner::Advertiser => {
+                    DBStatsAdvertiser::replace_if_unchanged(id, &stats, rebuilt, db)
+                        .await?
+                        .is_some()
+                }
+            };
+            if !replaced {
+                self.skipped.push(owner);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn rebuilt_stats(
+        stats: &DBStats,
+        contributions: &DailyContributions,
+        current_date: i32,
+    ) -> DBStats {
+        let rebuild = |before: &DBStatsBasic, after: DBStatsBasic| {
+            if contributions.unpriced {
+                with_spent(before, after)
+            } else {
+                after
+            }
+        };
+        let day =
+            |date: i32| DBStatsBasic::sum(contributions.daily.get(&date).into_iter().flatten());
+
+        DBStats {
+            current: rebuild(&stats.current, day(current_date)),
+            total: rebuild(
+                &stats.total,
+                DBStatsBasic::sum(contributions.daily.values().flatten()),
+            ),
+            daily: stats
+                .daily
+                .iter()
+                .map(|daily| DBStatsDaily {
+                    date: daily.date,
+                    // Записи без даты не сопоставить с историей, поэтому они не меняются
+                    stats: match daily.date {
+                        Some(date) => rebuild(&daily.stats, day(date)),
+                        None => daily.stats.clone(),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Оставляет в пересчитанной статистике сохранённые расходы и зависящую от них цену привлечения.
+fn with_spent(before: &DBStatsBasic, after: DBStatsBasic) -> DBStatsBasic {
+    DBStatsBasic {
+        spent_impressions: before.spent_impressions,
+        spent_clicks: before.spent_clicks,
+        spent_conversions: before.spent_conversions,
+        spent_total: before.spent_total,
+        cost_per_acquisition: before.cost_per_acquisition,
+        ..after
+    }
+}
+
+/// Сравнивает статистику. Денежные значения хранятся в decimal, поэтому сравниваются
+/// без округления: `0.3` и `0.30` считаются равными.
+fn stats_differ(before: &DBStatsBasic, after: &DBStatsBasic) -> bool {
+    before.impressions_count != after.impressions_count
+        || before.clicks_count != after.clicks_count
+        || before.conversions_count != after.conversions_count
+        || before.spent_impressions != after.spent_impressions
+        || before.spent_clicks != after.spent_clicks
+        || before.spent_conversions != after.spent_conversions
+        || before.spent_total != after.spent_total
+        || before.conversion_value != after.conversion_value
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use crate::models::db::stats::DBStatsBasic;
+
+    use super::stats_differ;
+
+    #[test]
+    fn stats_differ_ignores_scale() {
+        let before = DBStatsBasic {
+            spent_impressions: Decimal::new(1, 1) + Decimal::new(2, 1),
+            spent_total: Decimal::new(1, 1) + Decimal::new(2, 1),
+            ..DBStatsBasic::empty()
+        };
+        let after = DBStatsBasic {
+            spent_impressions: Decimal::new(30, 2),
+            spent_total: Decimal::new(30, 2),
+            ..DBStatsBasic::empty()
+        };
+        assert!(!stats_differ(&before, &after));
+
+        let after = DBStatsBasic {
+            impressions_count: 1,
+            ..after
+        };
+        assert!(stats_differ(&before, &after));
+    }
+}
@@ -5,7 +5,13 @@ use surrealdb_helper::SurrealDB;
 use utoipa::ToSchema;
 use validator::Validate;
 
-use crate::{models::db::DBTime, routes::ApiError};
+use crate::{
+    models::{
+        db::{DBAudit, DBAuditRecord, DBIdempotencyKey, DBTime},
+        AuditAction, Caller,
+    },
+    routes::ApiError,
+};
 
 static CURRENT_DAY: RwLock<i32> = RwLock::new(1);
 
@@ -24,7 +30,7 @@ impl Time {
         }
     }
 
-    pub async fn advance_day(self, db: &SurrealDB) -> Result<Self, ApiError> {
+    pub async fn advance_day(self, caller: &Caller, db: &SurrealDB) -> Result<Self, ApiError> {
         match (Self::get().current_date, self.current_date) {
             (cd, nd) if cd > nd => {
                 return Err(ApiError::InvalidInput(
@@ -33,8 +39,15 @@ impl Time {
                 ))
             }
             (cd, nd) if cd < nd => {
-                let mut current_day = CURRENT_DAY.write().unwrap();
-                *current_day = DBTime::upsert(self, db).await?.current_date;
+                let record = DBAuditRecord::new(AuditAction::TimeAdvanced, None, None)
+                    .with_before(Self { current_date: cd })
+                    .with_after(&self);
+                let audit = DBAudit::new(vec![record], caller);
+
+                let new_day = DBTime::upsert(self, audit, db).await?.current_date;
+                *CURRENT_DAY.write().unwrap() = new_day;
+
+                DBIdempotencyKey::purge_expired(db).await?;
             }
             _ => (),
         };
@@ -0,0 +1,189 @@
+use std::future::{ready, Future, Ready};
+
+use actix_web::{
+    dev::Payload,
+    http::{
+        header::{ContentType, HeaderName, HeaderValue},
+        StatusCode,
+    },
+    web::Data,
+    FromRequest, HttpMessage, HttpRequest, HttpResponse,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use surrealdb_helper::SurrealDB;
+
+use crate::{
+    config,
+    models::db::{DBIdempotencyKey, DBIdempotentRequest},
+    routes::ApiError,
+};
+
+use super::Caller;
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// Заголовок, которым помечаются ответы, возвращённые из сохранённых.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Ключ идемпотентности из заголовка `Idempotency-Key`. Ключи различаются
+/// для каждого владельца ключа API и метода, поэтому один и тот же ключ
+/// можно использовать для разных методов.
+pub struct IdempotencyKey {
+    /// Владелец ключа API, шаблон пути метода и сам ключ.
+    id: Option<[String; 3]>,
+    path: String,
+    db: Data<SurrealDB>,
+}
+
+impl IdempotencyKey {
+    fn parse(req: &HttpRequest) -> Result<Self, ApiError> {
+        let db = req
+            .app_data::<Data<SurrealDB>>()
+            .expect("SurrealDB should be registered as app data")
+            .clone();
+        let path = req.path().to_string();
+
+        let Some(header) = req.headers().get(IDEMPOTENCY_KEY) else {
+            return Ok(Self { id: None, path, db });
+        };
+
+        let key = header
+            .to_str()
+            .ok()
+            .map(str::trim)
+            .filter(|key| !key.is_empty() && key.len() <= 255)
+            .ok_or_else(|| {
+                ApiError::InvalidInput(
+                    "`Idempotency-Key` header must contain from 1 to 255 visible ASCII characters"
+                        .into(),
+                )
+            })?;
+
+        let caller = req
+            .extensions()
+            .get::<Caller>()
+            .cloned()
+            .ok_or(ApiError::Unauthorized)?;
+        let owner = match caller.api_key_id {
+            Some(api_key_id) => api_key_id.to_string(),
+            None => caller.role.to_string(),
+        };
+        let scope = format!(
+            "{} {}",
+            req.method(),
+            req.match_pattern().unwrap_or_else(|| path.clone())
+        );
+
+        Ok(Self {
+            id: Some([owner, scope, key.to_string()]),
+            path,
+            db,
+        })
+    }
+
+    /// Выполняет обработчик запроса не более одного раза для ключа идемпотентности.
+    /// Повторный запрос с тем же ключом получает сохранённый ответ, запрос с тем же
+    /// ключом, но другим телом или путём, отклоняется. Ответы с ошибками
+    /// не сохраняются, и запрос с тем же ключом можно повторить. Если ответ
+    /// так и не был сохранён, ключ освобождается по истечении аренды
+    /// `IDEMPOTENCY_KEY_LEASE_SECONDS`.
+    pub async fn handle<R, T, Fut>(
+        self,
+        status: StatusCode,
+        request: R,
+        handler: impl FnOnce(R) -> Fut,
+    ) -> Result<HttpResponse, ApiError>
+    where
+        R: Serialize,
+        T: Serialize,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        let Some([owner, scope, key]) = &self.id else {
+            let body = Self::serialize_body(handler(request).await?)?;
+            return Ok(Self::response(status, body));
+        };
+        let id = [owner.as_str(), scope.as_str(), key.as_str()];
+
+        let fingerprint = json!({
+            "path": self.path,
+            "body": request
+        });
+        let reserved = DBIdempotencyKey::reserve(
+            id,
+            &fingerprint,
+            *config::IDEMPOTENCY_KEY_LEASE_SECONDS,
+            &self.db,
+        )
+        .await?;
+
+        match reserved {
+            Some(DBIdempotentRequest {
+                same_request: false,
+                ..
+            }) => Err(ApiError::Custom {
+                error: "idempotency_key_reused".into(),
+                status_code: StatusCode::CONFLICT,
+                message: "Idempotency key was already used for a different request".into(),
+            }),
+            Some(DBIdempotentRequest { status: None, .. }) => Err(ApiError::Custom {
+                error: "idempotency_key_in_use".into(),
+                status_code: StatusCode::CONFLICT,
+                message: "Request with this idempotency key is still being processed".into(),
+            }),
+            Some(DBIdempotentRequest {
+                status: Some(stored_status),
+                body,
+                ..
+            }) => {
+                let stored_status = StatusCode::from_u16(stored_status).unwrap_or(status);
+                let mut response = Self::response(stored_status, body);
+                response
+                    .headers_mut()
+                    .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+                Ok(response)
+            }
+            None => {
+                let body = match handler(request).await {
+                    Ok(body) => Self::serialize_body(body)?,
+                    Err(err) => {
+                        DBIdempotencyKey::release(id, &self.db).await?;
+                        return Err(err);
+                    }
+                };
+                DBIdempotencyKey::store_response(
+                    id,
+                    status.as_u16(),
+                    body.clone(),
+                    *config::IDEMPOTENCY_KEY_TTL_SECONDS,
+                    &self.db,
+                )
+                .await?;
+                Ok(Self::response(status, body))
+            }
+        }
+    }
+
+    fn serialize_body(body: impl Serialize) -> Result<Option<String>, ApiError> {
+        Ok(match serde_json::to_value(body)? {
+            Value::Null => None,
+            body => Some(body.to_string()),
+        })
+    }
+
+    fn response(status: StatusCode, body: Option<String>) -> HttpResponse {
+        let mut response = HttpResponse::build(status);
+        match body {
+            Some(body) => response.content_type(ContentType::json()).body(body),
+            None => response.finish(),
+        }
+    }
+}
+
+impl FromRequest for IdempotencyKey {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Self::parse(req))
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Тип записи в журнале списаний и пополнений рекламодателя.
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LedgerEntryKind {
+    /// Пополнение баланса.
+    TopUp,
+    /// Списание за показ.
+    Impression,
+    /// Списание за переход.
+    Click,
+    /// Списание за конверсию.
+    Conversion,
+}
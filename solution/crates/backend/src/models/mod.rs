@@ -1,12 +1,34 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+mod attribute;
+mod attribution_window;
+mod auction;
+mod audit_action;
+mod caller;
+mod campaign_state;
+mod creative_selection;
 mod db;
 pub mod dto;
 mod gender;
+mod idempotency_key;
+mod ledger_entry_kind;
+mod role;
 pub mod url;
 
+pub use attribute::{
+    validate_attribute_name, validate_attributes, AttributeValue, Attributes, RuleOperator,
+};
+pub use attribution_window::AttributionWindow;
+pub use auction::{AuctionMode, AUCTION_MODE};
+pub use audit_action::{AuditAction, AuditEntity};
+pub use caller::Caller;
+pub use campaign_state::CampaignState;
+pub use creative_selection::{CreativeSelection, CREATIVE_SELECTION};
 pub use gender::{Gender, TargetingGender};
+pub use idempotency_key::{IdempotencyKey, IDEMPOTENCY_KEY, IDEMPOTENT_REPLAYED};
+pub use ledger_entry_kind::LedgerEntryKind;
+pub use role::Role;
 
 #[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct ApiError {
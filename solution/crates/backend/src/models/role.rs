@@ -0,0 +1,184 @@
+use std::fmt;
+
+use actix_web::http::Method;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Роль владельца ключа API.
+#[derive(Deserialize, Serialize, ToSchema, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Role {
+    /// Администратор платформы, имеет доступ ко всем методам.
+    Admin,
+    /// Оператор платформы: управление текущим днём и массовая загрузка клиентов и рекламодателей.
+    Operator,
+    /// Рекламодатель, имеет доступ только к собственным данным.
+    Advertiser,
+    /// Клиентское приложение: получение объявлений и фиксация кликов.
+    ClientApp,
+    /// Команда анализа данных: загрузка ML скоров.
+    DataScience,
+}
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let role = match self {
+            Self::Admin => "ADMIN",
+            Self::Operator => "OPERATOR",
+            Self::Advertiser => "ADVERTISER",
+            Self::ClientApp => "CLIENT_APP",
+            Self::DataScience => "DATA_SCIENCE",
+        };
+        write!(f, "{role}")
+    }
+}
+
+impl Role {
+    /// Роли, ключи которых выпускаются без привязки к рекламодателю.
+    pub fn is_platform(&self) -> bool {
+        matches!(self, Self::Operator | Self::ClientApp | Self::DataScience)
+    }
+
+    /// Проверяет, разрешён ли роли вызов метода по шаблону пути маршрута
+    /// (например, `/ads/{ad_id}/click`). Принадлежность данных конкретному
+    /// рекламодателю проверяется отдельно.
+    pub fn permits(&self, method: &Method, pattern: &str) -> bool {
+        match self {
+            Self::Admin => true,
+            Self::Operator => matches!(
+                (method.as_str(), pattern),
+                (
+                    "POST",
+                    "/time/advance" | "/clients/bulk" | "/advertisers/bulk"
+                )
+            ),
+            Self::Advertiser => {
+                (pattern.starts_with("/advertisers/{advertiser_id}")
+                    && !(method == Method::POST
+                        && pattern == "/advertisers/{advertiser_id}/billing/top-ups"))
+                    || pattern.starts_with("/statistics/advertisers/{advertiser_id}")
+                    || pattern.starts_with("/statistics/campaigns/{campaign_id}")
+                    || matches!(
+                        (method.as_str(), pattern),
+                        ("POST", "/ml-scores" | "/ads/{ad_id}/conversion")
+                    )
+            }
+            Self::ClientApp => matches!(
+                (method.as_str(), pattern),
+                ("GET", "/ads") | ("POST", "/ads/{ad_id}/click")
+            ),
+            Self::DataScience => method == Method::POST && pattern == "/ml-scores",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::Method, App};
+    use rstest::rstest;
+    use utoipa::{openapi::path::Operation, OpenApi};
+    use utoipa_actix_web::AppExt;
+
+    use crate::{routes, utils::openapi::Swagger};
+
+    use super::Role::{self, *};
+
+    #[rstest]
+    #[case(Admin, Method::POST, "/statistics/rebuild", true)]
+    #[case(Operator, Method::POST, "/time/advance", true)]
+    #[case(Operator, Method::POST, "/clients/bulk", true)]
+    #[case(Operator, Method::POST, "/advertisers/bulk", true)]
+    #[case(Operator, Method::GET, "/ads", false)]
+    #[case(Advertiser, Method::GET, "/advertisers/{advertiser_id}", true)]
+    #[case(
+        Advertiser,
+        Method::POST,
+        "/advertisers/{advertiser_id}/campaigns",
+        true
+    )]
+    #[case(
+        Advertiser,
+        Method::GET,
+        "/statistics/advertisers/{advertiser_id}/daily",
+        true
+    )]
+    #[case(
+        Advertiser,
+        Method::GET,
+        "/advertisers/{advertiser_id}/billing/balance",
+        true
+    )]
+    #[case(
+        Advertiser,
+        Method::POST,
+        "/advertisers/{advertiser_id}/billing/top-ups",
+        false
+    )]
+    #[case(Advertiser, Method::POST, "/ml-scores", true)]
+    #[case(Advertiser, Method::POST, "/advertisers/bulk", false)]
+    #[case(Advertiser, Method::GET, "/statistics/campaigns/{campaign_id}", true)]
+    #[case(
+        Advertiser,
+        Method::GET,
+        "/statistics/campaigns/{campaign_id}/export/daily",
+        true
+    )]
+    #[case(Advertiser, Method::POST, "/ads/{ad_id}/conversion", true)]
+    #[case(Advertiser, Method::POST, "/ads/{ad_id}/click", false)]
+    #[case(Advertiser, Method::GET, "/ads", false)]
+    #[case(ClientApp, Method::GET, "/ads", true)]
+    #[case(ClientApp, Method::POST, "/ads/{ad_id}/click", true)]
+    #[case(ClientApp, Method::POST, "/ads/{ad_id}/conversion", false)]
+    #[case(ClientApp, Method::GET, "/ads/explain", false)]
+    #[case(DataScience, Method::POST, "/ml-scores", true)]
+    #[case(DataScience, Method::POST, "/clients/bulk", false)]
+    fn permits(
+        #[case] role: Role,
+        #[case] method: Method,
+        #[case] pattern: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(role.permits(&method, pattern), expected);
+    }
+
+    /// Роли в `security(...)` документации каждого метода совпадают с ролями,
+    /// которым `Role::permits` разрешает его вызов.
+    #[test]
+    fn permits_matches_openapi_security() {
+        let (_, openapi) = App::new()
+            .into_utoipa_app()
+            .openapi(Swagger::openapi())
+            .configure(routes::config)
+            .split_for_parts();
+
+        for (path, item) in openapi.paths.paths {
+            let operations = [
+                (Method::GET, item.get),
+                (Method::POST, item.post),
+                (Method::PUT, item.put),
+                (Method::PATCH, item.patch),
+                (Method::DELETE, item.delete),
+            ];
+            for (method, operation) in operations {
+                let Some(Operation { security, .. }) = operation else {
+                    continue;
+                };
+                let documented: Vec<String> = serde_json::to_value(security)
+                    .unwrap()
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|requirement| requirement.get("api_key"))
+                    .flat_map(|roles| serde_json::from_value::<Vec<String>>(roles.clone()).unwrap())
+                    .collect();
+
+                for role in [Admin, Operator, Advertiser, ClientApp, DataScience] {
+                    assert_eq!(
+                        role.permits(&method, &path),
+                        documented.contains(&role.to_string()),
+                        "{role} {method} {path}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,23 @@
 use serde::Deserialize;
 use utoipa::IntoParams;
 use uuid::Uuid;
+use validator::Validate;
 
 #[derive(Deserialize, IntoParams, Debug)]
 pub struct AdIdPath {
     pub ad_id: Uuid,
 }
+
+#[derive(Deserialize, IntoParams, Validate, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct AdSlotsQuery {
+    /// UUID клиента, запрашивающего показ объявлений.
+    pub client_id: Uuid,
+
+    #[validate(range(min = 1, max = 20))]
+    /// Количество рекламных слотов. Если указано, возвращается массив из не более чем `slots` объявлений.
+    pub slots: Option<u32>,
+
+    /// Возвращать не более одного объявления от каждого рекламодателя.
+    pub unique_advertisers: Option<bool>,
+}
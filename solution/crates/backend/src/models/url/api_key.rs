@@ -0,0 +1,14 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams, Debug)]
+pub struct AdvertiserIdApiKeyIdPath {
+    pub advertiser_id: Uuid,
+    pub api_key_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+pub struct ApiKeyIdPath {
+    pub api_key_id: Uuid,
+}
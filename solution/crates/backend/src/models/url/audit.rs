@@ -0,0 +1,37 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::models::AuditEntity;
+
+#[derive(Deserialize, IntoParams, Validate, Debug)]
+#[into_params(parameter_in = Query)]
+#[validate(schema(function = "Self::validate_custom"))]
+pub struct AuditQuery {
+    /// Тип сущности, изменения которой запрашиваются.
+    pub entity: Option<AuditEntity>,
+
+    /// UUID сущности, изменения которой запрашиваются.
+    pub entity_id: Option<Uuid>,
+
+    /// UUID рекламодателя, к данным которого относятся изменения.
+    pub advertiser_id: Option<Uuid>,
+
+    #[validate(range(min = 1))]
+    /// Первый день периода (включительно).
+    pub from: Option<i32>,
+
+    #[validate(range(min = 1))]
+    /// Последний день периода (включительно).
+    pub to: Option<i32>,
+}
+
+impl AuditQuery {
+    fn validate_custom(&self) -> Result<(), ValidationError> {
+        if self.from.unwrap_or(i32::MIN) > self.to.unwrap_or(i32::MAX) {
+            return Err(ValidationError::new("`from` can't be greater than `to`"));
+        }
+        Ok(())
+    }
+}
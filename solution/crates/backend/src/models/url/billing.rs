@@ -0,0 +1,25 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use validator::{Validate, ValidationError};
+
+#[derive(Deserialize, IntoParams, Validate, Debug)]
+#[into_params(parameter_in = Query)]
+#[validate(schema(function = "Self::validate_custom"))]
+pub struct InvoiceQuery {
+    #[validate(range(min = 1))]
+    /// Первый день расчётного периода (включительно).
+    pub from: i32,
+
+    #[validate(range(min = 1))]
+    /// Последний день расчётного периода (включительно).
+    pub to: i32,
+}
+
+impl InvoiceQuery {
+    fn validate_custom(&self) -> Result<(), ValidationError> {
+        if self.from > self.to {
+            return Err(ValidationError::new("`from` can't be greater than `to`"));
+        }
+        Ok(())
+    }
+}
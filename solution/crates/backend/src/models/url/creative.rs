@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams, Debug)]
+pub struct AdvertiserIdCampaignIdCreativeIdPath {
+    pub advertiser_id: Uuid,
+    pub campaign_id: Uuid,
+    pub creative_id: Uuid,
+}
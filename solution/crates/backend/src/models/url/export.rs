@@ -0,0 +1,36 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, ToSchema, PartialEq, Clone, Copy, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+/// Формат файла выгрузки.
+pub enum ExportFormat {
+    /// CSV с заголовком в первой строке.
+    #[default]
+    Csv,
+    /// Apache Parquet.
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// Формат файла выгрузки. По умолчанию `csv`.
+    pub format: Option<ExportFormat>,
+}
@@ -1,11 +1,25 @@
 mod ad;
 mod advertiser;
+mod api_key;
+mod audit;
+mod billing;
 mod campaign;
 mod client;
+mod creative;
+mod export;
 mod pagination;
+mod segment;
+mod stats;
 
-pub use ad::AdIdPath;
+pub use ad::{AdIdPath, AdSlotsQuery};
 pub use advertiser::AdvertiserIdPath;
+pub use api_key::{AdvertiserIdApiKeyIdPath, ApiKeyIdPath};
+pub use audit::AuditQuery;
+pub use billing::InvoiceQuery;
 pub use campaign::{AdvertiserIdCampaignIdPath, CampaignIdPath};
 pub use client::ClientIdPath;
+pub use creative::AdvertiserIdCampaignIdCreativeIdPath;
+pub use export::{ExportFormat, ExportQuery};
 pub use pagination::Pagination;
+pub use segment::AdvertiserIdSegmentIdPath;
+pub use stats::{CampaignBreakdownQuery, StatsGranularity, StatsRangeQuery, StatsRebuildQuery};
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams, Debug)]
+pub struct AdvertiserIdSegmentIdPath {
+    pub advertiser_id: Uuid,
+    pub segment_id: Uuid,
+}
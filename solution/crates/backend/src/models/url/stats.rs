@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+#[derive(Deserialize, ToSchema, PartialEq, Clone, Copy, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+/// Период, за который агрегируется статистика.
+pub enum StatsGranularity {
+    /// Статистика за каждый день.
+    #[default]
+    Day,
+    /// Статистика за каждые 7 дней, начиная с первого дня.
+    Week,
+    /// Статистика за каждые 30 дней, начиная с первого дня.
+    Month,
+}
+
+impl StatsGranularity {
+    /// Возвращает первый и последний день периода, в который попадает день `date`.
+    pub fn period(&self, date: i32) -> (i32, i32) {
+        let length = match self {
+            Self::Day => 1,
+            Self::Week => 7,
+            Self::Month => 30,
+        };
+        let start_date = (date - 1).div_euclid(length) * length + 1;
+        (start_date, start_date + length - 1)
+    }
+}
+
+#[derive(Deserialize, IntoParams, Validate, Debug)]
+#[into_params(parameter_in = Query)]
+#[validate(schema(function = "Self::validate_custom"))]
+pub struct StatsRangeQuery {
+    #[validate(range(min = 1))]
+    /// Первый день диапазона (включительно).
+    pub from: Option<i32>,
+
+    #[validate(range(min = 1))]
+    /// Последний день диапазона (включительно).
+    pub to: Option<i32>,
+
+    /// Период агрегации статистики. По умолчанию `day`.
+    pub granularity: Option<StatsGranularity>,
+}
+
+impl StatsRangeQuery {
+    fn validate_custom(&self) -> Result<(), ValidationError> {
+        if self.from.unwrap_or(i32::MIN) > self.to.unwrap_or(i32::MAX) {
+            return Err(ValidationError::new("`from` can't be greater than `to`"));
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, date: i32) -> bool {
+        self.from.is_none_or(|from| from <= date) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct CampaignBreakdownQuery {
+    /// Добавить к статистике за каждый период разбивку по рекламным кампаниям.
+    pub by_campaign: Option<bool>,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct StatsRebuildQuery {
+    /// Только вычислить расхождения, не сохраняя пересчитанную статистику. По умолчанию `true`.
+    pub dry_run: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::StatsGranularity;
+
+    #[rstest]
+    #[case::day(StatsGranularity::Day, 5, (5, 5))]
+    #[case::week_start(StatsGranularity::Week, 1, (1, 7))]
+    #[case::week_end(StatsGranularity::Week, 14, (8, 14))]
+    #[case::month(StatsGranularity::Month, 31, (31, 60))]
+    fn period(
+        #[case] granularity: StatsGranularity,
+        #[case] date: i32,
+        #[case] expected: (i32, i32),
+    ) {
+        assert_eq!(granularity.period(date), expected);
+    }
+}
@@ -1,20 +1,21 @@
 use actix_web::{
+    http::StatusCode,
     post,
     web::{Data, Json},
     HttpResponse,
 };
 use actix_web_lab::extract::Path;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use surrealdb_helper::SurrealDB;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    models::{dto::Ad, url::AdIdPath, ApiError as ApiErrorStruct},
+    models::{dto::Ad, url::AdIdPath, ApiError as ApiErrorStruct, IdempotencyKey},
     routes::ApiError,
 };
 
-#[derive(Deserialize, ToSchema, Debug)]
+#[derive(Deserialize, Serialize, ToSchema, Debug)]
 struct ClickRequest {
     /// UUID клиента, совершившего клик по объявлению.
     client_id: Uuid,
@@ -23,23 +24,29 @@ struct ClickRequest {
 #[utoipa::path(
     tag = "Ads",
     operation_id = "record_ad_click",
+    security(("api_key" = ["ADMIN", "CLIENT_APP"])),
     summary = "Фиксация перехода по рекламному объявлению",
     description = "Фиксирует клик (переход) клиента по рекламному объявлению.",
     params(
-        ("ad_id" = Uuid, description = "UUID рекламного объявления (идентификатор кампании), по которому совершен клик.")
+        ("ad_id" = Uuid, description = "UUID рекламного объявления (идентификатор кампании), по которому совершен клик."),
+        ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности. Повторный запрос с тем же ключом возвращает сохранённый ответ на первый запрос, не выполняя его повторно.")
     ),
     responses(
         (status = 204, description = "Переход по рекламному объявлению успешно зафиксирован."),
-        (status = 409, description = "Клиент не видел данное рекламное объявление", body = ApiErrorStruct),
+        (status = 409, description = "Клиент не видел данное рекламное объявление, либо с момента последнего показа прошло больше дней, чем допускает окно атрибуции переходов. Также возвращается, если ключ идемпотентности уже использован для другого запроса или запрос с этим ключом ещё выполняется.", body = ApiErrorStruct),
         (status = 404, description = "Клиента или рекламного объявления с указанным UUID не существует.", body = ApiErrorStruct)
     ),
 )]
 #[post("/click")]
 pub async fn post_handler(
     db: Data<SurrealDB>,
+    idempotency_key: IdempotencyKey,
     Path(path): Path<AdIdPath>,
     Json(body): Json<ClickRequest>,
 ) -> Result<HttpResponse, ApiError> {
-    Ad::record_click(body.client_id, path.ad_id, &db).await?;
-    Ok(HttpResponse::NoContent().into())
+    idempotency_key
+        .handle(StatusCode::NO_CONTENT, body, |body| async move {
+            Ad::record_click(body.client_id, path.ad_id, &db).await
+        })
+        .await
 }
@@ -0,0 +1,57 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::Path;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use surrealdb_helper::SurrealDB;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{dto::Ad, url::AdIdPath, ApiError as ApiErrorStruct},
+    routes::ApiError,
+    utils::validation::{check_non_negative_money, parse_validation_errors},
+};
+
+#[derive(Deserialize, ToSchema, Validate, Debug)]
+struct ConversionRequest {
+    /// UUID клиента, совершившего целевое действие.
+    client_id: Uuid,
+
+    #[schema(nullable, examples(1000), minimum = 0)]
+    #[serde(default, with = "rust_decimal::serde::float_option")]
+    #[validate(custom(function = "check_non_negative_money"))]
+    /// Ценность конверсии для рекламодателя (например, сумма покупки).
+    value: Option<Decimal>,
+}
+
+#[utoipa::path(
+    tag = "Ads",
+    operation_id = "record_ad_conversion",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Фиксация конверсии по рекламному объявлению",
+    description = "Фиксирует целевое действие (например, покупку), совершённое клиентом после перехода по рекламному объявлению. Учитывается только первая конверсия клиента по объявлению.",
+    params(
+        ("ad_id" = Uuid, description = "UUID рекламного объявления (идентификатор кампании), после перехода по которому совершена конверсия.")
+    ),
+    responses(
+        (status = 204, description = "Конверсия по рекламному объявлению успешно зафиксирована."),
+        (status = 400, description = "Объект конверсии не соответствует модели", body = ApiErrorStruct),
+        (status = 409, description = "Клиент не переходил по данному рекламному объявлению, либо с момента перехода прошло больше дней, чем допускает окно атрибуции конверсий", body = ApiErrorStruct),
+        (status = 404, description = "Клиента или рекламного объявления с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/conversion")]
+pub async fn post_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdIdPath>,
+    Json(body): Json<ConversionRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ad::record_conversion(body.client_id, path.ad_id, body.value, &db).await?;
+    Ok(HttpResponse::NoContent().into())
+}
@@ -1,7 +1,16 @@
+use actix_web::middleware::from_fn;
 use utoipa_actix_web::{scope, service_config::ServiceConfig};
 
+use crate::utils::auth::require_campaign_owner;
+
 pub mod click;
+pub mod conversion;
 
 pub fn config(cfg: &mut ServiceConfig) {
-    cfg.service(scope("/{ad_id}").service(click::post_handler));
+    cfg.service(
+        scope("/{ad_id}")
+            .wrap(from_fn(require_campaign_owner))
+            .service(click::post_handler)
+            .service(conversion::post_handler),
+    );
 }
@@ -0,0 +1,35 @@
+use actix_web::{
+    get,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Query;
+use surrealdb_helper::SurrealDB;
+
+use crate::{
+    models::{dto::AdDecision, url::ClientIdPath, ApiError as ApiErrorStruct},
+    routes::ApiError,
+};
+
+#[utoipa::path(
+    tag = "Ads",
+    operation_id = "explain_ad_for_client",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Объяснение выбора рекламного объявления для клиента",
+    description = "Возвращает все рекламные кампании с результатами проверки условий таргетинга и составляющими скора для указанного клиента. Показ при этом не фиксируется.",
+    params(
+        ("client_id" = Uuid, Query, description = "UUID клиента, для которого объясняется выбор объявления."),
+    ),
+    responses(
+        (status = 200, description = "Объяснение выбора рекламного объявления успешно получено.", body = Vec<AdDecision>),
+        (status = 404, description = "Клиента с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/explain")]
+pub async fn get_handler(
+    db: Data<SurrealDB>,
+    Query(query): Query<ClientIdPath>,
+) -> Result<Json<Vec<AdDecision>>, ApiError> {
+    Ok(Json(
+        AdDecision::explain_for_client(query.client_id, &db).await?,
+    ))
+}
@@ -2,29 +2,43 @@ use actix_web::{get, web::Data, HttpResponse};
 use actix_web_lab::extract::Query;
 use surrealdb_helper::SurrealDB;
 use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
 
 use crate::{
-    models::{dto::Ad, url::ClientIdPath, ApiError as ApiErrorStruct},
+    models::{
+        dto::{Ad, AdResponse},
+        url::AdSlotsQuery,
+        ApiError as ApiErrorStruct,
+    },
     routes::ApiError,
+    utils::validation::parse_validation_errors,
 };
 
 mod by_id;
+mod explain;
 
 pub fn config(cfg: &mut ServiceConfig) {
-    cfg.service(scope("/ads").service(get_handler).configure(by_id::config));
+    cfg.service(
+        scope("/ads")
+            .service(get_handler)
+            .service(explain::get_handler)
+            .configure(by_id::config),
+    );
 }
 
 #[utoipa::path(
     tag = "Ads",
     operation_id = "get_ad_for_client",
-    summary = "Получение рекламного объявления для клиента",
-    description = "Возвращает рекламное объявление, подходящее для показа клиенту с учетом таргетинга и ML скора.",
-    params(
-        ("client_id" = Uuid, Query, description = "UUID клиента, запрашивающего показ объявления."),
-    ),
+    security(("api_key" = ["ADMIN", "CLIENT_APP"])),
+    summary = "Получение рекламных объявлений для клиента",
+    description = "Возвращает рекламное объявление, подходящее для показа клиенту с учетом таргетинга и ML скора. \
+        Если указан параметр `slots`, возвращает массив из не более чем `slots` различных объявлений в порядке убывания скора; \
+        каждое из них засчитывается как отдельный показ.",
+    params(AdSlotsQuery),
     responses(
-        (status = 200, description = "Рекламное объявление успешно возвращено.", body = Ad),
-        (status = 204, description = "Не удалось найти подходящее рекламное объявление."),
+        (status = 200, description = "Рекламное объявление (или массив объявлений, если указан `slots`) успешно возвращено.", body = AdResponse),
+        (status = 204, description = "Не удалось найти подходящее рекламное объявление (только без `slots`)."),
+        (status = 400, description = "Некорректное количество слотов.", body = ApiErrorStruct),
         (status = 404, description = "Клиента с указанным UUID не существует.", body = ApiErrorStruct)
 
     ),
@@ -32,7 +46,19 @@ pub fn config(cfg: &mut ServiceConfig) {
 #[get("")]
 async fn get_handler(
     db: Data<SurrealDB>,
-    Query(query): Query<ClientIdPath>,
+    Query(query): Query<AdSlotsQuery>,
 ) -> Result<HttpResponse, ApiError> {
-    Ok(Ad::get_for_client(query.client_id, &db).await?)
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(match query.slots {
+        None => Ad::get_for_client(query.client_id, &db).await?,
+        Some(slots) => HttpResponse::Ok().json(AdResponse::Slots(
+            Ad::get_many_for_client(
+                query.client_id,
+                slots,
+                query.unique_advertisers.unwrap_or(false),
+                &db,
+            )
+            .await?,
+        )),
+    })
 }
@@ -0,0 +1,109 @@
+use actix_web::{
+    delete, get,
+    middleware::from_fn,
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::{Path, Query};
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::ApiKey,
+        url::{AdvertiserIdApiKeyIdPath, AdvertiserIdPath, Pagination},
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+    utils::{auth::require_advertiser_owner, validation::parse_validation_errors},
+};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/{advertiser_id}/api-keys")
+            .wrap(from_fn(require_advertiser_owner))
+            .service(post_handler)
+            .service(get_handler)
+            .service(delete_handler),
+    );
+}
+
+#[utoipa::path(
+    tag = "API keys",
+    operation_id = "issue_api_key",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Выпуск ключа API рекламодателя",
+    description = "Выпускает новый ключ API рекламодателя. Ключ возвращается только в ответе на этот запрос, в базе данных хранится его хэш.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому выпускается ключ.")
+    ),
+    responses(
+        (status = 201, description = "Ключ API успешно выпущен.", body = ApiKey),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Ключ API принадлежит другому рекламодателю.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[post("")]
+async fn post_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Created().json(ApiKey::issue(path.advertiser_id, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "API keys",
+    operation_id = "list_api_keys",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение ключей API рекламодателя c пагинацией",
+    description = "Возвращает выпущенные ключи API рекламодателя без самих ключей.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, ключи которого запрашиваются."),
+        Pagination
+    ),
+    responses(
+        (status = 200, description = "Ключи API рекламодателя.", body = Vec<ApiKey>),
+        (status = 400, description = "Некорректные параметры пагинации.", body = ApiErrorStruct),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Ключ API принадлежит другому рекламодателю.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<Vec<ApiKey>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(ApiKey::list(path.advertiser_id, query, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "API keys",
+    operation_id = "revoke_api_key",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Отзыв ключа API рекламодателя",
+    description = "Отзывает ключ API рекламодателя. Запросы с отозванным ключом отклоняются.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому выпущен ключ."),
+        ("api_key_id" = Uuid, description = "UUID ключа, который необходимо отозвать.")
+    ),
+    responses(
+        (status = 204, description = "Ключ API успешно отозван."),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Ключ API принадлежит другому рекламодателю.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя или ключа с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[delete("/{api_key_id}")]
+async fn delete_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdApiKeyIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    ApiKey::revoke(path.advertiser_id, path.api_key_id, &db).await?;
+    Ok(HttpResponse::NoContent().into())
+}
@@ -0,0 +1,134 @@
+use actix_web::{
+    get,
+    middleware::from_fn,
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::{Path, Query};
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::{BillingBalance, Invoice, LedgerEntry, TopUp},
+        url::{AdvertiserIdPath, InvoiceQuery, Pagination},
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+    utils::{auth::require_advertiser_owner, validation::parse_validation_errors},
+};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/{advertiser_id}/billing")
+            .wrap(from_fn(require_advertiser_owner))
+            .service(top_up_handler)
+            .service(balance_handler)
+            .service(ledger_handler)
+            .service(invoice_handler),
+    );
+}
+
+#[utoipa::path(
+    tag = "Billing",
+    operation_id = "top_up_balance",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Пополнение баланса рекламодателя",
+    description = "Пополняет предоплаченный баланс рекламодателя. Доступно только администратору платформы. После первого пополнения кампании рекламодателя перестают показываться, как только баланса не хватает на следующий показ вместе с возможными переходом и конверсией. Списания, сделанные до первого пополнения, вычитаются из баланса.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, баланс которого пополняется.")
+    ),
+    request_body(description = "Объект с суммой пополнения.", content = TopUp),
+    responses(
+        (status = 201, description = "Баланс успешно пополнен.", body = LedgerEntry),
+        (status = 400, description = "Некорректная сумма пополнения.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/top-ups")]
+async fn top_up_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Json(body): Json<TopUp>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ok(HttpResponse::Created().json(LedgerEntry::top_up(path.advertiser_id, body, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "Billing",
+    operation_id = "get_balance",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение баланса рекламодателя",
+    description = "Возвращает остаток предоплаченного баланса рекламодателя.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, баланс которого запрашивается.")
+    ),
+    responses(
+        (status = 200, description = "Баланс рекламодателя.", body = BillingBalance),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/balance")]
+async fn balance_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+) -> Result<Json<BillingBalance>, ApiError> {
+    Ok(Json(BillingBalance::get(path.advertiser_id, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "Billing",
+    operation_id = "list_ledger_entries",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение журнала списаний и пополнений c пагинацией",
+    description = "Возвращает записи журнала рекламодателя: пополнения баланса и списания за показы, переходы и конверсии, упорядоченные по дню создания.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, журнал которого запрашивается."),
+        Pagination
+    ),
+    responses(
+        (status = 200, description = "Записи журнала рекламодателя.", body = Vec<LedgerEntry>),
+        (status = 400, description = "Некорректные параметры пагинации.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/ledger")]
+async fn ledger_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<Vec<LedgerEntry>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(
+        LedgerEntry::list(path.advertiser_id, query, &db).await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Billing",
+    operation_id = "get_invoice",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение счёта за период",
+    description = "Возвращает счёт рекламодателя за диапазон дней `from`–`to`: списания сгруппированы по кампании, типу и цене за единицу, итог считается по журналу без погрешности округления.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, для которого формируется счёт."),
+        InvoiceQuery
+    ),
+    responses(
+        (status = 200, description = "Счёт рекламодателя за период.", body = Invoice),
+        (status = 400, description = "Некорректный расчётный период.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/invoice")]
+async fn invoice_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Query(query): Query<InvoiceQuery>,
+) -> Result<Json<Invoice>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(Invoice::get(path.advertiser_id, &query, &db).await?))
+}
@@ -1,4 +1,5 @@
 use actix_web::{
+    http::StatusCode,
     post,
     web::{Data, Json},
     HttpResponse,
@@ -7,7 +8,7 @@ use surrealdb_helper::SurrealDB;
 use validator::Validate;
 
 use crate::{
-    models::{dto::Advertiser, ApiError as ApiErrorStruct},
+    models::{dto::Advertiser, ApiError as ApiErrorStruct, Caller, IdempotencyKey},
     routes::ApiError,
     utils::validation::parse_validation_errors,
 };
@@ -15,18 +16,32 @@ use crate::{
 #[utoipa::path(
     tag = "Advertisers",
     operation_id = "upsert_advertisers",
+    security(("api_key" = ["ADMIN", "OPERATOR"])),
     summary = "Массовое создание/обновление рекламодателей",
     description = "Создаёт новых или обновляет существующих рекламодателей",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности. Повторный запрос с тем же ключом возвращает сохранённый ответ на первый запрос, не выполняя его повторно.")
+    ),
     responses(
         (status = 201, description = "Успешное создание/обновление рекламодателей", body = Vec<Advertiser>),
-        (status = 400, description = "Объект рекламодателя не соответствует модели", body = ApiErrorStruct)
+        (status = 400, description = "Объект рекламодателя не соответствует модели", body = ApiErrorStruct),
+        (status = 401, description = "Ключ API не передан или недействителен", body = ApiErrorStruct),
+        (status = 403, description = "Роли вызывающего недоступен этот метод", body = ApiErrorStruct),
+        (status = 409, description = "Ключ идемпотентности уже использован для другого запроса или запрос с этим ключом ещё выполняется, \
+            либо обновление меняет валюту рекламодателя, у которого уже есть кампании или записи журнала списаний.", body = ApiErrorStruct)
     ),
 )]
 #[post("/bulk")]
 pub async fn post_handler(
     db: Data<SurrealDB>,
+    caller: Caller,
+    idempotency_key: IdempotencyKey,
     Json(body): Json<Vec<Advertiser>>,
 ) -> Result<HttpResponse, ApiError> {
     body.validate().map_err(parse_validation_errors)?;
-    Ok(HttpResponse::Created().json(Advertiser::upsert(body, &db).await?))
+    idempotency_key
+        .handle(StatusCode::CREATED, body, |body| async move {
+            Advertiser::upsert(body, &caller, &db).await
+        })
+        .await
 }
@@ -1,5 +1,6 @@
 use actix_web::{
     get,
+    middleware::from_fn,
     web::{Data, Json},
 };
 use actix_web_lab::extract::Path;
@@ -8,11 +9,13 @@ use surrealdb_helper::SurrealDB;
 use crate::{
     models::{dto::Advertiser, url::AdvertiserIdPath, ApiError as ApiErrorStruct},
     routes::ApiError,
+    utils::auth::require_advertiser_owner,
 };
 
 #[utoipa::path(
     tag = "Advertisers",
     operation_id = "get_advertiser_by_id",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение рекламодателя по ID",
     description = "Возвращает информацию о рекламодателе по его ID.",
     params(
@@ -23,7 +26,7 @@ use crate::{
         (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
     ),
 )]
-#[get("/{advertiser_id}")]
+#[get("/{advertiser_id}", wrap = "from_fn(require_advertiser_owner)")]
 pub async fn get_handler(
     db: Data<SurrealDB>,
     Path(path): Path<AdvertiserIdPath>,
@@ -0,0 +1,169 @@
+use actix_multipart::form::MultipartForm;
+use actix_web::{delete, get, put, web::Data, HttpResponse};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+
+use crate::{
+    models::{
+        dto::{AuditEntry, Creative},
+        url::AdvertiserIdCampaignIdCreativeIdPath,
+        ApiError as ApiErrorStruct, AuditAction, Caller,
+    },
+    routes::ApiError,
+    utils::minio::Minio,
+};
+
+use super::super::super::image::{check_image, UploadForm};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/image")
+            .service(put_handler)
+            .service(get_handler)
+            .service(delete_handler),
+    );
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "set_creative_image",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Установка изображения креатива",
+    description = "Добавляет изображение к креативу рекламной кампании",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которой принадлежит креатив."),
+        ("creative_id" = Uuid, description = "UUID креатива, которому необходимо установить данное изображение.")
+    ),
+    request_body(content = UploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 204, description = "Изображение успешно установлено."),
+        (status = 415, description = "MIME тип изображения не разрешён", body = ApiErrorStruct),
+        (status = 413, description = "Размер изображения превосходит максимально допустимый"),
+        (status = 404, description = "Рекламодателя, рекламной кампании или креатива с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[put("")]
+async fn put_handler(
+    db: Data<SurrealDB>,
+    minio: Data<Minio>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdCreativeIdPath>,
+    MultipartForm(form): MultipartForm<UploadForm>,
+) -> Result<HttpResponse, ApiError> {
+    let AdvertiserIdCampaignIdCreativeIdPath {
+        advertiser_id,
+        campaign_id,
+        creative_id,
+    } = path;
+    Creative::get_by_id(advertiser_id, campaign_id, creative_id, &db).await?;
+
+    let file = form.file;
+    check_image(&file)?;
+
+    minio
+        .put_creative_image(&advertiser_id, &campaign_id, &creative_id, file)
+        .await?;
+    AuditEntry::record_action(
+        &caller,
+        AuditAction::CreativeImageUploaded,
+        creative_id,
+        advertiser_id,
+        &db,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().into())
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "get_creative_image",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение изображения креатива",
+    description = "Получает изображение креатива рекламной кампании",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которой принадлежит креатив."),
+        ("creative_id" = Uuid, description = "UUID креатива, изображение которого необходимо получить.")
+    ),
+    responses(
+        (status = 200, description = "Изображение успешно получено."),
+        (status = 404, description = "Рекламодателя, рекламной кампании, креатива с указанным UUID или его изображения не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    minio: Data<Minio>,
+    Path(path): Path<AdvertiserIdCampaignIdCreativeIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    let AdvertiserIdCampaignIdCreativeIdPath {
+        advertiser_id,
+        campaign_id,
+        creative_id,
+    } = path;
+    Creative::get_by_id(advertiser_id, campaign_id, creative_id, &db).await?;
+
+    let (content_type, file) = match minio
+        .get_creative_image(&advertiser_id, &campaign_id, &creative_id)
+        .await?
+    {
+        Some(image) => image,
+        None => {
+            return Err(ApiError::NotFound(format!(
+                "Image for creative with UUID `{creative_id}`"
+            )))
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("content-type", content_type))
+        .body(file))
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "delete_creative_image",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Удаление изображения креатива",
+    description = "Удаляет изображение креатива рекламной кампании",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которой принадлежит креатив."),
+        ("creative_id" = Uuid, description = "UUID креатива, изображение которого необходимо удалить.")
+    ),
+    responses(
+        (status = 204, description = "Изображение успешно удалено."),
+        (status = 404, description = "Рекламодателя, рекламной кампании или креатива с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[delete("")]
+async fn delete_handler(
+    db: Data<SurrealDB>,
+    minio: Data<Minio>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdCreativeIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    let AdvertiserIdCampaignIdCreativeIdPath {
+        advertiser_id,
+        campaign_id,
+        creative_id,
+    } = path;
+    Creative::get_by_id(advertiser_id, campaign_id, creative_id, &db).await?;
+
+    minio
+        .remove_creative_image(&advertiser_id, &campaign_id, &creative_id)
+        .await?;
+    AuditEntry::record_action(
+        &caller,
+        AuditAction::CreativeImageDeleted,
+        creative_id,
+        advertiser_id,
+        &db,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().into())
+}
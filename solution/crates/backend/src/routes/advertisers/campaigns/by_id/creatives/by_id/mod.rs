@@ -0,0 +1,89 @@
+use actix_web::{
+    delete, get,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+
+use crate::{
+    models::{
+        dto::Creative, url::AdvertiserIdCampaignIdCreativeIdPath, ApiError as ApiErrorStruct,
+        Caller,
+    },
+    routes::ApiError,
+    utils::minio::Minio,
+};
+
+mod image;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/{creative_id}")
+            .service(get_handler)
+            .service(delete_handler)
+            .configure(image::config),
+    );
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "get_creative_by_id",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение креатива по ID",
+    description = "Возвращает информацию о креативе рекламной кампании по его ID.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которой принадлежит креатив."),
+        ("creative_id" = Uuid, description = "UUID креатива, который необходимо получить.")
+    ),
+    responses(
+        (status = 200, description = "Креатив успешно получен.", body = Creative),
+        (status = 404, description = "Рекламодателя, рекламной кампании или креатива с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdCampaignIdCreativeIdPath>,
+) -> Result<Json<Creative>, ApiError> {
+    Ok(Json(
+        Creative::get_by_id(path.advertiser_id, path.campaign_id, path.creative_id, &db).await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "delete_creative",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Удаление креатива",
+    description = "Удаляет креатив рекламной кампании вместе с его изображением и статистикой. Креативы не редактируются, чтобы их статистика оставалась сопоставимой: для проверки нового варианта создайте новый креатив.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которой принадлежит креатив."),
+        ("creative_id" = Uuid, description = "UUID креатива, который необходимо удалить.")
+    ),
+    responses(
+        (status = 204, description = "Креатив успешно удалён."),
+        (status = 404, description = "Рекламодателя, рекламной кампании или креатива с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[delete("")]
+async fn delete_handler(
+    db: Data<SurrealDB>,
+    minio: Data<Minio>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdCreativeIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    Creative::delete(
+        path.advertiser_id,
+        path.campaign_id,
+        path.creative_id,
+        &caller,
+        &db,
+        &minio,
+    )
+    .await?;
+    Ok(HttpResponse::NoContent().into())
+}
@@ -0,0 +1,89 @@
+use actix_web::{
+    get, post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::{Path, Query};
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::{CreateCreative, Creative},
+        url::{AdvertiserIdCampaignIdPath, Pagination},
+        ApiError as ApiErrorStruct, Caller,
+    },
+    routes::ApiError,
+    utils::validation::parse_validation_errors,
+};
+
+mod by_id;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/creatives")
+            .service(post_handler)
+            .service(get_handler)
+            .configure(by_id::config),
+    );
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "create_creative",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Создание креатива рекламной кампании",
+    description = "Добавляет к рекламной кампании новый вариант объявления. Если у кампании есть креативы, при каждом показе выбирается один из них вместо `ad_title` и `ad_text` самой кампании.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, для которой создаётся креатив.")
+    ),
+    request_body(description = "Объект с данными для создания креатива.", content = CreateCreative),
+    responses(
+        (status = 201, description = "Креатив успешно создан.", body = Creative),
+        (status = 400, description = "Объект креатива не соответствует модели", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Рекламная кампания архивирована.", body = ApiErrorStruct)
+    ),
+)]
+#[post("")]
+async fn post_handler(
+    db: Data<SurrealDB>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+    Json(body): Json<CreateCreative>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ok(HttpResponse::Created()
+        .json(Creative::create(path.advertiser_id, path.campaign_id, body, &caller, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "Creatives",
+    operation_id = "list_creatives",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение креативов рекламной кампании c пагинацией",
+    description = "Возвращает список креативов рекламной кампании с пагинацией в порядке их создания.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, для которой запрашиваются креативы."),
+        Pagination
+    ),
+    responses(
+        (status = 200, description = "Список креативов рекламной кампании.", body = Vec<Creative>),
+        (status = 400, description = "Некорректные параметры пагинации.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<Vec<Creative>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(
+        Creative::list(path.advertiser_id, path.campaign_id, query, &db).await?,
+    ))
+}
@@ -1,4 +1,4 @@
-use crate::{models::{dto::Campaign, url::AdvertiserIdCampaignIdPath, ApiError as ApiErrorStruct}, routes::ApiError, utils::minio::Minio};
+use crate::{models::{dto::{AuditEntry, Campaign}, url::AdvertiserIdCampaignIdPath, ApiError as ApiErrorStruct, AuditAction, Caller}, routes::ApiError, utils::minio::Minio};
 use actix_multipart::form::{tempfile::TempFile, MultipartForm};
 use actix_web::{delete, get, http::StatusCode, put, web::Data, HttpResponse};
 use actix_web_lab::extract::Path;
@@ -16,7 +16,7 @@ pub fn config(cfg: &mut ServiceConfig) {
 }
 
 #[derive(MultipartForm, ToSchema, Debug)]
-struct UploadForm {
+pub(super) struct UploadForm {
     #[schema(
         value_type = String, 
         format = Binary, 
@@ -25,12 +25,43 @@ struct UploadForm {
     /// Изображение, которое будет загружено. 
     /// Размер не должен превышать 5.7 МБ.
     /// Разрешённые MIME типы: `image/jpeg`, `image/pjpeg`, `image/png`, `image/webp`
-    file: TempFile,
+    pub(super) file: TempFile,
+}
+
+/// Проверяет размер и MIME тип загружаемого изображения.
+pub(super) fn check_image(file: &TempFile) -> Result<(), ApiError> {
+    if file.size > (5.7 * 1024 as f64 * 1024 as f64) as usize {
+        return Err(ApiError::Custom { error: "file_too_large".into(), status_code: StatusCode::PAYLOAD_TOO_LARGE, message: "File size exceeds the limit of 5.7 MB".into() })
+    }
+
+    let image_types = vec!["jpeg", "pjpeg", "png", "webp"];
+    let mime_type_err = |type_got: &str| Err(ApiError::Custom { 
+        error: "invalid_mime_type".into(), 
+        status_code: StatusCode::UNSUPPORTED_MEDIA_TYPE, 
+        message: format!(
+            "Invalide MIME type: expected file of one of the folowing mime types: {}, but got `{}`",
+            image_types.iter().map(|t| format!("`image/{t}`")).collect::<Vec<String>>().join(", "),
+            type_got
+        )
+    });
+
+    if let Some(content_type) = file.content_type.clone() {
+        if content_type.type_() != "image" 
+            || !image_types.contains(&content_type.subtype().as_str()) 
+        {
+            return mime_type_err(&format!("{}/{}", content_type.type_(), content_type.subtype()));
+        }
+    } else {
+        return mime_type_err("");
+    }
+
+    Ok(())
 }
 
 #[utoipa::path(
     tag = "Campaign images",
     operation_id = "set_campaign_image",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Установка изображения рекламной кампании",
     description = "Добавляет изображение к рекламной кампании",
     params(
@@ -49,6 +80,7 @@ struct UploadForm {
 pub async fn put_handler(
     db: Data<SurrealDB>,
     minio: Data<Minio>,
+    caller: Caller,
     Path(path): Path<AdvertiserIdCampaignIdPath>,
     MultipartForm(form): MultipartForm<UploadForm>
 ) -> Result<HttpResponse, ApiError> {
@@ -56,33 +88,10 @@ pub async fn put_handler(
     Campaign::get_by_id(advertiser_id, campaign_id, &db).await?;
 
     let file = form.file;
-
-    if file.size > (5.7 * 1024 as f64 * 1024 as f64) as usize {
-        return Err(ApiError::Custom { error: "file_too_large".into(), status_code: StatusCode::PAYLOAD_TOO_LARGE, message: "File size exceeds the limit of 5.7 MB".into() })
-    }
-
-    let image_types = vec!["jpeg", "pjpeg", "png", "webp"];
-    let mime_type_err = |type_got: &str| Err(ApiError::Custom { 
-        error: "invalid_mime_type".into(), 
-        status_code: StatusCode::UNSUPPORTED_MEDIA_TYPE, 
-        message: format!(
-            "Invalide MIME type: expected file of one of the folowing mime types: {}, but got `{}`",
-            image_types.iter().map(|t| format!("`image/{t}`")).collect::<Vec<String>>().join(", "),
-            type_got
-        )
-    });
-
-    if let Some(content_type) = file.content_type.clone() {
-        if content_type.type_() != "image" 
-            || !image_types.contains(&content_type.subtype().as_str()) 
-        {
-            return mime_type_err(&format!("{}/{}", content_type.type_(), content_type.subtype()));
-        }
-    } else {
-        return mime_type_err("");
-    }
+    check_image(&file)?;
 
     minio.put_image(&advertiser_id, &campaign_id, file).await?;
+    AuditEntry::record_action(&caller, AuditAction::CampaignImageUploaded, campaign_id, advertiser_id, &db).await?;
 
     Ok(HttpResponse::NoContent().into())
 }
@@ -90,6 +99,7 @@ pub async fn put_handler(
 #[utoipa::path(
     tag = "Campaign images",
     operation_id = "get_campaign_image",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение изображения рекламной кампании",
     description = "Получает изображение к рекламной кампании",
     params(
@@ -124,6 +134,7 @@ pub async fn get_handler(
 #[utoipa::path(
     tag = "Campaign images",
     operation_id = "delete_campaign_image",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Удаление изображения рекламной кампании",
     description = "Удаляет изображение рекламной кампании",
     params(
@@ -139,12 +150,14 @@ pub async fn get_handler(
 pub async fn delete_handler(
     db: Data<SurrealDB>,
     minio: Data<Minio>,
+    caller: Caller,
     Path(path): Path<AdvertiserIdCampaignIdPath>,
 ) -> Result<HttpResponse, ApiError> {
     let AdvertiserIdCampaignIdPath { advertiser_id, campaign_id } = path;
     Campaign::get_by_id(advertiser_id, campaign_id, &db).await?;
 
     minio.remove_image(&advertiser_id, &campaign_id).await?;
+    AuditEntry::record_action(&caller, AuditAction::CampaignImageDeleted, campaign_id, advertiser_id, &db).await?;
 
     Ok(HttpResponse::NoContent().into())
 }
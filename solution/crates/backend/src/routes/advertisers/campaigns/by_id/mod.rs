@@ -1,5 +1,5 @@
 use actix_web::{
-    delete, get, put,
+    delete, get, post, put,
     web::{Data, Json},
     HttpResponse,
 };
@@ -12,13 +12,15 @@ use crate::{
     models::{
         dto::{Campaign, CampaignUpdate},
         url::AdvertiserIdCampaignIdPath,
-        ApiError as ApiErrorStruct,
+        ApiError as ApiErrorStruct, Caller,
     },
     routes::ApiError,
     utils::validation::parse_validation_errors,
 };
 
+mod creatives;
 mod image;
+mod state;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
@@ -26,13 +28,17 @@ pub fn config(cfg: &mut ServiceConfig) {
             .service(get_handler)
             .service(put_handler)
             .service(delete_handler)
-            .configure(image::config),
+            .service(restore_handler)
+            .configure(creatives::config)
+            .configure(image::config)
+            .configure(state::config),
     );
 }
 
 #[utoipa::path(
     tag = "Campaigns",
     operation_id = "get_campaign_by_id",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение кампании по ID",
     description = "Возвращает информацию о кампании по её ID.",
     params(
@@ -57,6 +63,7 @@ async fn get_handler(
 #[utoipa::path(
     tag = "Campaigns",
     operation_id = "update_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Обновление рекламной кампании",
     description = "Обновляет разрешённые параметры рекламной кампании до её старта.",
     params(
@@ -73,20 +80,22 @@ async fn get_handler(
 #[put("")]
 async fn put_handler(
     db: Data<SurrealDB>,
+    caller: Caller,
     Path(path): Path<AdvertiserIdCampaignIdPath>,
     Json(body): Json<CampaignUpdate>,
 ) -> Result<Json<Campaign>, ApiError> {
     body.validate().map_err(parse_validation_errors)?;
     Ok(Json(
-        Campaign::update(path.advertiser_id, path.campaign_id, body, &db).await?,
+        Campaign::update(path.advertiser_id, path.campaign_id, body, &caller, &db).await?,
     ))
 }
 
 #[utoipa::path(
     tag = "Campaigns",
     operation_id = "delete_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Удаление рекламной кампании",
-    description = "Удаляет рекламную кампанию рекламодателя по заданному campaign_id.",
+    description = "Удаляет рекламную кампанию рекламодателя по заданному campaign_id. Кампания перестаёт показываться и пропадает из списков, но её статистика сохраняется, а саму кампанию можно восстановить в течение срока хранения.",
     params(
         ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
         ("campaign_id" = Uuid, description = "UUID рекламной кампании, которую необходимо удалить.")
@@ -99,8 +108,37 @@ async fn put_handler(
 #[delete("")]
 async fn delete_handler(
     db: Data<SurrealDB>,
+    caller: Caller,
     Path(path): Path<AdvertiserIdCampaignIdPath>,
 ) -> Result<HttpResponse, ApiError> {
-    Campaign::delete(path.advertiser_id, path.campaign_id, &db).await?;
+    Campaign::delete(path.advertiser_id, path.campaign_id, &caller, &db).await?;
     Ok(HttpResponse::NoContent().into())
 }
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "restore_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Восстановление удалённой рекламной кампании",
+    description = "Восстанавливает удалённую рекламную кампанию, если срок хранения после удаления ещё не истёк.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которую необходимо восстановить.")
+    ),
+    responses(
+        (status = 200, description = "Рекламная кампания успешно восстановлена.", body = Campaign),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Рекламная кампания не удалена.", body = ApiErrorStruct),
+        (status = 410, description = "Срок хранения удалённой рекламной кампании истёк.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/restore")]
+async fn restore_handler(
+    db: Data<SurrealDB>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+) -> Result<Json<Campaign>, ApiError> {
+    Ok(Json(
+        Campaign::restore(path.advertiser_id, path.campaign_id, &caller, &db).await?,
+    ))
+}
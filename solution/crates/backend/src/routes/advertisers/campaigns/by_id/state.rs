@@ -0,0 +1,150 @@
+use actix_web::{
+    get, post,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::service_config::ServiceConfig;
+
+use crate::{
+    models::{
+        dto::{Campaign, CampaignStateChange},
+        url::AdvertiserIdCampaignIdPath,
+        ApiError as ApiErrorStruct, Caller, CampaignState,
+    },
+    routes::ApiError,
+};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(pause_handler)
+        .service(resume_handler)
+        .service(archive_handler)
+        .service(history_handler);
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "pause_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Приостановка рекламной кампании",
+    description = "Приостанавливает показы активной рекламной кампании. Статистика кампании сохраняется.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которую необходимо приостановить.")
+    ),
+    responses(
+        (status = 200, description = "Рекламная кампания приостановлена.", body = Campaign),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Кампания не находится в статусе ACTIVE.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/pause")]
+async fn pause_handler(
+    db: Data<SurrealDB>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+) -> Result<Json<Campaign>, ApiError> {
+    Ok(Json(
+        Campaign::set_state(
+            path.advertiser_id,
+            path.campaign_id,
+            CampaignState::PAUSED,
+            &caller,
+            &db,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "resume_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Возобновление рекламной кампании",
+    description = "Возобновляет показы приостановленной рекламной кампании.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которую необходимо возобновить.")
+    ),
+    responses(
+        (status = 200, description = "Рекламная кампания возобновлена.", body = Campaign),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Кампания не находится в статусе PAUSED.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/resume")]
+async fn resume_handler(
+    db: Data<SurrealDB>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+) -> Result<Json<Campaign>, ApiError> {
+    Ok(Json(
+        Campaign::set_state(
+            path.advertiser_id,
+            path.campaign_id,
+            CampaignState::ACTIVE,
+            &caller,
+            &db,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "archive_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Архивирование рекламной кампании",
+    description = "Окончательно останавливает рекламную кампанию без удаления её статистики. Архивированную кампанию нельзя возобновить или изменить.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, которую необходимо архивировать.")
+    ),
+    responses(
+        (status = 200, description = "Рекламная кампания архивирована.", body = Campaign),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Кампания уже архивирована.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/archive")]
+async fn archive_handler(
+    db: Data<SurrealDB>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+) -> Result<Json<Campaign>, ApiError> {
+    Ok(Json(
+        Campaign::set_state(
+            path.advertiser_id,
+            path.campaign_id,
+            CampaignState::ARCHIVED,
+            &caller,
+            &db,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "get_campaign_state_history",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение истории статусов рекламной кампании",
+    description = "Возвращает все изменения статуса рекламной кампании в хронологическом порядке, начиная с её создания.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит кампания."),
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании.")
+    ),
+    responses(
+        (status = 200, description = "История статусов рекламной кампании.", body = Vec<CampaignStateChange>),
+        (status = 404, description = "Рекламодателя или рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/state-history")]
+async fn history_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdCampaignIdPath>,
+) -> Result<Json<Vec<CampaignStateChange>>, ApiError> {
+    Ok(Json(
+        Campaign::state_history(path.advertiser_id, path.campaign_id, &db).await?,
+    ))
+}
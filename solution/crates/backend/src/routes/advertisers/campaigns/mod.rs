@@ -1,5 +1,7 @@
 use actix_web::{
-    get, post,
+    get,
+    middleware::from_fn,
+    post,
     web::{Data, Json},
     HttpResponse,
 };
@@ -10,12 +12,12 @@ use validator::Validate;
 
 use crate::{
     models::{
-        dto::{Campaign, CreateCampaign},
+        dto::{Campaign, CampaignForecast, CreateCampaign},
         url::{AdvertiserIdPath, Pagination},
-        ApiError as ApiErrorStruct,
+        ApiError as ApiErrorStruct, Caller,
     },
     routes::ApiError,
-    utils::validation::parse_validation_errors,
+    utils::{auth::require_advertiser_owner, validation::parse_validation_errors},
 };
 
 mod by_id;
@@ -23,8 +25,10 @@ mod by_id;
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/{advertiser_id}/campaigns")
+            .wrap(from_fn(require_advertiser_owner))
             .service(post_handler)
             .service(get_handler)
+            .service(forecast_handler)
             .configure(by_id::config),
     );
 }
@@ -32,6 +36,7 @@ pub fn config(cfg: &mut ServiceConfig) {
 #[utoipa::path(
     tag = "Campaigns",
     operation_id = "create_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Создание рекламной кампании",
     description = "Создаёт новую рекламную кампанию для указанного рекламодателя.",
     params(
@@ -48,16 +53,19 @@ pub fn config(cfg: &mut ServiceConfig) {
 #[post("")]
 async fn post_handler(
     db: Data<SurrealDB>,
+    caller: Caller,
     Path(path): Path<AdvertiserIdPath>,
     Json(body): Json<CreateCampaign>,
 ) -> Result<HttpResponse, ApiError> {
     body.validate().map_err(parse_validation_errors)?;
-    Ok(HttpResponse::Created().json(Campaign::create(path.advertiser_id, body, &db).await?))
+    Ok(HttpResponse::Created()
+        .json(Campaign::create(path.advertiser_id, body, &caller, &db).await?))
 }
 
 #[utoipa::path(
     tag = "Campaigns",
     operation_id = "list_campaigns",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение рекламных кампаний рекламодателя c пагинацией",
     description = "Возвращает список рекламных кампаний для указанного рекламодателя с пагинацией.",
     params(
@@ -80,3 +88,31 @@ async fn get_handler(
     query.validate().map_err(parse_validation_errors)?;
     Ok(Json(Campaign::list(path.advertiser_id, query, &db).await?))
 }
+
+#[utoipa::path(
+    tag = "Campaigns",
+    operation_id = "forecast_campaign",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Прогноз охвата рекламной кампании",
+    description = "Оценивает охват рекламной кампании до её создания: количество подходящих клиентов, количество конкурирующих активных кампаний и ожидаемое количество показов и переходов в день по истории ежедневной статистики.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, для которого строится прогноз.")
+    ),
+    request_body(description = "Объект с данными рекламной кампании, для которой строится прогноз.", content = CreateCampaign),
+    responses(
+        (status = 200, description = "Прогноз охвата рекламной кампании.", body = CampaignForecast),
+        (status = 400, description = "Объект рекламной кампании не соответствует модели", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя или сегмента с таким ID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[post("/forecast")]
+async fn forecast_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Json(body): Json<CreateCampaign>,
+) -> Result<Json<CampaignForecast>, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ok(Json(
+        CampaignForecast::for_campaign(path.advertiser_id, body, &db).await?,
+    ))
+}
@@ -1,14 +1,20 @@
 use utoipa_actix_web::{scope, service_config::ServiceConfig};
 
+mod api_keys;
+mod billing;
 mod bulk;
 mod by_id;
 mod campaigns;
+mod segments;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/advertisers")
             .service(by_id::get_handler)
             .service(bulk::post_handler)
-            .configure(campaigns::config),
+            .configure(api_keys::config)
+            .configure(billing::config)
+            .configure(campaigns::config)
+            .configure(segments::config),
     );
 }
@@ -0,0 +1,136 @@
+use actix_web::{
+    delete, get, put,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::{Segment, SegmentReach, SegmentUpdate},
+        url::AdvertiserIdSegmentIdPath,
+        ApiError as ApiErrorStruct, Caller,
+    },
+    routes::ApiError,
+    utils::validation::parse_validation_errors,
+};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/{segment_id}")
+            .service(get_handler)
+            .service(put_handler)
+            .service(delete_handler)
+            .service(reach_handler),
+    );
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "get_segment_by_id",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение сегмента по ID",
+    description = "Возвращает информацию о сегменте аудитории по его ID.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит сегмент."),
+        ("segment_id" = Uuid, description = "UUID сегмента, который необходимо получить.")
+    ),
+    responses(
+        (status = 200, description = "Сегмент успешно получен.", body = Segment),
+        (status = 404, description = "Рекламодателя или сегмента с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdSegmentIdPath>,
+) -> Result<Json<Segment>, ApiError> {
+    Ok(Json(
+        Segment::get_by_id(path.advertiser_id, path.segment_id, &db).await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "update_segment",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Обновление сегмента аудитории",
+    description = "Обновляет название или таргетинг сегмента. Новый таргетинг сразу применяется ко всем кампаниям, использующим сегмент, \
+        поэтому таргетинг сегмента, который использует уже начавшаяся кампания, изменить нельзя.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит сегмент."),
+        ("segment_id" = Uuid, description = "UUID сегмента, который необходимо обновить.")
+    ),
+    request_body(description = "Объект с обновлёнными данными сегмента.", content = SegmentUpdate),
+    responses(
+        (status = 200, description = "Сегмент успешно обновлён.", body = Segment),
+        (status = 400, description = "Объект сегмента не соответствует модели", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя или сегмента с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Сегмент используется уже начавшейся рекламной кампанией.", body = ApiErrorStruct)
+    ),
+)]
+#[put("")]
+async fn put_handler(
+    db: Data<SurrealDB>,
+    caller: Caller,
+    Path(path): Path<AdvertiserIdSegmentIdPath>,
+    Json(body): Json<SegmentUpdate>,
+) -> Result<Json<Segment>, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ok(Json(
+        Segment::update(path.advertiser_id, path.segment_id, body, &caller, &db).await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "delete_segment",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Удаление сегмента аудитории",
+    description = "Удаляет сегмент аудитории. Сегмент, используемый хотя бы одной кампанией, удалить нельзя.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит сегмент."),
+        ("segment_id" = Uuid, description = "UUID сегмента, который необходимо удалить.")
+    ),
+    responses(
+        (status = 204, description = "Сегмент успешно удалён."),
+        (status = 404, description = "Рекламодателя или сегмента с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Сегмент используется рекламными кампаниями.", body = ApiErrorStruct)
+    ),
+)]
+#[delete("")]
+async fn delete_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdSegmentIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    Segment::delete(path.advertiser_id, path.segment_id, &db).await?;
+    Ok(HttpResponse::NoContent().into())
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "get_segment_reach",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Оценка охвата сегмента",
+    description = "Возвращает количество клиентов, которые подходят под таргетинг сегмента. Лимиты, бюджеты и ограничения частоты показов кампаний не учитываются.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, которому принадлежит сегмент."),
+        ("segment_id" = Uuid, description = "UUID сегмента, охват которого необходимо оценить.")
+    ),
+    responses(
+        (status = 200, description = "Оценка охвата сегмента.", body = SegmentReach),
+        (status = 404, description = "Рекламодателя или сегмента с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/reach")]
+async fn reach_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdSegmentIdPath>,
+) -> Result<Json<SegmentReach>, ApiError> {
+    Ok(Json(
+        Segment::reach(path.advertiser_id, path.segment_id, &db).await?,
+    ))
+}
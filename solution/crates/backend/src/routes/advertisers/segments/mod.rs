@@ -0,0 +1,85 @@
+use actix_web::{
+    get,
+    middleware::from_fn,
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::{Path, Query};
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::{CreateSegment, Segment},
+        url::{AdvertiserIdPath, Pagination},
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+    utils::{auth::require_advertiser_owner, validation::parse_validation_errors},
+};
+
+mod by_id;
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/{advertiser_id}/segments")
+            .wrap(from_fn(require_advertiser_owner))
+            .service(post_handler)
+            .service(get_handler)
+            .configure(by_id::config),
+    );
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "create_segment",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Создание сегмента аудитории",
+    description = "Создаёт новый сегмент аудитории для указанного рекламодателя. Сегмент можно использовать в кампаниях вместо собственного таргетинга.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, для которого создаётся сегмент.")
+    ),
+    request_body(description = "Объект с данными для создания сегмента аудитории.", content = CreateSegment),
+    responses(
+        (status = 201, description = "Сегмент аудитории успешно создан.", body = Segment),
+        (status = 400, description = "Объект сегмента не соответствует модели", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с таким ID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[post("")]
+async fn post_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Json(body): Json<CreateSegment>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ok(HttpResponse::Created().json(Segment::create(path.advertiser_id, body, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "Segments",
+    operation_id = "list_segments",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение сегментов аудитории рекламодателя c пагинацией",
+    description = "Возвращает список сегментов аудитории для указанного рекламодателя с пагинацией.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, для которого запрашиваются сегменты."),
+        Pagination
+    ),
+    responses(
+        (status = 200, description = "Список сегментов аудитории рекламодателя.", body = Vec<Segment>),
+        (status = 400, description = "Некорректные параметры пагинации.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя с таким ID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<Vec<Segment>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(Segment::list(path.advertiser_id, query, &db).await?))
+}
@@ -0,0 +1,99 @@
+use actix_web::{
+    delete, get, post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use actix_web_lab::extract::{Path, Query};
+use surrealdb_helper::SurrealDB;
+use utoipa_actix_web::{scope, service_config::ServiceConfig};
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::{ApiKey, CreateApiKey},
+        url::{ApiKeyIdPath, Pagination},
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+    utils::validation::parse_validation_errors,
+};
+
+pub fn config(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/api-keys")
+            .service(post_handler)
+            .service(get_handler)
+            .service(delete_handler),
+    );
+}
+
+#[utoipa::path(
+    tag = "API keys",
+    operation_id = "issue_platform_api_key",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Выпуск ключа API платформенной роли",
+    description = "Выпускает ключ API оператора, клиентского приложения или команды анализа данных. Ключ возвращается только в ответе на этот запрос.",
+    request_body(description = "Объект с ролью владельца ключа.", content = CreateApiKey),
+    responses(
+        (status = 201, description = "Ключ API успешно выпущен.", body = ApiKey),
+        (status = 400, description = "Роль не является платформенной.", body = ApiErrorStruct),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Роли вызывающего недоступен этот метод.", body = ApiErrorStruct)
+    ),
+)]
+#[post("")]
+async fn post_handler(
+    db: Data<SurrealDB>,
+    Json(body): Json<CreateApiKey>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate().map_err(parse_validation_errors)?;
+    Ok(HttpResponse::Created().json(ApiKey::issue_platform(body, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "API keys",
+    operation_id = "list_platform_api_keys",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Получение ключей API платформенных ролей c пагинацией",
+    description = "Возвращает выпущенные ключи API платформенных ролей без самих ключей.",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Ключи API платформенных ролей.", body = Vec<ApiKey>),
+        (status = 400, description = "Некорректные параметры пагинации.", body = ApiErrorStruct),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Роли вызывающего недоступен этот метод.", body = ApiErrorStruct)
+    ),
+)]
+#[get("")]
+async fn get_handler(
+    db: Data<SurrealDB>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<Vec<ApiKey>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(ApiKey::list_platform(query, &db).await?))
+}
+
+#[utoipa::path(
+    tag = "API keys",
+    operation_id = "revoke_platform_api_key",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Отзыв ключа API платформенной роли",
+    description = "Отзывает ключ API платформенной роли. Запросы с отозванным ключом отклоняются.",
+    params(
+        ("api_key_id" = Uuid, description = "UUID ключа, который необходимо отозвать.")
+    ),
+    responses(
+        (status = 204, description = "Ключ API успешно отозван."),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Роли вызывающего недоступен этот метод.", body = ApiErrorStruct),
+        (status = 404, description = "Ключа с указанным UUID не существует.", body = ApiErrorStruct)
+    ),
+)]
+#[delete("/{api_key_id}")]
+async fn delete_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<ApiKeyIdPath>,
+) -> Result<HttpResponse, ApiError> {
+    ApiKey::revoke_platform(path.api_key_id, &db).await?;
+    Ok(HttpResponse::NoContent().into())
+}
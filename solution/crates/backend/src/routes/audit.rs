@@ -0,0 +1,42 @@
+use actix_web::{
+    get,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Query;
+use surrealdb_helper::SurrealDB;
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::AuditEntry,
+        url::{AuditQuery, Pagination},
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+    utils::validation::parse_validation_errors,
+};
+
+#[utoipa::path(
+    tag = "Audit",
+    operation_id = "list_audit_entries",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Получение журнала аудита c пагинацией",
+    description = "Возвращает записи журнала аудита изменяющих запросов в порядке их создания. Записи можно отфильтровать по типу и UUID сущности, рекламодателю и периоду (в днях).",
+    params(AuditQuery, Pagination),
+    responses(
+        (status = 200, description = "Записи журнала аудита.", body = Vec<AuditEntry>),
+        (status = 400, description = "Некорректные параметры фильтрации или пагинации.", body = ApiErrorStruct),
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Роли вызывающего недоступен этот метод.", body = ApiErrorStruct)
+    ),
+)]
+#[get("/audit")]
+pub async fn get_handler(
+    db: Data<SurrealDB>,
+    Query(query): Query<AuditQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Vec<AuditEntry>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    pagination.validate().map_err(parse_validation_errors)?;
+    Ok(Json(AuditEntry::list(query, pagination, &db).await?))
+}
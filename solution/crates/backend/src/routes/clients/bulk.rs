@@ -1,4 +1,5 @@
 use actix_web::{
+    http::StatusCode,
     post,
     web::{Data, Json},
     HttpResponse,
@@ -7,7 +8,7 @@ use surrealdb_helper::SurrealDB;
 use validator::Validate;
 
 use crate::{
-    models::{dto::Client, ApiError as ApiErrorStruct},
+    models::{dto::Client, ApiError as ApiErrorStruct, Caller, IdempotencyKey},
     routes::ApiError,
     utils::validation::parse_validation_errors,
 };
@@ -15,18 +16,29 @@ use crate::{
 #[utoipa::path(
     tag = "Clients",
     operation_id = "upsert_clients",
+    security(("api_key" = ["ADMIN", "OPERATOR"])),
     summary = "Массовое создание/обновление клиентов",
     description = "Создаёт новых или обновляет существующих клиентов",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности. Повторный запрос с тем же ключом возвращает сохранённый ответ на первый запрос, не выполняя его повторно.")
+    ),
     responses(
         (status = 201, description = "Успешное создание/обновление клиентов", body = Vec<Client>),
-        (status = 400, description = "Объект клиента не соответствует модели", body = ApiErrorStruct)
+        (status = 400, description = "Объект клиента не соответствует модели", body = ApiErrorStruct),
+        (status = 409, description = "Ключ идемпотентности уже использован для другого запроса или запрос с этим ключом ещё выполняется.", body = ApiErrorStruct)
     ),
 )]
 #[post("/bulk")]
 pub async fn post_handler(
     db: Data<SurrealDB>,
+    caller: Caller,
+    idempotency_key: IdempotencyKey,
     Json(body): Json<Vec<Client>>,
 ) -> Result<HttpResponse, ApiError> {
     body.validate().map_err(parse_validation_errors)?;
-    Ok(HttpResponse::Created().json(Client::upsert(body, &db).await?))
+    idempotency_key
+        .handle(StatusCode::CREATED, body, |body| async move {
+            Client::upsert(body, &caller, &db).await
+        })
+        .await
 }
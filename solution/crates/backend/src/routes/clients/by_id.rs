@@ -13,6 +13,7 @@ use crate::{
 #[utoipa::path(
     tag = "Clients",
     operation_id = "get_client_by_id",
+    security(("api_key" = ["ADMIN"])),
     summary = "Получение клиента по ID",
     description = "Возвращает информацию о клиенте по его ID.",
     params(
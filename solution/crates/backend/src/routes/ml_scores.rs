@@ -1,4 +1,5 @@
 use actix_web::{
+    http::StatusCode,
     post,
     web::{Data, Json},
     HttpResponse,
@@ -7,7 +8,7 @@ use surrealdb_helper::SurrealDB;
 use validator::Validate;
 
 use crate::{
-    models::{dto::MLScore, ApiError as ApiErrorStruct},
+    models::{dto::MLScore, ApiError as ApiErrorStruct, Caller, IdempotencyKey},
     routes::ApiError,
     utils::validation::parse_validation_errors,
 };
@@ -15,23 +16,36 @@ use crate::{
 #[utoipa::path(
     tag = "Advertisers",
     operation_id = "upsert_ml_score",
+    security(("api_key" = ["ADMIN", "ADVERTISER", "DATA_SCIENCE"])),
     summary = "Добавление или обновление ML скора",
     description = "Добавляет или обновляет ML скор для указанной пары клиент-рекламодатель.",
     request_body(
         description = "Объект с данными ML скора, включая client_id, advertiser_id и значение скора.", 
         content = MLScore
     ),
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности. Повторный запрос с тем же ключом возвращает сохранённый ответ на первый запрос, не выполняя его повторно.")
+    ),
     responses(
         (status = 200, description = "ML скор успешно добавлен или обновлён."),
-        (status = 404, description = "Рекламодателя или клиента с указанным UUID не существует.", body = ApiErrorStruct)
+        (status = 401, description = "Ключ API не передан или недействителен.", body = ApiErrorStruct),
+        (status = 403, description = "Ключ API принадлежит другому рекламодателю.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламодателя или клиента с указанным UUID не существует.", body = ApiErrorStruct),
+        (status = 409, description = "Ключ идемпотентности уже использован для другого запроса или запрос с этим ключом ещё выполняется.", body = ApiErrorStruct)
     ),
 )]
 #[post("/ml-scores")]
 pub async fn post_handler(
     db: Data<SurrealDB>,
+    caller: Caller,
+    idempotency_key: IdempotencyKey,
     Json(body): Json<MLScore>,
 ) -> Result<HttpResponse, ApiError> {
     body.validate().map_err(parse_validation_errors)?;
-    MLScore::upsert(body, &db).await?;
-    Ok(HttpResponse::Ok().into())
+    caller.check_advertiser(body.advertiser_id)?;
+    idempotency_key
+        .handle(StatusCode::OK, body, |body| async move {
+            MLScore::upsert(body, &caller, &db).await.map(|_| ())
+        })
+        .await
 }
@@ -3,6 +3,8 @@ use utoipa_actix_web::service_config::ServiceConfig;
 
 mod ads;
 mod advertisers;
+mod api_keys;
+mod audit;
 mod clients;
 mod ml_scores;
 mod not_found;
@@ -16,10 +18,12 @@ pub use self::not_found::not_found;
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.configure(clients::config)
         .configure(advertisers::config)
+        .configure(api_keys::config)
         .service(ml_scores::post_handler)
         .configure(ads::config)
         .configure(statistics::config)
-        .configure(time::config);
+        .configure(time::config)
+        .service(audit::get_handler);
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -33,12 +37,24 @@ pub enum ApiError {
     #[error("{0} was not found")]
     NotFound(String),
 
+    #[error("Missing or invalid API key")]
+    Unauthorized,
+
     #[error("You're not allowed to do this")]
     NotOwner,
 
+    #[error("Role `{0}` is not allowed to call this method")]
+    Forbidden(String),
+
     #[error("Deserialization error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("CSV export error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Parquet export error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
@@ -48,6 +64,9 @@ pub enum ApiError {
     #[error("Unable to change field `{0}`: Campaign has already started")]
     CampaignStarted(String),
 
+    #[error("{event} is outside of the {days}-day attribution window")]
+    AttributionWindowExpired { event: String, days: i32 },
+
     #[error("{message}")]
     Custom {
         error: String,
@@ -63,11 +82,16 @@ impl ApiError {
                 Self::Database(..) => "database_error",
                 Self::FileHost(..) => "file_host_error",
                 Self::NotFound(..) => "not_found",
+                Self::Unauthorized => "unauthorized",
                 Self::NotOwner => "not_owner",
+                Self::Forbidden(..) => "forbidden",
                 Self::Json(..) => "json_error",
+                Self::Csv(..) => "export_error",
+                Self::Parquet(..) => "export_error",
                 Self::InvalidInput(..) => "invalid_input",
                 Self::Validation(..) => "invalid_input",
                 Self::CampaignStarted(..) => "campaign_started",
+                Self::AttributionWindowExpired { .. } => "attribution_window_expired",
                 Self::Custom { error, .. } => error,
             }
             .to_string(),
@@ -82,11 +106,16 @@ impl ResponseError for ApiError {
             Self::Database(..) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::FileHost(..) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::NotFound(..) => StatusCode::NOT_FOUND,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
             Self::NotOwner => StatusCode::FORBIDDEN,
+            Self::Forbidden(..) => StatusCode::FORBIDDEN,
             Self::Json(..) => StatusCode::BAD_REQUEST,
+            Self::Csv(..) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Parquet(..) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::InvalidInput(..) => StatusCode::BAD_REQUEST,
             Self::Validation(..) => StatusCode::BAD_REQUEST,
             Self::CampaignStarted(..) => StatusCode::CONFLICT,
+            Self::AttributionWindowExpired { .. } => StatusCode::CONFLICT,
             Self::Custom { status_code, .. } => *status_code,
         }
     }
@@ -0,0 +1,40 @@
+use actix_web::{
+    get,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+
+use crate::{
+    models::{
+        dto::{AudienceStats, Stats},
+        url::AdvertiserIdPath,
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+};
+
+#[utoipa::path(
+    tag = "Statistics",
+    operation_id = "get_advertiser_audience_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение статистики по аудитории всех кампаний рекламодателя",
+    description = "Возвращает показы, переходы, конверсию и затраты по всем рекламным кампаниям рекламодателя, сгруппированные по полу, возрастной группе и местоположению клиентов.",
+    params(
+        ("advertiser_id" = Uuid, description = "UUID рекламодателя, для которого запрашивается статистика по аудитории."),
+    ),
+    responses(
+        (status = 200, description = "Статистика по аудитории кампаний рекламодателя успешно получена.", body = AudienceStats),
+        (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
+
+    ),
+)]
+#[get("/audience")]
+pub async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<AdvertiserIdPath>,
+) -> Result<Json<AudienceStats>, ApiError> {
+    Ok(Json(
+        Stats::advertiser_audience(path.advertiser_id, &db).await?,
+    ))
+}
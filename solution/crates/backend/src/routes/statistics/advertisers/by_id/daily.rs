@@ -2,24 +2,36 @@ use actix_web::{
     get,
     web::{Data, Json},
 };
-use actix_web_lab::extract::Path;
+use actix_web_lab::extract::{Path, Query};
 use surrealdb_helper::SurrealDB;
+use validator::Validate;
 
 use crate::{
-    models::{dto::Stats, url::AdvertiserIdPath, ApiError as ApiErrorStruct},
+    models::{
+        dto::{PeriodStats, Stats},
+        url::{AdvertiserIdPath, CampaignBreakdownQuery, StatsRangeQuery},
+        ApiError as ApiErrorStruct,
+    },
     routes::ApiError,
+    utils::validation::parse_validation_errors,
 };
 
 #[utoipa::path(
     tag = "Statistics",
     operation_id = "get_advertiser_daily_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение ежедневной агрегированной статистики по всем кампаниям рекламодателя",
-    description = "Возвращает массив ежедневной сводной статистики по всем рекламным кампаниям заданного рекламодателя.",
+    description = "Возвращает массив сводной статистики по всем рекламным кампаниям заданного рекламодателя за каждый день. \
+        Параметры `from` и `to` ограничивают диапазон дней, `granularity` позволяет агрегировать статистику по неделям или месяцам, \
+        а `by_campaign` добавляет к каждому периоду разбивку по кампаниям.",
     params(
         ("advertiser_id" = Uuid, description = "UUID рекламодателя, для которого запрашивается ежедневная статистика по кампаниям."),
+        StatsRangeQuery,
+        CampaignBreakdownQuery,
     ),
     responses(
-        (status = 200, description = "Ежедневная агрегированная статистика успешно получена.", body = Stats),
+        (status = 200, description = "Ежедневная агрегированная статистика успешно получена.", body = Vec<PeriodStats>),
+        (status = 400, description = "Некорректный диапазон дней.", body = ApiErrorStruct),
         (status = 404, description = "Рекламодателя с указанным UUID не существует.", body = ApiErrorStruct)
 
     ),
@@ -28,8 +40,17 @@ use crate::{
 pub async fn get_handler(
     db: Data<SurrealDB>,
     Path(path): Path<AdvertiserIdPath>,
-) -> Result<Json<Vec<Stats>>, ApiError> {
+    Query(query): Query<StatsRangeQuery>,
+    Query(breakdown): Query<CampaignBreakdownQuery>,
+) -> Result<Json<Vec<PeriodStats>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
     Ok(Json(
-        Stats::advertiser_daily(path.advertiser_id, &db).await?,
+        Stats::advertiser_daily(
+            path.advertiser_id,
+            &query,
+            breakdown.by_campaign.unwrap_or(false),
+            &db,
+        )
+        .await?,
     ))
 }
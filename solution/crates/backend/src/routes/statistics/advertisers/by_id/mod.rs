@@ -1,5 +1,6 @@
 use actix_web::{
     get,
+    middleware::from_fn,
     web::{Data, Json},
 };
 use actix_web_lab::extract::Path;
@@ -9,21 +10,29 @@ use utoipa_actix_web::{scope, service_config::ServiceConfig};
 use crate::{
     models::{dto::Stats, url::AdvertiserIdPath, ApiError as ApiErrorStruct},
     routes::ApiError,
+    utils::auth::require_advertiser_owner,
 };
 
+mod audience;
 mod daily;
+mod export;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/{advertiser_id}")
+            .wrap(from_fn(require_advertiser_owner))
             .service(get_handler)
-            .service(daily::get_handler),
+            .service(daily::get_handler)
+            .service(audience::get_handler)
+            .service(export::daily_handler)
+            .service(export::interactions_handler),
     );
 }
 
 #[utoipa::path(
     tag = "Statistics",
     operation_id = "get_advertiser_campaigns_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение агрегированной статистики по всем кампаниям рекламодателя",
     description = "Возвращает сводную статистику по всем рекламным кампаниям, принадлежащим заданному рекламодателю.",
     params(
@@ -0,0 +1,38 @@
+use actix_web::{
+    get,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+
+use crate::{
+    models::{
+        dto::{AudienceStats, Stats},
+        url::CampaignIdPath,
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+};
+
+#[utoipa::path(
+    tag = "Statistics",
+    operation_id = "get_campaign_audience_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение статистики по аудитории рекламной кампании",
+    description = "Возвращает показы, переходы, конверсию и затраты рекламной кампании, сгруппированные по полу, возрастной группе и местоположению клиентов.",
+    params(
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, для которой запрашивается статистика по аудитории."),
+    ),
+    responses(
+        (status = 200, description = "Статистика по аудитории рекламной кампании успешно получена.", body = AudienceStats),
+        (status = 404, description = "Рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
+
+    ),
+)]
+#[get("/audience")]
+pub async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<CampaignIdPath>,
+) -> Result<Json<AudienceStats>, ApiError> {
+    Ok(Json(Stats::campaign_audience(path.campaign_id, &db).await?))
+}
@@ -0,0 +1,40 @@
+use actix_web::{
+    get,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Path;
+use surrealdb_helper::SurrealDB;
+
+use crate::{
+    models::{
+        dto::{CreativeStats, Stats},
+        url::CampaignIdPath,
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+};
+
+#[utoipa::path(
+    tag = "Statistics",
+    operation_id = "get_campaign_creatives_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Получение статистики по креативам рекламной кампании",
+    description = "Возвращает агрегированную статистику отдельно для каждого креатива рекламной кампании. Переход засчитывается креативу, который был показан клиенту последним.",
+    params(
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, для креативов которой запрашивается статистика."),
+    ),
+    responses(
+        (status = 200, description = "Статистика по креативам рекламной кампании успешно получена.", body = Vec<CreativeStats>),
+        (status = 404, description = "Рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
+
+    ),
+)]
+#[get("/creatives")]
+pub async fn get_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<CampaignIdPath>,
+) -> Result<Json<Vec<CreativeStats>>, ApiError> {
+    Ok(Json(
+        Stats::campaign_creatives(path.campaign_id, &db).await?,
+    ))
+}
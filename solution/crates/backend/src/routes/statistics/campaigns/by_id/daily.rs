@@ -2,24 +2,34 @@ use actix_web::{
     get,
     web::{Data, Json},
 };
-use actix_web_lab::extract::Path;
+use actix_web_lab::extract::{Path, Query};
 use surrealdb_helper::SurrealDB;
+use validator::Validate;
 
 use crate::{
-    models::{dto::Stats, url::CampaignIdPath, ApiError as ApiErrorStruct},
+    models::{
+        dto::{PeriodStats, Stats},
+        url::{CampaignIdPath, StatsRangeQuery},
+        ApiError as ApiErrorStruct,
+    },
     routes::ApiError,
+    utils::validation::parse_validation_errors,
 };
 
 #[utoipa::path(
     tag = "Statistics",
     operation_id = "get_campaign_daily_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение ежедневной статистики по рекламной кампании",
-    description = "Возвращает массив ежедневной статистики для указанной рекламной кампании.",
+    description = "Возвращает массив статистики для указанной рекламной кампании за каждый день. \
+        Параметры `from` и `to` ограничивают диапазон дней, а `granularity` позволяет агрегировать статистику по неделям или месяцам.",
     params(
         ("campaign_id" = Uuid, description = "UUID рекламной кампании, для которой запрашивается ежедневная статистика."),
+        StatsRangeQuery,
     ),
     responses(
-        (status = 200, description = "Ежедневная статистика по рекламной кампании успешно получена.", body = Stats),
+        (status = 200, description = "Ежедневная статистика по рекламной кампании успешно получена.", body = Vec<PeriodStats>),
+        (status = 400, description = "Некорректный диапазон дней.", body = ApiErrorStruct),
         (status = 404, description = "Рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
 
     ),
@@ -28,6 +38,10 @@ use crate::{
 pub async fn get_handler(
     db: Data<SurrealDB>,
     Path(path): Path<CampaignIdPath>,
-) -> Result<Json<Vec<Stats>>, ApiError> {
-    Ok(Json(Stats::campaign_daily(path.campaign_id, &db).await?))
+    Query(query): Query<StatsRangeQuery>,
+) -> Result<Json<Vec<PeriodStats>>, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Ok(Json(
+        Stats::campaign_daily(path.campaign_id, &query, &db).await?,
+    ))
 }
@@ -0,0 +1,78 @@
+use actix_web::{get, web::Data, HttpResponse};
+use actix_web_lab::extract::{Path, Query};
+use surrealdb_helper::SurrealDB;
+use validator::Validate;
+
+use crate::{
+    models::{
+        dto::Export,
+        url::{CampaignIdPath, ExportQuery, StatsRangeQuery},
+        ApiError as ApiErrorStruct,
+    },
+    routes::ApiError,
+    utils::validation::parse_validation_errors,
+};
+
+#[utoipa::path(
+    tag = "Statistics",
+    operation_id = "export_campaign_daily_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Выгрузка ежедневной статистики рекламной кампании",
+    description = "Выгружает ежедневную статистику рекламной кампании в формате CSV или Parquet. \
+        Параметры `from`, `to` и `granularity` работают так же, как при получении ежедневной статистики.",
+    params(
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, для которой выгружаются данные."),
+        ExportQuery,
+        StatsRangeQuery,
+    ),
+    responses(
+        (status = 200, description = "Файл выгрузки передаётся по частям.", content((String = "text/csv"), (Vec<u8> = "application/vnd.apache.parquet"))),
+        (status = 400, description = "Некорректный диапазон дней.", body = ApiErrorStruct),
+        (status = 404, description = "Рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
+
+    ),
+)]
+#[get("/export/daily")]
+pub async fn daily_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<CampaignIdPath>,
+    Query(export): Query<ExportQuery>,
+    Query(query): Query<StatsRangeQuery>,
+) -> Result<HttpResponse, ApiError> {
+    query.validate().map_err(parse_validation_errors)?;
+    Export::campaign_daily(
+        path.campaign_id,
+        &query,
+        export.format.unwrap_or_default(),
+        &db,
+    )
+    .await
+}
+
+#[utoipa::path(
+    tag = "Statistics",
+    operation_id = "export_campaign_interactions",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
+    summary = "Выгрузка взаимодействий клиентов рекламной кампании",
+    description = "Выгружает взаимодействия клиентов с рекламной кампанией (день показа, перехода и конверсии, затраты) в формате CSV или Parquet. \
+        Затраты взаимодействий, цены которых не были сохранены при их записи, рассчитываются по текущим ценам кампании \
+        и отмечаются в колонке `spent_estimated`. \
+        Записи читаются из базы данных постранично и передаются по мере готовности.",
+    params(
+        ("campaign_id" = Uuid, description = "UUID рекламной кампании, для которой выгружаются данные."),
+        ExportQuery,
+    ),
+    responses(
+        (status = 200, description = "Файл выгрузки передаётся по частям.", content((String = "text/csv"), (Vec<u8> = "application/vnd.apache.parquet"))),
+        (status = 404, description = "Рекламной кампании с указанным UUID не существует.", body = ApiErrorStruct)
+
+    ),
+)]
+#[get("/export/interactions")]
+pub async fn interactions_handler(
+    db: Data<SurrealDB>,
+    Path(path): Path<CampaignIdPath>,
+    Query(export): Query<ExportQuery>,
+) -> Result<HttpResponse, ApiError> {
+    Export::campaign_interactions(path.campaign_id, export.format.unwrap_or_default(), &db).await
+}
@@ -1,5 +1,6 @@
 use actix_web::{
     get,
+    middleware::from_fn,
     web::{Data, Json},
 };
 use actix_web_lab::extract::Path;
@@ -9,21 +10,31 @@ use utoipa_actix_web::{scope, service_config::ServiceConfig};
 use crate::{
     models::{dto::Stats, url::CampaignIdPath, ApiError as ApiErrorStruct},
     routes::ApiError,
+    utils::auth::require_campaign_owner,
 };
 
+mod audience;
+mod creatives;
 mod daily;
+mod export;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/{campaign_id}")
+            .wrap(from_fn(require_campaign_owner))
             .service(get_handler)
-            .service(daily::get_handler),
+            .service(daily::get_handler)
+            .service(creatives::get_handler)
+            .service(audience::get_handler)
+            .service(export::daily_handler)
+            .service(export::interactions_handler),
     );
 }
 
 #[utoipa::path(
     tag = "Statistics",
     operation_id = "get_campaign_stats",
+    security(("api_key" = ["ADMIN", "ADVERTISER"])),
     summary = "Получение статистики по рекламной кампании",
     description = "Возвращает агрегированную статистику (показы, переходы, затраты и конверсию) для заданной рекламной кампании.",
     params(
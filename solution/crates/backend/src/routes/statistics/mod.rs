@@ -2,11 +2,13 @@ use utoipa_actix_web::{scope, service_config::ServiceConfig};
 
 mod advertisers;
 mod campaigns;
+mod rebuild;
 
 pub fn config(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/statistics")
             .configure(campaigns::config)
-            .configure(advertisers::config),
+            .configure(advertisers::config)
+            .service(rebuild::post_handler),
     );
 }
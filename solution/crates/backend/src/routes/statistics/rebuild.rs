@@ -0,0 +1,41 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+};
+use actix_web_lab::extract::Query;
+use surrealdb_helper::SurrealDB;
+
+use crate::{
+    models::{dto::StatsRebuildReport, url::StatsRebuildQuery, ApiError as ApiErrorStruct},
+    routes::ApiError,
+};
+
+#[utoipa::path(
+    tag = "Statistics",
+    operation_id = "rebuild_stats",
+    security(("api_key" = ["ADMIN"])),
+    summary = "Пересчёт статистики по истории взаимодействий",
+    description = "Пересчитывает статистику (`current`, `total` и `daily`) всех рекламных кампаний и рекламодателей \
+        по истории взаимодействий клиентов и ценам, сохранённым при каждом показе, переходе и конверсии, \
+        и возвращает найденные расхождения. Взаимодействия окончательно удалённых кампаний учитываются \
+        в статистике рекламодателя по данным, сохранённым при удалении. Используется для восстановления статистики после сбоев. \
+        По умолчанию расхождения только вычисляются. Чтобы сохранить пересчитанную статистику, нужно указать `dry_run=false`. \
+        Статистика, изменившаяся во время пересчёта, не сохраняется и возвращается в `skipped`. \
+        Если у части конверсий не сохранена цена, расходы такой статистики не пересчитываются, \
+        а она возвращается в `unpriced`.",
+    params(StatsRebuildQuery),
+    responses(
+        (status = 200, description = "Статистика успешно пересчитана.", body = StatsRebuildReport),
+        (status = 500, description = "Ошибка базы данных.", body = ApiErrorStruct)
+
+    ),
+)]
+#[post("/rebuild")]
+pub async fn post_handler(
+    db: Data<SurrealDB>,
+    Query(query): Query<StatsRebuildQuery>,
+) -> Result<Json<StatsRebuildReport>, ApiError> {
+    Ok(Json(
+        StatsRebuildReport::rebuild(query.dry_run.unwrap_or(true), &db).await?,
+    ))
+}
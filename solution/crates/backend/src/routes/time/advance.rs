@@ -6,16 +6,20 @@ use surrealdb_helper::SurrealDB;
 use validator::Validate;
 
 use crate::{
-    models::{dto::Time, ApiError as ApiErrorStruct},
+    models::{
+        dto::{Campaign, Time},
+        ApiError as ApiErrorStruct, Caller,
+    },
     routes::ApiError,
-    utils::validation::parse_validation_errors,
+    utils::{minio::Minio, validation::parse_validation_errors},
 };
 
 #[utoipa::path(
     tag = "Time",
     operation_id = "advance_day",
+    security(("api_key" = ["ADMIN", "OPERATOR"])),
     summary = "Установка текущей даты",
-    description = "Устанавливает текущий день в системе в заданную дату.",
+    description = "Устанавливает текущий день в системе в заданную дату. Удалённые кампании, срок хранения которых истёк, удаляются окончательно вместе со статистикой и изображениями. При смене дня удаляются истёкшие ключи идемпотентности.",
     responses(
         (status = 200, description = "Текущая дата обновлена", body = Time),
         (status = 400, description = "Новая дата раньше текущей", body = ApiErrorStruct)
@@ -24,8 +28,12 @@ use crate::{
 #[post("/advance")]
 pub async fn post_handler(
     db: Data<SurrealDB>,
+    minio: Data<Minio>,
+    caller: Caller,
     Json(body): Json<Time>,
 ) -> Result<Json<Time>, ApiError> {
     body.validate().map_err(parse_validation_errors)?;
-    Ok(Json(Time::advance_day(body, &db).await?))
+    let time = Time::advance_day(body, &caller, &db).await?;
+    Campaign::purge_deleted(&db, &minio).await?;
+    Ok(Json(time))
 }
@@ -0,0 +1,110 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    web::Data,
+    Error, HttpMessage,
+};
+use surrealdb_helper::SurrealDB;
+use uuid::Uuid;
+
+use crate::{
+    models::{dto::Campaign, Caller, Role},
+    routes::ApiError,
+};
+
+/// Определяет вызывающего по ключу из заголовка `Authorization: Bearer <ключ>`,
+/// кладёт его в расширения запроса и проверяет, что его роли разрешён вызываемый
+/// метод. Запросы к несуществующим маршрутам пропускаются без проверки.
+pub async fn authorize(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let Some(pattern) = req.match_pattern() else {
+        return next.call(req).await;
+    };
+
+    let api_key = bearer_token(&req)?.ok_or(ApiError::Unauthorized)?;
+    let db = req
+        .app_data::<Data<SurrealDB>>()
+        .expect("SurrealDB should be registered as app data")
+        .clone();
+    let caller = Caller::resolve(&api_key, &db).await?;
+
+    if !caller.role.permits(req.method(), &pattern) {
+        return Err(ApiError::Forbidden(caller.role.to_string()).into());
+    }
+
+    req.extensions_mut().insert(caller);
+    next.call(req).await
+}
+
+/// Пропускает к ресурсам рекламодателя из пути запроса (`{advertiser_id}`)
+/// только его самого и администраторов.
+pub async fn require_advertiser_owner(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let caller = req
+        .extensions()
+        .get::<Caller>()
+        .cloned()
+        .ok_or(ApiError::Unauthorized)?;
+
+    // Некорректный UUID отклонит сам обработчик при разборе пути
+    if let Some(advertiser_id) = req
+        .match_info()
+        .get("advertiser_id")
+        .and_then(|advertiser_id| Uuid::parse_str(advertiser_id).ok())
+    {
+        caller.check_advertiser(advertiser_id)?;
+    }
+
+    next.call(req).await
+}
+
+/// Пропускает к рекламной кампании из пути запроса (`{campaign_id}` или `{ad_id}`)
+/// ключи рекламодателя, только если кампания принадлежит ему. Остальные роли
+/// ограничиваются набором доступных методов.
+pub async fn require_campaign_owner(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let caller = req
+        .extensions()
+        .get::<Caller>()
+        .cloned()
+        .ok_or(ApiError::Unauthorized)?;
+
+    // Некорректный UUID отклонит сам обработчик при разборе пути
+    let campaign_id = req
+        .match_info()
+        .get("campaign_id")
+        .or_else(|| req.match_info().get("ad_id"))
+        .and_then(|campaign_id| Uuid::parse_str(campaign_id).ok());
+
+    if let (Role::Advertiser, Some(campaign_id)) = (caller.role, campaign_id) {
+        let db = req
+            .app_data::<Data<SurrealDB>>()
+            .expect("SurrealDB should be registered as app data");
+
+        let campaign = Campaign::get_by_id_unchecked(campaign_id, db).await?;
+        caller.check_advertiser(campaign.advertiser_id)?;
+    }
+
+    next.call(req).await
+}
+
+fn bearer_token(req: &ServiceRequest) -> Result<Option<String>, ApiError> {
+    let Some(header) = req.headers().get(AUTHORIZATION) else {
+        return Ok(None);
+    };
+
+    header
+        .to_str()
+        .ok()
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .map(|token| Some(token.trim().to_string()))
+        .ok_or(ApiError::Unauthorized)
+}
@@ -0,0 +1,196 @@
+use std::{future::Future, marker::PhantomData};
+
+use actix_web::{
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    HttpResponse,
+};
+use arrow_array::RecordBatch;
+use arrow_schema::{ArrowError, SchemaRef};
+use bytes::Bytes;
+use futures_util::stream;
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+use serde::Serialize;
+
+use crate::{models::url::ExportFormat, routes::ApiError};
+
+/// Количество записей, запрашиваемых из базы данных и кодируемых за один раз.
+const PAGE_SIZE: i64 = 1000;
+
+/// Запись, которую можно выгрузить в CSV (через `Serialize`) и в Parquet.
+pub trait ExportRecord: Serialize + Sized {
+    fn schema() -> SchemaRef;
+
+    fn to_record_batch(records: &[Self]) -> Result<RecordBatch, ArrowError>;
+}
+
+/// Кодирует записи в выбранный формат порциями, не накапливая весь файл в памяти.
+pub struct Exporter<T: ExportRecord> {
+    csv_headers_written: bool,
+    parquet: Option<ArrowWriter<Vec<u8>>>,
+    record: PhantomData<T>,
+}
+
+impl<T: ExportRecord> Exporter<T> {
+    pub fn new(format: ExportFormat) -> Result<Self, ApiError> {
+        Ok(Self {
+            csv_headers_written: false,
+            parquet: match format {
+                ExportFormat::Csv => None,
+                ExportFormat::Parquet => Some(ArrowWriter::try_new(Vec::new(), T::schema(), None)?),
+            },
+            record: PhantomData,
+        })
+    }
+
+    /// Кодирует очередную порцию записей и возвращает готовые к отправке байты.
+    pub fn write(&mut self, records: &[T]) -> Result<Vec<u8>, ApiError> {
+        if records.is_empty() {
+            return Ok(vec![]);
+        }
+
+        match &mut self.parquet {
+            None => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(!self.csv_headers_written)
+                    .from_writer(vec![]);
+                for record in records {
+                    writer.serialize(record)?;
+                }
+                self.csv_headers_written = true;
+                Ok(writer
+                    .into_inner()
+                    .map_err(|err| csv::Error::from(err.into_error()))?)
+            }
+            Some(writer) => {
+                writer.write(&T::to_record_batch(records).map_err(ParquetError::from)?)?;
+                writer.flush()?;
+                Ok(std::mem::take(writer.inner_mut()))
+            }
+        }
+    }
+
+    /// Завершает выгрузку и возвращает оставшиеся байты (для Parquet - метаданные файла).
+    pub fn finish(self) -> Result<Vec<u8>, ApiError> {
+        match self.parquet {
+            None => Ok(vec![]),
+            Some(writer) => Ok(writer.into_inner()?),
+        }
+    }
+}
+
+/// Возвращает файл выгрузки `filename`, который передаётся клиенту по частям:
+/// записи запрашиваются через `fetch_page(limit, offset)` страницами по `PAGE_SIZE`.
+pub fn export_response<T, F, Fut>(
+    format: ExportFormat,
+    filename: String,
+    fetch_page: F,
+) -> Result<HttpResponse, ApiError>
+where
+    T: ExportRecord + 'static,
+    F: FnMut(i64, i64) -> Fut + 'static,
+    Fut: Future<Output = Result<Vec<T>, ApiError>> + 'static,
+{
+    let exporter = Exporter::<T>::new(format)?;
+    let body = stream::try_unfold(
+        (Some(exporter), 0, fetch_page),
+        |(exporter, offset, mut fetch_page)| async move {
+            let Some(mut exporter) = exporter else {
+                return Ok(None);
+            };
+
+            let records = fetch_page(PAGE_SIZE, offset).await?;
+            let mut chunk = exporter.write(&records)?;
+            if (records.len() as i64) < PAGE_SIZE {
+                chunk.extend(exporter.finish()?);
+                return Ok(Some((Bytes::from(chunk), (None, offset, fetch_page))));
+            }
+
+            Ok::<_, ApiError>(Some((
+                Bytes::from(chunk),
+                (Some(exporter), offset + PAGE_SIZE, fetch_page),
+            )))
+        },
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{filename}.{}",
+                format.extension()
+            ))],
+        })
+        .streaming(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{Int32Array, RecordBatch};
+    use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+    use bytes::Bytes;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use serde::Serialize;
+
+    use crate::models::url::ExportFormat;
+
+    use super::{ExportRecord, Exporter};
+
+    #[derive(Serialize)]
+    struct Record {
+        day: i32,
+    }
+
+    impl ExportRecord for Record {
+        fn schema() -> SchemaRef {
+            Arc::new(Schema::new(vec![Field::new("day", DataType::Int32, false)]))
+        }
+
+        fn to_record_batch(records: &[Self]) -> Result<RecordBatch, ArrowError> {
+            RecordBatch::try_new(
+                Self::schema(),
+                vec![Arc::new(Int32Array::from_iter_values(
+                    records.iter().map(|record| record.day),
+                ))],
+            )
+        }
+    }
+
+    #[test]
+    fn csv_headers_written_once() {
+        let mut exporter = Exporter::new(ExportFormat::Csv).unwrap();
+        let mut output = exporter.write(&[Record { day: 1 }]).unwrap();
+        output.extend(exporter.write(&[Record { day: 2 }]).unwrap());
+        output.extend(exporter.finish().unwrap());
+
+        assert_eq!(String::from_utf8(output).unwrap(), "day\n1\n2\n");
+    }
+
+    #[test]
+    fn parquet_file_complete() {
+        let mut exporter = Exporter::new(ExportFormat::Parquet).unwrap();
+        let mut output = exporter.write(&[Record { day: 1 }]).unwrap();
+        output.extend(exporter.write(&[Record { day: 2 }]).unwrap());
+        output.extend(exporter.finish().unwrap());
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(output))
+            .unwrap()
+            .build()
+            .unwrap();
+        let days: Vec<i32> = reader
+            .flat_map(|batch| {
+                let batch = batch.unwrap();
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        assert_eq!(days, vec![1, 2]);
+    }
+}
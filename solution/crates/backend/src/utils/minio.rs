@@ -56,13 +56,84 @@ impl Minio {
         campaign_id: &Uuid,
         file: TempFile,
     ) -> Result<(), Error> {
+        self.put_object(&Self::ids_to_filename(advertiser_id, campaign_id), file)
+            .await
+    }
+
+    pub async fn get_image(
+        &self,
+        advertiser_id: &Uuid,
+        campaign_id: &Uuid,
+    ) -> Result<Option<(String, Bytes)>, Error> {
+        self.get_object(&Self::ids_to_filename(advertiser_id, campaign_id))
+            .await
+    }
+
+    pub async fn remove_image(
+        &self,
+        advertiser_id: &Uuid,
+        campaign_id: &Uuid,
+    ) -> Result<(), Error> {
+        self.remove_object(&Self::ids_to_filename(advertiser_id, campaign_id))
+            .await
+    }
+
+    pub async fn put_creative_image(
+        &self,
+        advertiser_id: &Uuid,
+        campaign_id: &Uuid,
+        creative_id: &Uuid,
+        file: TempFile,
+    ) -> Result<(), Error> {
+        self.put_object(
+            &Self::creative_ids_to_filename(advertiser_id, campaign_id, creative_id),
+            file,
+        )
+        .await
+    }
+
+    pub async fn get_creative_image(
+        &self,
+        advertiser_id: &Uuid,
+        campaign_id: &Uuid,
+        creative_id: &Uuid,
+    ) -> Result<Option<(String, Bytes)>, Error> {
+        self.get_object(&Self::creative_ids_to_filename(
+            advertiser_id,
+            campaign_id,
+            creative_id,
+        ))
+        .await
+    }
+
+    pub async fn remove_creative_image(
+        &self,
+        advertiser_id: &Uuid,
+        campaign_id: &Uuid,
+        creative_id: &Uuid,
+    ) -> Result<(), Error> {
+        self.remove_object(&Self::creative_ids_to_filename(
+            advertiser_id,
+            campaign_id,
+            creative_id,
+        ))
+        .await
+    }
+
+    pub async fn remove_bucket(&self) -> Result<(), Error> {
+        self.client
+            .remove_bucket(&RemoveBucketArgs::new(&self.bucket_name)?)
+            .await?;
+        Ok(())
+    }
+
+    async fn put_object(&self, file_name: &str, file: TempFile) -> Result<(), Error> {
         let file_size = Some(file.size);
-        let file_name = Self::ids_to_filename(advertiser_id, campaign_id);
         let mime_type = file.content_type.unwrap().to_string();
         let mut file = file.file;
 
         let mut args =
-            PutObjectArgs::new(&self.bucket_name, &file_name, &mut file, file_size, None)?;
+            PutObjectArgs::new(&self.bucket_name, file_name, &mut file, file_size, None)?;
         args.content_type = &mime_type;
 
         self.client.put_object(&mut args).await?;
@@ -70,17 +141,10 @@ impl Minio {
         Ok(())
     }
 
-    pub async fn get_image(
-        &self,
-        advertiser_id: &Uuid,
-        campaign_id: &Uuid,
-    ) -> Result<Option<(String, Bytes)>, Error> {
+    async fn get_object(&self, file_name: &str) -> Result<Option<(String, Bytes)>, Error> {
         let resp = if let Ok(resp) = self
             .client
-            .get_object(&GetObjectArgs::new(
-                &self.bucket_name,
-                &Self::ids_to_filename(advertiser_id, campaign_id),
-            )?)
+            .get_object(&GetObjectArgs::new(&self.bucket_name, file_name)?)
             .await
         {
             resp
@@ -103,29 +167,23 @@ impl Minio {
         })
     }
 
-    pub async fn remove_image(
-        &self,
-        advertiser_id: &Uuid,
-        campaign_id: &Uuid,
-    ) -> Result<(), Error> {
+    async fn remove_object(&self, file_name: &str) -> Result<(), Error> {
         self.client
-            .remove_object(&RemoveObjectArgs::new(
-                &self.bucket_name,
-                &Self::ids_to_filename(advertiser_id, campaign_id),
-            )?)
+            .remove_object(&RemoveObjectArgs::new(&self.bucket_name, file_name)?)
             .await?;
 
         Ok(())
     }
 
-    pub async fn remove_bucket(&self) -> Result<(), Error> {
-        self.client
-            .remove_bucket(&RemoveBucketArgs::new(&self.bucket_name)?)
-            .await?;
-        Ok(())
-    }
-
     fn ids_to_filename(advertiser_id: &Uuid, campaign_id: &Uuid) -> String {
         format!("{advertiser_id}_{campaign_id}")
     }
+
+    fn creative_ids_to_filename(
+        advertiser_id: &Uuid,
+        campaign_id: &Uuid,
+        creative_id: &Uuid,
+    ) -> String {
+        format!("{advertiser_id}_{campaign_id}_{creative_id}")
+    }
 }
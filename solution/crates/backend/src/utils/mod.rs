@@ -1,8 +1,13 @@
 use std::fmt::Debug;
 
+use rust_decimal::{Decimal, RoundingStrategy};
+
+pub mod auth;
+pub mod export;
 pub mod logger;
 pub mod minio;
 pub mod openapi;
+pub mod rate_limit;
 pub mod validation;
 
 pub trait RoundToDigits: PartialEq + Debug {
@@ -23,14 +28,23 @@ impl RoundToDigits for f64 {
     }
 }
 
+impl RoundToDigits for Decimal {
+    fn round_to_digits(&self, digits: i32) -> Self {
+        self.round_dp_with_strategy(digits as u32, RoundingStrategy::MidpointAwayFromZero)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
+    use rust_decimal::Decimal;
 
     use super::RoundToDigits;
 
     #[rstest]
     #[case(10.0 / 3.0, 2, 3.33)]
+    #[case(Decimal::new(10, 0) / Decimal::new(3, 0), 2, Decimal::new(333, 2))]
+    #[case(Decimal::new(125, 3), 2, Decimal::new(13, 2))]
     fn round_to_digits<T: RoundToDigits>(
         #[case] input: T,
         #[case] digits: i32,
@@ -1,4 +1,10 @@
-use utoipa::{openapi::OpenApi as OpenApiStruct, OpenApi};
+use utoipa::{
+    openapi::{
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+        Components, OpenApi as OpenApiStruct,
+    },
+    Modify, OpenApi,
+};
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi, Debug)]
@@ -8,6 +14,7 @@ use utoipa_swagger_ui::SwaggerUi;
         description = r#"API для управления данными клиентов, рекламодателей, рекламными кампаниями, показом объявлений, статистикой и управлением "текущим днём" в системе."#,
         version = "1.1.0"
     ),
+    modifiers(&ApiKeySecurity),
     servers(
         (
             url = "http://localhost:8080",
@@ -31,6 +38,22 @@ use utoipa_swagger_ui::SwaggerUi;
             name = "Campaign images",
             description = "Управление изображениями рекламных кампаний: загрузка, обновление, удаление и получение изображения рекламной кампании."
         ),
+        (
+            name = "Creatives",
+            description = "Управление креативами рекламных кампаний: варианты заголовка, текста и изображения объявления для A/B тестирования."
+        ),
+        (
+            name = "Segments",
+            description = "Управление сегментами аудитории: сохранённые настройки таргетинга, которые можно использовать в нескольких кампаниях."
+        ),
+        (
+            name = "Billing",
+            description = "Биллинг рекламодателей: предоплаченный баланс, журнал списаний и счета за период."
+        ),
+        (
+            name = "API keys",
+            description = "Ключи API рекламодателей и платформенных ролей (оператор, клиентское приложение, анализ данных): выпуск, просмотр и отзыв. Ключ передаётся в заголовке `Authorization: Bearer <ключ>`."
+        ),
         (
             name = "Ads",
             description = "Показ рекламных объявлений клиентам и фиксация кликов."
@@ -42,10 +65,34 @@ use utoipa_swagger_ui::SwaggerUi;
         (
             name = "Time",
             description = "Управление текущим днём (эмуляция времени) в системе."
+        ),
+        (
+            name = "Audit",
+            description = "Журнал аудита изменяющих запросов: кто, когда и что изменил."
         )
     )
 )]
 pub struct Swagger;
+
+/// Схема авторизации по ключу API. В требованиях операций перечисляются роли,
+/// которым доступен метод.
+struct ApiKeySecurity;
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut OpenApiStruct) {
+        openapi
+            .components
+            .get_or_insert_with(Components::new)
+            .add_security_scheme(
+                "api_key",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .description(Some("Ключ API, выпущенный администратором платформы."))
+                        .build(),
+                ),
+            );
+    }
+}
 impl Swagger {
     pub fn ui_service(api: OpenApiStruct) -> SwaggerUi {
         SwaggerUi::new("/swagger-ui/{_}*").url("/openapi.json", api)
@@ -0,0 +1,331 @@
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{HeaderValue, RETRY_AFTER},
+        Method, StatusCode,
+    },
+    Error, HttpMessage, ResponseError,
+};
+use futures_util::future::LocalBoxFuture;
+
+use crate::{config, models::Caller, routes::ApiError};
+
+/// Количество корзин, после которого из памяти удаляются полностью
+/// восстановившиеся корзины.
+const CLEANUP_THRESHOLD: usize = 10_000;
+
+/// Ограничение частоты запросов: `per_second` запросов в секунду
+/// с возможным всплеском до `burst` запросов подряд.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RateLimit {
+    pub per_second: u32,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Ограничение из переменных окружения. Нулевая частота отключает ограничение.
+    fn from_config(per_second: i32, burst: i32) -> Option<Self> {
+        (per_second > 0).then(|| Self {
+            per_second: per_second as u32,
+            burst: burst.max(1) as u32,
+        })
+    }
+}
+
+/// Группа методов, для которой действует отдельное ограничение.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum RateLimitGroup {
+    /// Все запросы с одного IP адреса, включая запросы с неверным ключом API.
+    Ip,
+    /// Получение объявления клиентом (`GET /ads`).
+    Ads,
+    /// Все изменяющие методы.
+    Write,
+}
+
+impl RateLimitGroup {
+    /// Группа определяется по шаблону маршрута, а не по пути, поэтому
+    /// на неё не влияет запись пути (`//ads`, `/ads/`).
+    fn of(req: &ServiceRequest) -> Option<Self> {
+        let method = req.method();
+        if *method == Method::GET && req.match_pattern().as_deref() == Some("/ads") {
+            Some(Self::Ads)
+        } else if [Method::POST, Method::PUT, Method::PATCH, Method::DELETE].contains(method) {
+            Some(Self::Write)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, limit: RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.per_second as f64).min(limit.burst as f64);
+        self.updated_at = now;
+    }
+
+    /// Забирает токен из корзины или возвращает время, через которое он появится.
+    fn take(&mut self, limit: RateLimit, now: Instant) -> Result<(), Duration> {
+        self.refill(limit, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / limit.per_second as f64,
+            ))
+        }
+    }
+
+    fn is_full(&self, limit: RateLimit, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens + elapsed * limit.per_second as f64 >= limit.burst as f64
+    }
+}
+
+#[derive(Default, Debug)]
+struct RateLimiterState {
+    ads: Option<RateLimit>,
+    write: Option<RateLimit>,
+    ip: Option<RateLimit>,
+    buckets: Mutex<HashMap<(RateLimitGroup, String), TokenBucket>>,
+}
+
+impl RateLimiterState {
+    fn limit(&self, group: RateLimitGroup) -> Option<RateLimit> {
+        match group {
+            RateLimitGroup::Ip => self.ip,
+            RateLimitGroup::Ads => self.ads,
+            RateLimitGroup::Write => self.write,
+        }
+    }
+
+    fn take(&self, group: RateLimitGroup, client: String, now: Instant) -> Result<(), Duration> {
+        let Some(limit) = self.limit(group) else {
+            return Ok(());
+        };
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() > CLEANUP_THRESHOLD {
+            buckets.retain(|(group, _), bucket| {
+                self.limit(*group)
+                    .is_some_and(|limit| !bucket.is_full(limit, now))
+            });
+        }
+
+        buckets
+            .entry((group, client))
+            .or_insert_with(|| TokenBucket::full(limit, now))
+            .take(limit, now)
+    }
+}
+
+/// Этап обработки запроса, на котором подключён ограничитель.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum RateLimitStage {
+    /// До `authorize`: все запросы учитываются по IP адресу, поэтому перебор
+    /// ключей ограничивается до их поиска в базе данных.
+    BeforeAuthorization,
+    /// После `authorize`: `GET /ads` и изменяющие методы учитываются по проверенному ключу API.
+    AfterAuthorization,
+}
+
+/// Ограничитель частоты запросов по алгоритму token bucket. Корзины ведутся
+/// отдельно для каждого ключа API и отдельно для `GET /ads` и изменяющих методов,
+/// а также для каждого IP адреса. Сам ограничитель подключается после `authorize`,
+/// а его ограничение по IP адресу, [`RateLimiter::by_ip`], — перед ним.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    state: Arc<RateLimiterState>,
+    stage: RateLimitStage,
+}
+
+impl RateLimiter {
+    pub fn new(ads: Option<RateLimit>, write: Option<RateLimit>, ip: Option<RateLimit>) -> Self {
+        Self {
+            state: Arc::new(RateLimiterState {
+                ads,
+                write,
+                ip,
+                ..Default::default()
+            }),
+            stage: RateLimitStage::AfterAuthorization,
+        }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(
+            RateLimit::from_config(*config::ADS_RATE_LIMIT, *config::ADS_RATE_LIMIT_BURST),
+            RateLimit::from_config(*config::WRITE_RATE_LIMIT, *config::WRITE_RATE_LIMIT_BURST),
+            RateLimit::from_config(*config::IP_RATE_LIMIT, *config::IP_RATE_LIMIT_BURST),
+        )
+    }
+
+    /// Ограничение по IP адресу, которое подключается перед `authorize`.
+    pub fn by_ip(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            stage: RateLimitStage::BeforeAuthorization,
+        }
+    }
+}
+
+/// Группа и идентификатор клиента, по которым ведётся корзина запроса.
+fn bucket_key(req: &ServiceRequest, stage: RateLimitStage) -> Option<(RateLimitGroup, String)> {
+    match stage {
+        RateLimitStage::BeforeAuthorization => {
+            let ip = match req.peer_addr() {
+                Some(addr) => format!("ip:{}", addr.ip()),
+                None => "ip:unknown".into(),
+            };
+            Some((RateLimitGroup::Ip, ip))
+        }
+        // Запросы без вызывающего (к несуществующим маршрутам) учитываются только по IP адресу
+        RateLimitStage::AfterAuthorization => {
+            let client = match req.extensions().get::<Caller>()? {
+                Caller {
+                    api_key_id: Some(api_key_id),
+                    ..
+                } => format!("key:{api_key_id}"),
+                _ => "admin".into(),
+            };
+            Some((RateLimitGroup::of(req)?, client))
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RateLimiterMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimiterMiddleware {
+            service: Rc::new(service),
+            state: self.state.clone(),
+            stage: self.stage,
+        }))
+    }
+}
+
+pub struct RateLimiterMiddleware<S> {
+    service: Rc<S>,
+    state: Arc<RateLimiterState>,
+    stage: RateLimitStage,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimiterMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let limited = bucket_key(&req, self.stage)
+            .and_then(|(group, client)| self.state.take(group, client, Instant::now()).err());
+
+        if let Some(retry_after) = limited {
+            // Retry-After принимает только целое число секунд
+            let retry_after = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            let mut response = ApiError::Custom {
+                error: "too_many_requests".into(),
+                status_code: StatusCode::TOO_MANY_REQUESTS,
+                message: format!("Rate limit exceeded, retry in {retry_after} s"),
+            }
+            .error_response();
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+            return Box::pin(async move { Ok(req.into_response(response)) });
+        }
+
+        let service = self.service.clone();
+        Box::pin(async move { Ok(service.call(req).await?.map_into_boxed_body()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use rstest::rstest;
+
+    use super::{RateLimit, TokenBucket};
+
+    const LIMIT: RateLimit = RateLimit {
+        per_second: 2,
+        burst: 3,
+    };
+
+    #[rstest]
+    #[case(0, 0, Ok(()))]
+    #[case(2, 0, Ok(()))]
+    #[case(3, 0, Err(Duration::from_millis(500)))]
+    #[case(3, 250, Err(Duration::from_millis(250)))]
+    #[case(3, 500, Ok(()))]
+    #[case(5, 10_000, Ok(()))]
+    fn take(#[case] taken: usize, #[case] after_ms: u64, #[case] expected: Result<(), Duration>) {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::full(LIMIT, now);
+        for _ in 0..taken {
+            let _ = bucket.take(LIMIT, now);
+        }
+
+        let result = bucket.take(LIMIT, now + Duration::from_millis(after_ms));
+        assert_eq!(
+            result.map_err(|wait| wait.as_millis()),
+            expected.map_err(|wait| wait.as_millis())
+        );
+    }
+
+    #[rstest]
+    #[case(1, 0, false)]
+    #[case(1, 499, false)]
+    #[case(1, 500, true)]
+    fn is_full(#[case] taken: usize, #[case] after_ms: u64, #[case] expected: bool) {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::full(LIMIT, now);
+        for _ in 0..taken {
+            let _ = bucket.take(LIMIT, now);
+        }
+
+        assert_eq!(
+            bucket.is_full(LIMIT, now + Duration::from_millis(after_ms)),
+            expected
+        );
+    }
+}
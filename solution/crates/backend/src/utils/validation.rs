@@ -1,9 +1,10 @@
 use profanity_checker::ProfanityChecker;
+use rust_decimal::Decimal;
 use std::{ops::Not, sync::LazyLock};
 
 use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
 
-use crate::{config, routes::ApiError};
+use crate::{config, models::Role, routes::ApiError};
 
 static PROFANITY_CHECKER: LazyLock<ProfanityChecker> = LazyLock::new(ProfanityChecker::new);
 static PROFANITY_CHECKER_ADVANCED: LazyLock<ProfanityChecker> =
@@ -33,6 +34,42 @@ pub fn check_profanity_advanced(text: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+pub fn check_positive_money(amount: &Decimal) -> Result<(), ValidationError> {
+    if amount.is_sign_negative() || amount.is_zero() {
+        return Err(ValidationError::new("Amount must be greater than 0"));
+    }
+
+    Ok(())
+}
+
+pub fn check_non_negative_money(amount: &Decimal) -> Result<(), ValidationError> {
+    if amount.is_sign_negative() && !amount.is_zero() {
+        return Err(ValidationError::new("Amount must not be negative"));
+    }
+
+    Ok(())
+}
+
+pub fn check_currency_code(code: &str) -> Result<(), ValidationError> {
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(ValidationError::new(
+            "Currency must be an ISO 4217 code like `RUB`",
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn check_platform_role(role: &Role) -> Result<(), ValidationError> {
+    if !role.is_platform() {
+        return Err(ValidationError::new(
+            "Role must be one of `OPERATOR`, `CLIENT_APP`, `DATA_SCIENCE`",
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn parse_validation_errors(errors: ValidationErrors) -> ApiError {
     ApiError::InvalidInput(validation_errors_to_string(errors, None))
 }
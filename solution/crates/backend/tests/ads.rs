@@ -3,10 +3,13 @@ use common::{
     environment::with_test_environment,
     models::{
         builders::{Buildable, Builder},
-        Advertiser, Campaign, ClickInfo, Client, CreateCampaign, MLScore, Model, Targeting,
+        Ad, AdDecision, Advertiser, AgeRange, Campaign, ClickInfo, Client, ConversionInfo,
+        CreateCampaign, FrequencyCap, MLScore, Model, OptionalModel, Stats, Targeting,
+        TargetingRule, Time,
     },
 };
 use rand::{random_bool, rng, seq::IndexedRandom, Rng};
+use serial_test::serial;
 use uuid::Uuid;
 
 mod common;
@@ -72,8 +75,578 @@ async fn dev() {
     .await
 }
 
+#[actix_rt::test]
+async fn get_ad_for_client_total_budget_exhausted() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_cost_per_impression(1.0)
+            .with_total_budget(1.5)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(clients[0].id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(clients[1].id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ad_for_client_daily_budget_paced() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        // Бюджет в 4 единицы на 2 дня распределяется по 2 единицы в день
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(2)
+            .with_cost_per_impression(1.0)
+            .with_total_budget(4.0)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for client in &clients[..2] {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let resp = api.get_ad_for_client(clients[2].id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ad_for_client_budget_click_overshoot() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_cost_per_impression(1.0)
+            .with_cost_per_click(2.0)
+            .with_total_budget(1.5)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(clients[0].id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        // Бюджет ограничивает показы, переход по показанному объявлению списывается сверх него
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(Stats::from_resp(resp).await.spent_total, 3.0);
+
+        let resp = api.get_ad_for_client(clients[1].id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ad_for_client_frequency_cap_lifetime() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .with_frequency_cap(FrequencyCap {
+                lifetime: Some(2),
+                ..FrequencyCap::empty()
+            })
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        for _ in 0..2 {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_json!(resp, StatusCode::OK, Ad, Ad::from(campaign.clone()));
+        }
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        // Повторные показы ограничиваются, но не списываются
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.impressions_count, 1);
+    })
+    .await
+}
+
+#[serial(time)]
+#[actix_rt::test]
+async fn get_ad_for_client_repeat_impression_after_click() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(10)
+            .with_cost_per_impression(1.0)
+            .with_cost_per_click(2.0)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let mut time = Time::start();
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.impressions_count, 1);
+        assert_eq!(stats.clicks_count, 1);
+        assert_eq!(stats.spent_total, 3.0);
+
+        // Повторный показ не переносит день перехода
+        let resp = api.get_campaign_daily_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let daily = Vec::<Stats>::from_resp(resp).await;
+        assert_eq!(
+            daily
+                .iter()
+                .map(|stats| stats.clicks_count)
+                .collect::<Vec<_>>(),
+            vec![1, 0]
+        );
+    })
+    .await
+}
+
+#[serial(time)]
+#[actix_rt::test]
+async fn get_ad_for_client_frequency_cap_per_day() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(10)
+            .with_targeting(Targeting::empty())
+            .with_frequency_cap(FrequencyCap {
+                per_day: Some(1),
+                min_days_between: Some(2),
+                ..FrequencyCap::empty()
+            })
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let mut time = Time::start();
+        for expected in [StatusCode::NO_CONTENT, StatusCode::OK] {
+            time.increase_day();
+            let resp = api.advance_day(time).await;
+            assert_status!(resp, StatusCode::OK);
+
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, expected);
+        }
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ad_for_client_targeting_lists() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![
+            Client::builder()
+                .with_location("Moscow")
+                .with_age(20)
+                .build(),
+            Client::builder()
+                .with_location("Kazan")
+                .with_age(65)
+                .build(),
+            Client::builder().with_location("Tver").with_age(20).build(),
+            Client::builder()
+                .with_location("Kazan")
+                .with_age(40)
+                .build(),
+            Client::builder()
+                .with_location("Moscow")
+                .with_age(20)
+                .build(),
+        ];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting {
+                locations: Some(vec!["Moscow".into(), "Kazan".into()]),
+                age_ranges: Some(vec![
+                    AgeRange {
+                        age_from: Some(18),
+                        age_to: Some(24),
+                    },
+                    AgeRange {
+                        age_from: Some(40),
+                        age_to: Some(40),
+                    },
+                    AgeRange {
+                        age_from: Some(60),
+                        age_to: None,
+                    },
+                ]),
+                denied_clients: Some(vec![clients[4].id]),
+                ..Targeting::empty()
+            })
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for (client, expected) in clients.iter().zip([
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::NO_CONTENT,
+            StatusCode::OK,
+            StatusCode::NO_CONTENT,
+        ]) {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, expected);
+        }
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ad_for_client_targeting_rules() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![
+            Client::builder()
+                .with_attribute("device", "ios")
+                .with_attribute("subscription_tier", 3.0)
+                .with_attribute("interests", vec!["sport", "music"])
+                .build(),
+            Client::builder()
+                .with_attribute("device", "android")
+                .with_attribute("subscription_tier", 3.0)
+                .with_attribute("interests", vec!["sport"])
+                .build(),
+            Client::builder()
+                .with_attribute("device", "ios")
+                .with_attribute("subscription_tier", 1.0)
+                .with_attribute("interests", vec!["sport"])
+                .build(),
+            Client::builder()
+                .with_attribute("device", "ios")
+                .with_attribute("subscription_tier", 3.0)
+                .build(),
+        ];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting {
+                rules: Some(vec![
+                    TargetingRule {
+                        values: Some(vec!["ios".into(), "ipados".into()]),
+                        ..TargetingRule::new("device", "in")
+                    },
+                    TargetingRule {
+                        min: Some(2.0),
+                        ..TargetingRule::new("subscription_tier", "range")
+                    },
+                    TargetingRule {
+                        value: Some("sport".into()),
+                        ..TargetingRule::new("interests", "contains")
+                    },
+                ]),
+                ..Targeting::empty()
+            })
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for (client, expected) in clients.iter().zip([
+            StatusCode::OK,
+            StatusCode::NO_CONTENT,
+            StatusCode::NO_CONTENT,
+            StatusCode::NO_CONTENT,
+        ]) {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, expected);
+        }
+    })
+    .await
+}
+
 // TODO: написать больше тестов на получение рекламных объявлений
 
+#[actix_rt::test]
+async fn get_ads_for_client_invalid_slots() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for slots in [0, 21] {
+            let resp = api.get_ads_for_client(client.id, slots, false).await;
+            assert_status!(resp, StatusCode::BAD_REQUEST);
+        }
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ads_for_client_slots() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertisers = vec![Advertiser::new(), Advertiser::new()];
+        let resp = api.upsert_advertisers(advertisers.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let mut ads = Vec::new();
+        for advertiser_id in [advertisers[0].id, advertisers[0].id, advertisers[1].id] {
+            let campaign = CreateCampaign::builder()
+                .with_start_date(1)
+                .with_targeting(Targeting::empty())
+                .build();
+            let resp = api.create_campaign(advertiser_id, campaign).await;
+            assert_status!(resp, StatusCode::CREATED);
+            ads.push(Ad::from(Campaign::from_resp(resp).await));
+        }
+
+        let resp = api.get_ads_for_client(clients[0].id, 5, false).await;
+        assert_status!(resp, StatusCode::OK);
+        let received = Vec::<Ad>::from_resp(resp).await;
+        assert_eq!(received.len(), ads.len());
+        for ad in &ads {
+            assert!(received.contains(ad));
+        }
+
+        let resp = api.get_ads_for_client(clients[1].id, 5, true).await;
+        assert_status!(resp, StatusCode::OK);
+        let received = Vec::<Ad>::from_resp(resp).await;
+        assert_eq!(received.len(), advertisers.len());
+        for advertiser in &advertisers {
+            assert!(received.iter().any(|ad| ad.advertiser_id == advertiser.id));
+        }
+
+        let resp = api.get_ads_for_client(clients[1].id, 1, false).await;
+        assert_status!(resp, StatusCode::OK);
+        let received = Vec::<Ad>::from_resp(resp).await;
+        assert_eq!(received.len(), 1);
+
+        for ad in &ads {
+            let resp = api.get_campaign_stats(ad.id).await;
+            assert_status!(resp, StatusCode::OK);
+            let stats = Stats::from_resp(resp).await;
+            assert!(stats.impressions_count >= 1);
+        }
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn explain_ad_for_client_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api.explain_ad_for_client(Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn explain_ad_for_client() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::builder().with_gender("MALE").build();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let matching = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, matching).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let matching = Campaign::from_resp(resp).await;
+
+        let other_gender = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(
+                Targeting::builder()
+                    .with_gender(Some("FEMALE"))
+                    .with_age_from(None)
+                    .with_age_to(None)
+                    .with_location(None)
+                    .build(),
+            )
+            .build();
+        let resp = api.create_campaign(advertiser.id, other_gender).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let other_gender = Campaign::from_resp(resp).await;
+
+        let resp = api.explain_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let decisions = Vec::<AdDecision>::from_resp(resp).await;
+        assert_eq!(decisions.len(), 2);
+
+        let decision = |id| decisions.iter().find(|d| d.campaign_id == id).unwrap();
+        assert!(decision(matching.id).eligible);
+        assert!(!decision(other_gender.id).eligible);
+        assert!(!decision(other_gender.id).predicates.gender);
+
+        let resp = api.get_campaign_stats(matching.id).await;
+        assert_json!(resp, StatusCode::OK, Stats, Stats::empty());
+    })
+    .await
+}
+
+#[serial(time)]
+#[actix_rt::test]
+async fn explain_ad_for_client_after_end_date() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let mut time = Time::start();
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.explain_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let decisions = Vec::<AdDecision>::from_resp(resp).await;
+        assert_eq!(decisions.len(), 1);
+        assert!(!decisions[0].predicates.active_window);
+        assert!(!decisions[0].eligible);
+    })
+    .await
+}
+
 #[actix_rt::test]
 async fn record_ad_click_non_existent() {
     with_test_environment(|test_env| async move {
@@ -123,4 +696,140 @@ async fn record_ad_click_not_viewed() {
     .await
 }
 
+#[serial(time)]
+#[actix_rt::test]
+async fn record_ad_click_attribution_window_expired() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        for client in &clients {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let mut time = Time::start();
+        for _ in 0..7 {
+            time.increase_day();
+        }
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(clients[1].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        for _ in 0..30 {
+            time.increase_day();
+        }
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let conversion_info = ConversionInfo {
+            client_id: clients[0].id,
+            value: None,
+        };
+        let resp = api.record_ad_conversion(campaign.id, conversion_info).await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.clicks_count, 1);
+        assert_eq!(stats.conversions_count, 0);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn record_ad_conversion() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .with_cost_per_conversion(25.0)
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let conversion_info = ConversionInfo {
+            client_id: client.id,
+            value: Some(1000.0),
+        };
+        let resp = api
+            .record_ad_conversion(campaign.id, conversion_info.clone())
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .record_ad_conversion(campaign.id, conversion_info.clone())
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        for _ in 0..2 {
+            let resp = api
+                .record_ad_conversion(campaign.id, conversion_info.clone())
+                .await;
+            assert_status!(resp, StatusCode::NO_CONTENT);
+        }
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.conversions_count, 1);
+        assert_eq!(stats.conversion_value, 1000.0);
+        assert_eq!(stats.spent_conversions, 25.0);
+        assert_eq!(
+            stats.spent_total,
+            stats.spent_impressions + stats.spent_clicks + stats.spent_conversions
+        );
+        assert_eq!(
+            stats.cost_per_acquisition,
+            (stats.spent_total * 100.0).round() / 100.0
+        );
+    })
+    .await
+}
+
 // TODO: написать больше тестов на переходы по рекламным объявлениям
@@ -1,7 +1,7 @@
 mod common;
 
 use actix_http::StatusCode;
-use common::{environment::with_test_environment, models::{builders::{Buildable, Builder}, Advertiser, Client, MLScore, Model}
+use common::{environment::with_test_environment, models::{builders::{Buildable, Builder}, Advertiser, Client, MLScore, Model, TopUp}
 };
 use rand::{rng, seq::IndexedRandom};
 use rstest::rstest;
@@ -14,6 +14,16 @@ use validator::ValidateLength;
         .with_name("питон")
         .build()
 )]
+#[case::lowercase_currency(
+    Advertiser::builder()
+        .with_currency("rub")
+        .build()
+)]
+#[case::unknown_currency_format(
+    Advertiser::builder()
+        .with_currency("RUBLE")
+        .build()
+)]
 #[actix_rt::test]
 async fn advertisers_upsertion_invalid(#[case] advertiser: Advertiser) {
     with_test_environment(|test_env| async move {
@@ -62,6 +72,39 @@ async fn advertisers_upsertion_with_duplicates() {
     .await
 }
 
+#[actix_rt::test]
+async fn advertiser_currency_change() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut advertiser = Advertiser::builder().with_currency("USD").build();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        advertiser.currency = "EUR".into();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_json!(resp, StatusCode::CREATED, Vec<Advertiser>, vec![advertiser.clone()]);
+
+        let resp = api
+            .top_up_balance(advertiser.id, TopUp { amount: 10.0 })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let mut changed = advertiser.clone();
+        changed.currency = "RUB".into();
+        let resp = api.upsert_advertisers(vec![changed]).await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        advertiser.name = "Renamed".into();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_json!(resp, StatusCode::CREATED, Vec<Advertiser>, vec![advertiser.clone()]);
+
+        let resp = api.get_advertiser_by_id(advertiser.id).await;
+        assert_json!(resp, StatusCode::OK, Advertiser, advertiser);
+    })
+    .await
+}
+
 #[actix_rt::test]
 async fn get_advertiser_by_id_non_existent() {
     with_test_environment(|test_env| async move {
@@ -0,0 +1,94 @@
+use actix_http::StatusCode;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Ad, Advertiser, Campaign, ClickInfo, Client, CreateCampaign, Model, OptionalModel, Stats,
+        Targeting,
+    },
+};
+
+mod common;
+
+fn second_price_auction() {
+    std::env::set_var("AUCTION_MODE", "second_price");
+}
+
+#[actix_rt::test]
+async fn second_price_auction_charges_runner_up_price() {
+    second_price_auction();
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        // Без ML скоров скор кампании равен сумме ставок: 12 у победителя и 6 у следующей
+        let mut campaigns = Vec::new();
+        for (cost_per_impression, cost_per_click) in [(2.0, 10.0), (1.0, 5.0)] {
+            let campaign = CreateCampaign::builder()
+                .with_start_date(1)
+                .with_cost_per_impression(cost_per_impression)
+                .with_cost_per_click(cost_per_click)
+                .with_targeting(Targeting::empty())
+                .build();
+            let resp = api.create_campaign(advertiser.id, campaign).await;
+            assert_status!(resp, StatusCode::CREATED);
+            campaigns.push(Campaign::from_resp(resp).await);
+        }
+        let winner = campaigns[0].clone();
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_json!(resp, StatusCode::OK, Ad, Ad::from(winner.clone()));
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(winner.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_campaign_stats(winner.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.spent_impressions, 1.0);
+        assert_eq!(stats.spent_clicks, 5.0);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn second_price_auction_without_runner_up() {
+    second_price_auction();
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_cost_per_impression(2.0)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.spent_impressions, 2.0);
+    })
+    .await
+}
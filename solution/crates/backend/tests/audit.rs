@@ -0,0 +1,347 @@
+mod common;
+
+use actix_http::StatusCode;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Advertiser, ApiKey, AuditActor, AuditEntry, Campaign, CampaignUpdate, Client,
+        CreateCampaign, CreateCreative, CreateSegment, Creative, MLScore, Model, OptionalModel,
+        Segment, SegmentUpdate, Targeting, Time,
+    },
+};
+use rstest::rstest;
+use serde_json::json;
+use serial_test::serial;
+use uuid::Uuid;
+
+#[actix_rt::test]
+async fn campaign_lifecycle() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.issue_api_key(advertiser.id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let api_key = ApiKey::from_resp(resp).await;
+        let owner_api = api.with_api_key(api_key.key.clone().unwrap());
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(50)
+            .with_end_date(60)
+            .with_ad_title("Old title")
+            .build();
+        let resp = owner_api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let campaign_update = CampaignUpdate {
+            ad_title: Some("New title".into()),
+            ..CampaignUpdate::empty()
+        };
+        let resp = owner_api
+            .update_campaign(advertiser.id, campaign.id, campaign_update)
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = owner_api.delete_campaign(advertiser.id, campaign.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api
+            .list_audit_entries(&format!("entity=CAMPAIGN&entity_id={}", campaign.id))
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let entries = Vec::<AuditEntry>::from_resp(resp).await;
+
+        let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            vec!["CAMPAIGN_CREATED", "CAMPAIGN_UPDATED", "CAMPAIGN_DELETED"]
+        );
+
+        let actor = AuditActor {
+            role: "ADVERTISER".into(),
+            advertiser_id: Some(advertiser.id),
+            api_key_id: Some(api_key.id),
+        };
+        for entry in &entries {
+            assert_eq!(entry.entity_id, Some(campaign.id));
+            assert_eq!(entry.advertiser_id, Some(advertiser.id));
+            assert_eq!(entry.actor, actor);
+            assert_eq!(entry.date, 1);
+        }
+
+        assert_eq!(entries[0].before, None);
+        assert_eq!(entries[0].after.as_ref().unwrap()["ad_title"], "Old title");
+        assert_eq!(entries[1].before, Some(json!({"ad_title": "Old title"})));
+        assert_eq!(entries[1].after, Some(json!({"ad_title": "New title"})));
+        assert_eq!(entries[2].before.as_ref().unwrap()["ad_title"], "New title");
+        assert_eq!(entries[2].after, None);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn campaign_state_changes_and_creatives() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .create_campaign(advertiser.id, CreateCampaign::builder().build())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api
+            .create_creative(
+                advertiser.id,
+                campaign.id,
+                CreateCreative::new("Title", "Text"),
+            )
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let creative = Creative::from_resp(resp).await;
+
+        let resp = api
+            .delete_creative(advertiser.id, campaign.id, creative.id)
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        for action in ["pause", "resume", "archive"] {
+            let resp = api
+                .set_campaign_state(advertiser.id, campaign.id, action)
+                .await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let resp = api.delete_campaign(advertiser.id, campaign.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+        let resp = api.restore_campaign(advertiser.id, campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .list_audit_entries(&format!("entity=CREATIVE&entity_id={}", creative.id))
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let entries = Vec::<AuditEntry>::from_resp(resp).await;
+        let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["CREATIVE_CREATED", "CREATIVE_DELETED"]);
+        assert_eq!(entries[0].after.as_ref().unwrap()["ad_title"], "Title");
+        assert_eq!(entries[1].before.as_ref().unwrap()["ad_title"], "Title");
+
+        let resp = api
+            .list_audit_entries(&format!("entity=CAMPAIGN&entity_id={}", campaign.id))
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let entries = Vec::<AuditEntry>::from_resp(resp).await;
+        let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                "CAMPAIGN_CREATED",
+                "CAMPAIGN_PAUSED",
+                "CAMPAIGN_RESUMED",
+                "CAMPAIGN_ARCHIVED",
+                "CAMPAIGN_DELETED",
+                "CAMPAIGN_RESTORED"
+            ]
+        );
+        assert_eq!(entries[1].before, Some(json!({"state": "ACTIVE"})));
+        assert_eq!(entries[1].after, Some(json!({"state": "PAUSED"})));
+        assert_eq!(entries[3].after, Some(json!({"state": "ARCHIVED"})));
+        assert_eq!(entries[5].after.as_ref().unwrap()["state"], "ARCHIVED");
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn segment_update() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .create_segment(
+                advertiser.id,
+                CreateSegment::new("Old name", Targeting::empty()),
+            )
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let segment = Segment::from_resp(resp).await;
+
+        let segment_update = SegmentUpdate {
+            name: Some("New name".into()),
+            targeting: None,
+        };
+        let resp = api
+            .update_segment(advertiser.id, segment.id, segment_update)
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.list_audit_entries("entity=SEGMENT").await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Vec<AuditEntry>,
+            vec![AuditEntry {
+                audit_entry_id: Uuid::nil(),
+                action: "SEGMENT_UPDATED".into(),
+                entity: "SEGMENT".into(),
+                entity_id: Some(segment.id),
+                advertiser_id: Some(advertiser.id),
+                actor: AuditActor {
+                    role: "ADMIN".into(),
+                    advertiser_id: None,
+                    api_key_id: None,
+                },
+                date: 1,
+                before: Some(json!({"name": "Old name"})),
+                after: Some(json!({"name": "New name"})),
+            }]
+        );
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn bulk_upserts_and_ml_scores() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let ml_score = MLScore::builder()
+            .with_client_id(clients[0].id)
+            .with_advertiser_id(advertiser.id)
+            .build();
+        let resp = api.upsert_ml_score(ml_score.clone()).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let admin = AuditActor {
+            role: "ADMIN".into(),
+            advertiser_id: None,
+            api_key_id: None,
+        };
+
+        let resp = api.list_audit_entries("entity=CLIENT").await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Vec<AuditEntry>,
+            clients
+                .iter()
+                .map(|client| AuditEntry {
+                    audit_entry_id: Uuid::nil(),
+                    action: "CLIENT_UPSERTED".into(),
+                    entity: "CLIENT".into(),
+                    entity_id: Some(client.id),
+                    advertiser_id: None,
+                    actor: admin.clone(),
+                    date: 1,
+                    before: None,
+                    after: Some(serde_json::to_value(client).unwrap()),
+                })
+                .collect::<Vec<_>>()
+        );
+
+        let resp = api
+            .list_audit_entries(&format!("advertiser_id={}", advertiser.id))
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let entries = Vec::<AuditEntry>::from_resp(resp).await;
+        let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["ADVERTISER_UPSERTED", "ML_SCORE_UPSERTED"]);
+        assert_eq!(entries[1].entity_id, Some(clients[0].id));
+        assert_eq!(
+            entries[1].after,
+            Some(serde_json::to_value(&ml_score).unwrap())
+        );
+    })
+    .await
+}
+
+#[rstest]
+#[serial(time)]
+#[actix_rt::test]
+async fn time_advance() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.list_audit_entries("entity=TIME").await;
+        assert_status!(resp, StatusCode::OK);
+        let entries = Vec::<AuditEntry>::from_resp(resp).await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "TIME_ADVANCED");
+        assert_eq!(entries[0].before, Some(json!({"current_date": 1})));
+        assert_eq!(entries[0].after, Some(json!({"current_date": 2})));
+        assert_eq!(entries[0].date, 2);
+
+        let resp = api.list_audit_entries("from=1&to=1").await;
+        assert_json!(resp, StatusCode::OK, Vec<AuditEntry>, vec![]);
+
+        let resp = api.list_audit_entries("from=2").await;
+        assert_json!(resp, StatusCode::OK, Vec<AuditEntry>, entries);
+    })
+    .await
+}
+
+#[rstest]
+#[case::inverted_range("from=3&to=1")]
+#[case::zero_from("from=0")]
+#[case::unknown_entity("entity=SEGMENT")]
+#[case::negative_page("page=-1")]
+#[actix_rt::test]
+async fn list_audit_entries_invalid(#[case] query: &str) {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api.list_audit_entries(query).await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn list_audit_entries_forbidden() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.issue_api_key(advertiser.id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let owner_api = api.with_api_key(ApiKey::from_resp(resp).await.key.unwrap());
+
+        let resp = owner_api
+            .list_audit_entries(&format!("advertiser_id={}", advertiser.id))
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+    })
+    .await
+}
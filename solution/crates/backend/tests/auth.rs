@@ -0,0 +1,441 @@
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Advertiser, ApiKey, Campaign, ClickInfo, Client, ConversionInfo, CreateApiKey,
+        CreateCampaign, MLScore, Model, OptionalModel, Targeting, Time,
+    },
+};
+use rstest::rstest;
+use uuid::Uuid;
+
+mod common;
+
+#[actix_rt::test]
+async fn anonymous_request() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.anonymous().get_advertiser_by_id(advertiser.id).await;
+        assert_status!(resp, StatusCode::UNAUTHORIZED);
+
+        let resp = api
+            .anonymous()
+            .list_campaigns(advertiser.id, None, None)
+            .await;
+        assert_status!(resp, StatusCode::UNAUTHORIZED);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn unknown_route_anonymous() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let req = TestRequest::get().uri("/unknown").to_request();
+        let resp = api.anonymous().call(req).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn unknown_api_key() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .with_api_key("adk_unknown")
+            .get_advertiser_by_id(advertiser.id)
+            .await;
+        assert_status!(resp, StatusCode::UNAUTHORIZED);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn issue_api_key_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api.issue_api_key(Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn advertiser_api_key_ownership() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertisers = vec![Advertiser::new(), Advertiser::new()];
+        let resp = api.upsert_advertisers(advertisers.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.issue_api_key(advertisers[0].id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let api_key = ApiKey::from_resp(resp).await;
+        assert_eq!(api_key.advertiser_id, Some(advertisers[0].id));
+        assert_eq!(api_key.role, "ADVERTISER");
+        let owner_api = api.with_api_key(api_key.key.clone().unwrap());
+
+        let resp = owner_api.get_advertiser_by_id(advertisers[0].id).await;
+        assert_json!(resp, StatusCode::OK, Advertiser, advertisers[0].clone());
+
+        let resp = owner_api
+            .create_campaign(advertisers[0].id, CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = owner_api
+            .get_advertiser_campaigns_stats(advertisers[0].id)
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = owner_api.get_advertiser_by_id(advertisers[1].id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api
+            .create_campaign(advertisers[1].id, CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api
+            .get_advertiser_campaigns_stats(advertisers[1].id)
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api.issue_api_key(advertisers[1].id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api
+            .upsert_advertisers(vec![advertisers[1].clone()])
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn campaign_ownership() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertisers = vec![Advertiser::new(), Advertiser::new()];
+        let resp = api.upsert_advertisers(advertisers.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertisers[0].id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let owned = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(owned.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api
+            .create_campaign(advertisers[1].id, CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let foreign = Campaign::from_resp(resp).await;
+
+        let resp = api.issue_api_key(advertisers[0].id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let owner_api = api.with_api_key(ApiKey::from_resp(resp).await.key.unwrap());
+
+        let conversion_info = ConversionInfo {
+            client_id: client.id,
+            value: None,
+        };
+        let resp = owner_api
+            .record_ad_conversion(foreign.id, conversion_info.clone())
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api
+            .record_ad_conversion(owned.id, conversion_info)
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = owner_api.get_campaign_stats(owned.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = owner_api.get_campaign_daily_stats(owned.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = owner_api.get_campaign_stats(foreign.id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api.get_campaign_daily_stats(foreign.id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api.get_campaign_stats(Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn ml_score_ownership() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertisers = vec![Advertiser::new(), Advertiser::new()];
+        let resp = api.upsert_advertisers(advertisers.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.issue_api_key(advertisers[0].id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let owner_api = api.with_api_key(ApiKey::from_resp(resp).await.key.unwrap());
+
+        let ml_score = MLScore::builder()
+            .with_client_id(client.id)
+            .with_advertiser_id(advertisers[0].id)
+            .build();
+        let resp = owner_api.upsert_ml_score(ml_score).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let ml_score = MLScore::builder()
+            .with_client_id(client.id)
+            .with_advertiser_id(advertisers[1].id)
+            .build();
+        let resp = owner_api.upsert_ml_score(ml_score).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn revoke_api_key() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.issue_api_key(advertiser.id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let api_key = ApiKey::from_resp(resp).await;
+        let owner_api = api.with_api_key(api_key.key.clone().unwrap());
+
+        let resp = owner_api.list_api_keys(advertiser.id).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Vec<ApiKey>,
+            vec![ApiKey {
+                key: None,
+                ..api_key.clone()
+            }]
+        );
+
+        let resp = owner_api.revoke_api_key(advertiser.id, api_key.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = owner_api.get_advertiser_by_id(advertiser.id).await;
+        assert_status!(resp, StatusCode::UNAUTHORIZED);
+
+        let resp = api.revoke_api_key(advertiser.id, api_key.id).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[rstest]
+#[case::advertiser("ADVERTISER")]
+#[case::admin("ADMIN")]
+#[case::unknown("ROOT")]
+#[actix_rt::test]
+async fn issue_platform_api_key_invalid(#[case] role: &str) {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api
+            .issue_platform_api_key(CreateApiKey { role: role.into() })
+            .await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn client_app_role() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api
+            .issue_platform_api_key(CreateApiKey {
+                role: "CLIENT_APP".into(),
+            })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let api_key = ApiKey::from_resp(resp).await;
+        assert_eq!(api_key.advertiser_id, None);
+        let client_app_api = api.with_api_key(api_key.key.unwrap());
+
+        let resp = client_app_api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = client_app_api
+            .record_ad_click(campaign.id, click_info)
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let conversion_info = ConversionInfo {
+            client_id: client.id,
+            value: None,
+        };
+        let resp = client_app_api
+            .record_ad_conversion(campaign.id, conversion_info)
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = client_app_api.upsert_clients(vec![Client::new()]).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = client_app_api.get_advertiser_by_id(advertiser.id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn data_science_role() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .issue_platform_api_key(CreateApiKey {
+                role: "DATA_SCIENCE".into(),
+            })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let data_science_api = api.with_api_key(ApiKey::from_resp(resp).await.key.unwrap());
+
+        let ml_score = MLScore::builder()
+            .with_client_id(client.id)
+            .with_advertiser_id(advertiser.id)
+            .build();
+        let resp = data_science_api.upsert_ml_score(ml_score).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = data_science_api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = data_science_api.advance_day(Time { current_date: 2 }).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn operator_role() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api
+            .issue_platform_api_key(CreateApiKey {
+                role: "OPERATOR".into(),
+            })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let api_key = ApiKey::from_resp(resp).await;
+        let operator_api = api.with_api_key(api_key.key.clone().unwrap());
+
+        let client = Client::new();
+        let resp = operator_api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = operator_api
+            .upsert_advertisers(vec![advertiser.clone()])
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = operator_api.advance_day(Time { current_date: 2 }).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = operator_api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = operator_api
+            .issue_platform_api_key(CreateApiKey {
+                role: "OPERATOR".into(),
+            })
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = api.list_platform_api_keys().await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Vec<ApiKey>,
+            vec![ApiKey {
+                key: None,
+                ..api_key.clone()
+            }]
+        );
+
+        let resp = api.revoke_platform_api_key(api_key.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = operator_api.advance_day(Time { current_date: 3 }).await;
+        assert_status!(resp, StatusCode::UNAUTHORIZED);
+    })
+    .await
+}
@@ -0,0 +1,329 @@
+use actix_http::StatusCode;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Advertiser, ApiKey, BillingBalance, Campaign, ClickInfo, Client, CreateCampaign, Invoice,
+        InvoiceLine, LedgerEntry, Model, OptionalModel, Targeting, TopUp,
+    },
+};
+use rstest::rstest;
+use uuid::Uuid;
+
+mod common;
+
+#[rstest]
+#[case::zero(0.0)]
+#[case::negative(-10.0)]
+#[actix_rt::test]
+async fn top_up_balance_invalid(#[case] amount: f64) {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.top_up_balance(advertiser.id, TopUp { amount }).await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn top_up_balance_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api
+            .top_up_balance(Uuid::now_v7(), TopUp { amount: 10.0 })
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn top_up_balance_advertiser_forbidden() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.issue_api_key(advertiser.id).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let api_key = ApiKey::from_resp(resp).await;
+        let owner_api = api.with_api_key(api_key.key.unwrap());
+
+        let resp = owner_api
+            .top_up_balance(advertiser.id, TopUp { amount: 10.0 })
+            .await;
+        assert_status!(resp, StatusCode::FORBIDDEN);
+
+        let resp = owner_api.get_balance(advertiser.id).await;
+        assert_status!(resp, StatusCode::OK);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn balance_exhausted() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::builder().with_currency("USD").build();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_balance(advertiser.id).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            BillingBalance,
+            BillingBalance {
+                advertiser_id: advertiser.id,
+                prepaid: false,
+                balance: 0.0,
+                currency: "USD".into(),
+            }
+        );
+
+        let resp = api
+            .top_up_balance(advertiser.id, TopUp { amount: 1.5 })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_cost_per_impression(1.0)
+            .with_cost_per_click(0.5)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(clients[0].id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(clients[1].id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_balance(advertiser.id).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            BillingBalance,
+            BillingBalance {
+                advertiser_id: advertiser.id,
+                prepaid: true,
+                balance: 0.5,
+                currency: "USD".into(),
+            }
+        );
+
+        let resp = api.list_ledger_entries(advertiser.id).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Vec<LedgerEntry>,
+            vec![
+                LedgerEntry {
+                    id: Uuid::nil(),
+                    advertiser_id: advertiser.id,
+                    campaign_id: None,
+                    kind: "TOP_UP".into(),
+                    date: 1,
+                    unit_price: None,
+                    amount: 1.5,
+                },
+                LedgerEntry {
+                    id: Uuid::nil(),
+                    advertiser_id: advertiser.id,
+                    campaign_id: Some(campaign.id),
+                    kind: "IMPRESSION".into(),
+                    date: 1,
+                    unit_price: Some(1.0),
+                    amount: 1.0,
+                },
+            ]
+        );
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn click_does_not_overdraw_balance() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .top_up_balance(advertiser.id, TopUp { amount: 4.0 })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_cost_per_impression(1.0)
+            .with_cost_per_click(2.0)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(clients[0].id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        // Остатка 3.0 хватает на показ, но не на показ вместе с переходом
+        let resp = api.get_ad_for_client(clients[1].id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_balance(advertiser.id).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(BillingBalance::from_resp(resp).await.balance, 1.0);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn charges_before_top_up() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::builder().with_currency("USD").build();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_cost_per_impression(1.0)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .top_up_balance(advertiser.id, TopUp { amount: 10.0 })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_balance(advertiser.id).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            BillingBalance,
+            BillingBalance {
+                advertiser_id: advertiser.id,
+                prepaid: true,
+                balance: 9.0,
+                currency: "USD".into(),
+            }
+        );
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_invoice() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .top_up_balance(advertiser.id, TopUp { amount: 10.0 })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(1)
+            .with_impressions_limit(10)
+            .with_clicks_limit(10)
+            .with_cost_per_impression(0.1)
+            .with_cost_per_click(0.2)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        for client in &clients {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_invoice(advertiser.id, 1, 1).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Invoice,
+            Invoice {
+                advertiser_id: advertiser.id,
+                from: 1,
+                to: 1,
+                lines: vec![
+                    InvoiceLine {
+                        campaign_id: campaign.id,
+                        kind: "CLICK".into(),
+                        unit_price: 0.2,
+                        quantity: 1,
+                        amount: 0.2,
+                    },
+                    InvoiceLine {
+                        campaign_id: campaign.id,
+                        kind: "IMPRESSION".into(),
+                        unit_price: 0.1,
+                        quantity: 2,
+                        amount: 0.2,
+                    },
+                ],
+                total: 0.4,
+                top_ups: 10.0,
+                currency: "RUB".into(),
+            }
+        );
+
+        let resp = api.get_invoice(advertiser.id, 2, 1).await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
@@ -2,10 +2,11 @@ use crate::common::environment::with_test_environment;
 use actix_http::StatusCode;
 use common::
      models::{
-        builders::{Buildable, Builder}, Advertiser, Campaign, CampaignUpdate, CreateCampaign, Model, Targeting
+        builders::{Buildable, Builder}, Advertiser, Campaign, CampaignForecast, CampaignStateChange, Client, Time, CampaignUpdate, CreateCampaign, FrequencyCap, Model, OptionalModel, Targeting, AgeRange, TargetingRule
     };
 use rand::{rng, seq::IndexedRandom, Rng};
 use rstest::rstest;
+use serial_test::serial;
 use uuid::Uuid;
 use validator::ValidateLength;
 
@@ -54,6 +55,74 @@ mod common;
         .with_end_date(7)
         .build()
 )]
+#[case::total_budget(
+    CreateCampaign::builder()
+        .with_total_budget(-1.0)
+        .build()
+)]
+#[case::daily_budget_greater_than_total_budget(
+    CreateCampaign::builder()
+        .with_total_budget(10.0)
+        .with_daily_budget(100.0)
+        .build()
+)]
+#[case::targeting_locations_intersect(
+    CreateCampaign::builder()
+        .with_targeting(Targeting {
+            locations: Some(vec!["Moscow".into(), "Kazan".into()]),
+            excluded_locations: Some(vec!["Kazan".into()]),
+            ..Targeting::empty()
+        })
+        .build()
+)]
+#[case::targeting_age_range(
+    CreateCampaign::builder()
+        .with_targeting(Targeting {
+            age_ranges: Some(vec![AgeRange {
+                age_from: Some(30),
+                age_to: Some(20),
+            }]),
+            ..Targeting::empty()
+        })
+        .build()
+)]
+#[case::targeting_clients_intersect({
+    let client_id = Uuid::now_v7();
+    CreateCampaign::builder()
+        .with_targeting(Targeting {
+            allowed_clients: Some(vec![client_id]),
+            denied_clients: Some(vec![client_id]),
+            ..Targeting::empty()
+        })
+        .build()
+})]
+#[case::targeting_rule_without_value(
+    CreateCampaign::builder()
+        .with_targeting(Targeting {
+            rules: Some(vec![TargetingRule::new("device", "equals")]),
+            ..Targeting::empty()
+        })
+        .build()
+)]
+#[case::targeting_rule_unknown_operator(
+    CreateCampaign::builder()
+        .with_targeting(Targeting {
+            rules: Some(vec![TargetingRule {
+                value: Some("ios".into()),
+                ..TargetingRule::new("device", "like")
+            }]),
+            ..Targeting::empty()
+        })
+        .build()
+)]
+#[case::frequency_cap_per_day(
+    CreateCampaign::builder()
+        .with_frequency_cap(FrequencyCap {
+            per_day: Some(0),
+            ..FrequencyCap::empty()
+        })
+        .build()
+)]
 #[case::targeting_gender(
     CreateCampaign::builder()
         .with_targeting(
@@ -163,6 +232,80 @@ async fn create_campaign() {
     .await
 }
 
+#[actix_rt::test]
+async fn forecast_campaign_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api
+            .forecast_campaign(Uuid::now_v7(), CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+#[serial(time)]
+async fn forecast_campaign() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api.advance_day(Time::start()).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let clients = vec![
+            Client::builder().with_location("Moscow").build(),
+            Client::builder().with_location("Moscow").build(),
+            Client::builder().with_location("Moscow").build(),
+            Client::builder().with_location("Kazan").build(),
+        ];
+        let resp = api.upsert_clients(clients).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let moscow = Targeting {
+            location: Some("Moscow".into()),
+            ..Targeting::empty()
+        };
+        let kazan = Targeting {
+            location: Some("Kazan".into()),
+            ..Targeting::empty()
+        };
+        for (targeting, start_date) in [(moscow.clone(), 1), (kazan, 1), (moscow.clone(), 10)] {
+            let campaign = CreateCampaign::builder()
+                .with_targeting(targeting)
+                .with_start_date(start_date)
+                .with_end_date(20)
+                .build();
+            let resp = api.create_campaign(advertiser.id, campaign).await;
+            assert_status!(resp, StatusCode::CREATED);
+        }
+
+        let campaign = CreateCampaign::builder()
+            .with_targeting(moscow)
+            .build();
+        let resp = api
+            .forecast_campaign(advertiser.id, campaign)
+            .await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            CampaignForecast,
+            CampaignForecast {
+                clients_count: 3,
+                competing_campaigns_count: 1,
+                estimated_daily_impressions: 1.5,
+                estimated_daily_clicks: 0.0,
+            }
+        );
+    })
+    .await
+}
+
 #[actix_rt::test]
 async fn list_campaigns_non_existent_advertiser() {
     with_test_environment(|test_env| async move {
@@ -411,6 +554,20 @@ async fn get_campaign_by_id() {
         .with_end_date(Some(7))
         .build()
 )]
+#[case::daily_budget_greater_than_total_budget(
+    CampaignUpdate::builder()
+        .with_total_budget(Some(10.0))
+        .with_daily_budget(Some(100.0))
+        .build()
+)]
+#[case::frequency_cap_min_days_between(
+    CampaignUpdate::builder()
+        .with_frequency_cap(Some(FrequencyCap {
+            min_days_between: Some(0),
+            ..FrequencyCap::empty()
+        }))
+        .build()
+)]
 #[case::targeting_gender(
     CampaignUpdate::builder()
         .with_targeting(Some(
@@ -631,6 +788,233 @@ async fn delete_campaign_non_existent() {
     .await
 }
 
+#[actix_rt::test]
+async fn restore_campaign() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .create_campaign(advertiser.id, CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api
+            .restore_campaign(advertiser.id, campaign.id)
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let resp = api
+            .delete_campaign(advertiser.id, campaign.id)
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api
+            .delete_campaign(advertiser.id, campaign.id)
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        let resp = api
+            .get_campaign_by_id(advertiser.id, campaign.id)
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        let resp = api.list_campaigns(advertiser.id, None, None).await;
+        assert_json!(resp, StatusCode::OK, Vec<Campaign>, vec![]);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .restore_campaign(advertiser.id, campaign.id)
+            .await;
+        assert_json!(resp, StatusCode::OK, Campaign, campaign.clone());
+
+        let resp = api
+            .get_campaign_by_id(advertiser.id, campaign.id)
+            .await;
+        assert_json!(resp, StatusCode::OK, Campaign, campaign);
+    })
+    .await
+}
+
+#[actix_rt::test]
+#[serial(time)]
+async fn purge_deleted_campaign() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .create_campaign(advertiser.id, CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api
+            .delete_campaign(advertiser.id, campaign.id)
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        time.current_date += 30;
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        let resp = api
+            .restore_campaign(advertiser.id, campaign.id)
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn set_campaign_state_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for action in ["pause", "resume", "archive"] {
+            let resp = api
+                .set_campaign_state(advertiser.id, Uuid::now_v7(), action)
+                .await;
+            assert_status!(resp, StatusCode::NOT_FOUND);
+        }
+
+        let resp = api
+            .get_campaign_state_history(advertiser.id, Uuid::now_v7())
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+#[serial(time)]
+async fn set_campaign_state() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(10)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api
+            .set_campaign_state(advertiser.id, campaign.id, "pause")
+            .await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Campaign,
+            Campaign {
+                state: "PAUSED".into(),
+                ..campaign.clone()
+            }
+        );
+
+        let resp = api
+            .set_campaign_state(advertiser.id, campaign.id, "pause")
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api
+            .set_campaign_state(advertiser.id, campaign.id, "resume")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .set_campaign_state(advertiser.id, campaign.id, "archive")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        for action in ["pause", "resume", "archive"] {
+            let resp = api
+                .set_campaign_state(advertiser.id, campaign.id, action)
+                .await;
+            assert_status!(resp, StatusCode::CONFLICT);
+        }
+
+        let resp = api
+            .update_campaign(advertiser.id, campaign.id, CampaignUpdate::empty())
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .get_campaign_state_history(advertiser.id, campaign.id)
+            .await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            Vec<CampaignStateChange>,
+            [("ACTIVE", 1), ("PAUSED", 1), ("ACTIVE", 2), ("ARCHIVED", 2)]
+                .into_iter()
+                .map(|(state, date)| CampaignStateChange {
+                    state: state.into(),
+                    date,
+                })
+                .collect::<Vec<_>>()
+        );
+    })
+    .await
+}
+
 #[actix_rt::test]
 async fn delete_campaign() {
     with_test_environment(|test_env| async move {
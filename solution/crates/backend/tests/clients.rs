@@ -34,6 +34,11 @@ use validator::ValidateLength;
         .with_location("питон")
         .build()
 )]
+#[case::attribute_name(
+    Client::builder()
+        .with_attribute("Device Type", "ios")
+        .build()
+)]
 #[actix_rt::test]
 async fn clients_upsertion_invalid(#[case] client: Client) {
     with_test_environment(|test_env| async move {
@@ -60,6 +65,27 @@ async fn clients_upsertion() {
     .await
 }
 
+#[actix_rt::test]
+async fn clients_upsertion_with_attributes() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::builder()
+            .with_attribute("device", "ios")
+            .with_attribute("subscription_tier", 2.5)
+            .with_attribute("is_premium", true)
+            .with_attribute("interests", vec!["sport", "music"])
+            .build();
+
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_json!(resp, StatusCode::CREATED, Vec<Client>, vec![client.clone()]);
+
+        let resp = api.get_client_by_id(client.id).await;
+        assert_json!(resp, StatusCode::OK, Client, client);
+    })
+    .await
+}
+
 #[actix_rt::test]
 async fn clients_upsertion_with_duplicates() {
     with_test_environment(|test_env| async move {
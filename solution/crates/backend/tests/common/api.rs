@@ -2,36 +2,115 @@ use std::rc::Rc;
 
 use actix_web::{
     dev::ServiceResponse,
+    http::header::{HeaderValue, AUTHORIZATION},
+    middleware::{NormalizePath, TrailingSlash},
     test::{self, TestRequest},
-    App,
+    App, HttpMessage,
 };
-use backend::BackendConfig;
+use backend::{config, models::IDEMPOTENCY_KEY, utils::rate_limit::RateLimiter, BackendConfig};
 use utoipa_actix_web::AppExt;
 
 use super::{
     environment::LocalService,
-    models::{Advertiser, CampaignUpdate, ClickInfo, Client, CreateCampaign, MLScore, Time},
+    models::{
+        Advertiser, CampaignUpdate, ClickInfo, Client, ConversionInfo, CreateApiKey,
+        CreateCampaign, CreateCreative, CreateSegment, MLScore, SegmentUpdate, Time, TopUp,
+    },
 };
 
 #[derive(Clone)]
 pub struct Api {
     pub test_app: Rc<dyn LocalService>,
+    pub api_key: Option<String>,
+    pub idempotency_key: Option<String>,
 }
 
 #[allow(dead_code)]
 impl Api {
+    /// Клиент API приложения без ограничения частоты запросов.
     pub async fn build(solution_config: BackendConfig) -> Self {
+        let solution_config = BackendConfig {
+            rate_limiter: RateLimiter::new(None, None, None),
+            ..solution_config
+        };
+        let app = App::new()
+            .into_utoipa_app()
+            .configure(solution_config.build())
+            .into_app();
+
+        let test_app = Rc::new(test::init_service(app).await);
+
+        Self {
+            test_app,
+            api_key: Some(config::ADMIN_API_KEY.clone()),
+            idempotency_key: None,
+        }
+    }
+
+    /// Клиент API приложения с указанным ограничителем частоты запросов.
+    /// Пути запросов нормализуются так же, как в `main`.
+    pub async fn build_rate_limited(
+        solution_config: BackendConfig,
+        rate_limiter: RateLimiter,
+    ) -> Self {
+        let solution_config = BackendConfig {
+            rate_limiter,
+            ..solution_config
+        };
         let app = App::new()
+            .wrap(NormalizePath::new(TrailingSlash::MergeOnly))
             .into_utoipa_app()
-            .configure(solution_config.clone().build())
+            .configure(solution_config.build())
             .into_app();
 
         let test_app = Rc::new(test::init_service(app).await);
 
-        Self { test_app }
+        Self {
+            test_app,
+            api_key: Some(config::ADMIN_API_KEY.clone()),
+            idempotency_key: None,
+        }
+    }
+
+    /// Клиент API, отправляющий запросы с указанным ключом.
+    pub fn with_api_key(&self, api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: Some(api_key.into()),
+            ..self.clone()
+        }
     }
 
-    pub async fn call(&self, req: actix_http::Request) -> ServiceResponse {
+    /// Клиент API, отправляющий запросы с указанным ключом идемпотентности.
+    pub fn with_idempotency_key(&self, idempotency_key: impl Into<String>) -> Self {
+        Self {
+            idempotency_key: Some(idempotency_key.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Клиент API, отправляющий запросы без ключа.
+    pub fn anonymous(&self) -> Self {
+        Self {
+            api_key: None,
+            ..self.clone()
+        }
+    }
+
+    pub async fn call(&self, mut req: actix_http::Request) -> ServiceResponse {
+        if let Some(api_key) = &self.api_key {
+            if !req.headers().contains_key(AUTHORIZATION) {
+                req.headers_mut().insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {api_key}")).unwrap(),
+                );
+            }
+        }
+        if let Some(idempotency_key) = &self.idempotency_key {
+            req.headers_mut().insert(
+                IDEMPOTENCY_KEY,
+                HeaderValue::from_str(idempotency_key).unwrap(),
+            );
+        }
         self.test_app.call(req).await.unwrap()
     }
 
@@ -85,6 +164,21 @@ impl Api {
         self.call(req).await
     }
 
+    pub async fn forecast_campaign(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign: CreateCampaign,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/forecast",
+                advertiser_id.into()
+            ))
+            .set_json(campaign)
+            .to_request();
+        self.call(req).await
+    }
+
     pub async fn list_campaigns(
         &self,
         advertiser_id: impl Into<String>,
@@ -155,6 +249,249 @@ impl Api {
         self.call(req).await
     }
 
+    pub async fn restore_campaign(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/restore",
+                advertiser_id.into(),
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn set_campaign_state(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+        action: &str,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/{action}",
+                advertiser_id.into(),
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_campaign_state_history(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/state-history",
+                advertiser_id.into(),
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn create_creative(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+        creative: CreateCreative,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/creatives",
+                advertiser_id.into(),
+                campaign_id.into()
+            ))
+            .set_json(creative)
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn list_creatives(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/creatives",
+                advertiser_id.into(),
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_creative_by_id(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+        creative_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/creatives/{}",
+                advertiser_id.into(),
+                campaign_id.into(),
+                creative_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn delete_creative(
+        &self,
+        advertiser_id: impl Into<String>,
+        campaign_id: impl Into<String>,
+        creative_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::delete()
+            .uri(&format!(
+                "/advertisers/{}/campaigns/{}/creatives/{}",
+                advertiser_id.into(),
+                campaign_id.into(),
+                creative_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn create_segment(
+        &self,
+        advertiser_id: impl Into<String>,
+        segment: CreateSegment,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!("/advertisers/{}/segments", advertiser_id.into()))
+            .set_json(segment)
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn list_segments(&self, advertiser_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!("/advertisers/{}/segments", advertiser_id.into()))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_segment_by_id(
+        &self,
+        advertiser_id: impl Into<String>,
+        segment_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/segments/{}",
+                advertiser_id.into(),
+                segment_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn update_segment(
+        &self,
+        advertiser_id: impl Into<String>,
+        segment_id: impl Into<String>,
+        segment_update: SegmentUpdate,
+    ) -> ServiceResponse {
+        let req = TestRequest::put()
+            .uri(&format!(
+                "/advertisers/{}/segments/{}",
+                advertiser_id.into(),
+                segment_id.into()
+            ))
+            .set_json(segment_update)
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn delete_segment(
+        &self,
+        advertiser_id: impl Into<String>,
+        segment_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::delete()
+            .uri(&format!(
+                "/advertisers/{}/segments/{}",
+                advertiser_id.into(),
+                segment_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_segment_reach(
+        &self,
+        advertiser_id: impl Into<String>,
+        segment_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/segments/{}/reach",
+                advertiser_id.into(),
+                segment_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn top_up_balance(
+        &self,
+        advertiser_id: impl Into<String>,
+        top_up: TopUp,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!(
+                "/advertisers/{}/billing/top-ups",
+                advertiser_id.into()
+            ))
+            .set_json(top_up)
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_balance(&self, advertiser_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/billing/balance",
+                advertiser_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn list_ledger_entries(&self, advertiser_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/billing/ledger",
+                advertiser_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_invoice(
+        &self,
+        advertiser_id: impl Into<String>,
+        from: i64,
+        to: i64,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/advertisers/{}/billing/invoice?from={from}&to={to}",
+                advertiser_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
     pub async fn get_ad_for_client(&self, client_id: impl Into<String>) -> ServiceResponse {
         let req = TestRequest::get()
             .uri(&format!("/ads?client_id={}", client_id.into()))
@@ -162,6 +499,28 @@ impl Api {
         self.call(req).await
     }
 
+    pub async fn get_ads_for_client(
+        &self,
+        client_id: impl Into<String>,
+        slots: u32,
+        unique_advertisers: bool,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/ads?client_id={}&slots={slots}&unique_advertisers={unique_advertisers}",
+                client_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn explain_ad_for_client(&self, client_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!("/ads/explain?client_id={}", client_id.into()))
+            .to_request();
+        self.call(req).await
+    }
+
     pub async fn record_ad_click(
         &self,
         ad_id: impl Into<String>,
@@ -174,6 +533,18 @@ impl Api {
         self.call(req).await
     }
 
+    pub async fn record_ad_conversion(
+        &self,
+        ad_id: impl Into<String>,
+        conversion_info: ConversionInfo,
+    ) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!("/ads/{}/conversion", ad_id.into()))
+            .set_json(conversion_info)
+            .to_request();
+        self.call(req).await
+    }
+
     pub async fn get_campaign_stats(&self, campaign_id: impl Into<String>) -> ServiceResponse {
         let req = TestRequest::get()
             .uri(&format!("/statistics/campaigns/{}", campaign_id.into()))
@@ -181,6 +552,19 @@ impl Api {
         self.call(req).await
     }
 
+    pub async fn get_campaign_creatives_stats(
+        &self,
+        campaign_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/campaigns/{}/creatives",
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
     pub async fn get_advertiser_campaigns_stats(
         &self,
         advertiser_id: impl Into<String>,
@@ -217,6 +601,95 @@ impl Api {
         self.call(req).await
     }
 
+    pub async fn get_campaign_daily_stats_with_query(
+        &self,
+        campaign_id: impl Into<String>,
+        query: &str,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/campaigns/{}/daily?{query}",
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_advertiser_daily_stats_with_query(
+        &self,
+        advertiser_id: impl Into<String>,
+        query: &str,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/advertisers/{}/daily?{query}",
+                advertiser_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_campaign_audience_stats(
+        &self,
+        campaign_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/campaigns/{}/audience",
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn get_advertiser_audience_stats(
+        &self,
+        advertiser_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/advertisers/{}/audience",
+                advertiser_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn export_campaign_daily_stats(
+        &self,
+        campaign_id: impl Into<String>,
+        format: &str,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/campaigns/{}/export/daily?format={format}",
+                campaign_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn export_advertiser_interactions(
+        &self,
+        advertiser_id: impl Into<String>,
+        format: &str,
+    ) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!(
+                "/statistics/advertisers/{}/export/interactions?format={format}",
+                advertiser_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn rebuild_stats(&self, query: &str) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!("/statistics/rebuild?{query}"))
+            .to_request();
+        self.call(req).await
+    }
+
     pub async fn advance_day(&self, new_time: Time) -> ServiceResponse {
         let req = TestRequest::post()
             .uri("/time/advance")
@@ -224,4 +697,60 @@ impl Api {
             .to_request();
         self.call(req).await
     }
+
+    pub async fn issue_api_key(&self, advertiser_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri(&format!("/advertisers/{}/api-keys", advertiser_id.into()))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn list_api_keys(&self, advertiser_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!("/advertisers/{}/api-keys", advertiser_id.into()))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn revoke_api_key(
+        &self,
+        advertiser_id: impl Into<String>,
+        api_key_id: impl Into<String>,
+    ) -> ServiceResponse {
+        let req = TestRequest::delete()
+            .uri(&format!(
+                "/advertisers/{}/api-keys/{}",
+                advertiser_id.into(),
+                api_key_id.into()
+            ))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn issue_platform_api_key(&self, create_api_key: CreateApiKey) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri("/api-keys")
+            .set_json(create_api_key)
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn list_platform_api_keys(&self) -> ServiceResponse {
+        let req = TestRequest::get().uri("/api-keys").to_request();
+        self.call(req).await
+    }
+
+    pub async fn revoke_platform_api_key(&self, api_key_id: impl Into<String>) -> ServiceResponse {
+        let req = TestRequest::delete()
+            .uri(&format!("/api-keys/{}", api_key_id.into()))
+            .to_request();
+        self.call(req).await
+    }
+
+    pub async fn list_audit_entries(&self, query: &str) -> ServiceResponse {
+        let req = TestRequest::get()
+            .uri(&format!("/audit?{query}"))
+            .to_request();
+        self.call(req).await
+    }
 }
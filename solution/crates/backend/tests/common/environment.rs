@@ -1,9 +1,15 @@
-use std::future::Future;
+use std::{env, future::Future, sync::Once};
 
 use actix_web::dev::ServiceResponse;
 use backend::{app_setup, models::dto::Time};
 
-use super::{api::Api, database::TemporaryDatabase, file_host::TemporaryFileHost};
+use super::{
+    api::Api,
+    database::{generate_random_name, TemporaryDatabase},
+    file_host::TemporaryFileHost,
+};
+
+static ADMIN_API_KEY: Once = Once::new();
 
 pub async fn with_test_environment<Fut>(f: impl FnOnce(TestEnvironment) -> Fut)
 where
@@ -26,6 +32,13 @@ pub struct TestEnvironment {
 
 impl TestEnvironment {
     pub async fn build() -> Self {
+        // По умолчанию доступ администратора отключён, тестам нужен ключ
+        ADMIN_API_KEY.call_once(|| {
+            if env::var("ADMIN_API_KEY").is_err() {
+                env::set_var("ADMIN_API_KEY", generate_random_name("admin"));
+            }
+        });
+
         let db = TemporaryDatabase::create().await;
         let file_host = TemporaryFileHost::create().await;
         let config = app_setup(db.surreal.clone(), file_host.minio.clone());
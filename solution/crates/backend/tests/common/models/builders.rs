@@ -3,12 +3,16 @@ use backend::{
     utils::RoundToDigits,
 };
 use rand::{distr::uniform::SampleUniform, random_bool, rng, seq::IndexedRandom, Rng};
-use std::sync::atomic::{AtomicU32, Ordering};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU32, Ordering},
+};
 use uuid::Uuid;
 
 use super::{
-    Advertiser, Campaign, CampaignUpdate, ClickInfo, Client, CreateCampaign, MLScore, Stats,
-    Targeting,
+    Advertiser, Campaign, CampaignUpdate, ClickInfo, Client, CreateCampaign, FrequencyCap, MLScore,
+    OptionalModel, Stats, Targeting,
 };
 
 static CLIENT_COUNTER: AtomicU32 = AtomicU32::new(0);
@@ -37,6 +41,7 @@ pub struct ClientBuilder {
     age: Option<i64>,
     location: Option<String>,
     gender: Option<String>,
+    attributes: BTreeMap<String, Value>,
 }
 impl Builder<Client> for ClientBuilder {
     fn new() -> Self {
@@ -46,6 +51,7 @@ impl Builder<Client> for ClientBuilder {
             age: None,
             location: None,
             gender: None,
+            attributes: BTreeMap::new(),
         }
     }
 
@@ -68,6 +74,7 @@ impl Builder<Client> for ClientBuilder {
                 }
                 .to_string()
             }),
+            attributes: self.attributes,
         }
     }
 }
@@ -96,17 +103,24 @@ impl ClientBuilder {
         self.gender = Some(gender.to_string());
         self
     }
+
+    pub fn with_attribute(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.attributes.insert(name.into(), value.into());
+        self
+    }
 }
 
 pub struct AdvertiserBuilder {
     id: Option<Uuid>,
     name: Option<String>,
+    currency: Option<String>,
 }
 impl Builder<Advertiser> for AdvertiserBuilder {
     fn new() -> Self {
         Self {
             id: None,
             name: None,
+            currency: None,
         }
     }
 
@@ -118,6 +132,7 @@ impl Builder<Advertiser> for AdvertiserBuilder {
             name: self
                 .name
                 .unwrap_or_else(|| format!("Advertiser_{}", counter)),
+            currency: self.currency.unwrap_or_else(|| "RUB".into()),
         }
     }
 }
@@ -131,6 +146,11 @@ impl AdvertiserBuilder {
         self.name = Some(name.into());
         self
     }
+
+    pub fn with_currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = Some(currency.into());
+        self
+    }
 }
 
 pub struct MLScoreBuilder {
@@ -179,6 +199,7 @@ impl MLScoreBuilder {
 pub struct CampaignBuilder {
     id: Option<Uuid>,
     advertiser_id: Option<Uuid>,
+    state: Option<String>,
     inner: Option<CreateCampaign>,
 }
 impl Builder<Campaign> for CampaignBuilder {
@@ -186,6 +207,7 @@ impl Builder<Campaign> for CampaignBuilder {
         Self {
             id: None,
             advertiser_id: None,
+            state: None,
             inner: None,
         }
     }
@@ -194,6 +216,7 @@ impl Builder<Campaign> for CampaignBuilder {
         Campaign {
             id: self.id.unwrap_or_else(Uuid::now_v7),
             advertiser_id: self.advertiser_id.unwrap_or_else(Uuid::now_v7),
+            state: self.state.unwrap_or_else(|| "ACTIVE".into()),
             inner: self.inner.unwrap_or_else(CreateCampaign::new),
         }
     }
@@ -209,6 +232,11 @@ impl CampaignBuilder {
         self
     }
 
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
     pub fn with_inner(mut self, inner: CreateCampaign) -> Self {
         self.inner = Some(inner);
         self
@@ -220,11 +248,16 @@ pub struct CreateCampaignBuilder {
     clicks_limit: Option<i64>,
     cost_per_impression: Option<f64>,
     cost_per_click: Option<f64>,
+    cost_per_conversion: Option<f64>,
+    total_budget: Option<f64>,
+    daily_budget: Option<f64>,
     ad_title: Option<String>,
     ad_text: Option<String>,
     start_date: Option<i64>,
     end_date: Option<i64>,
     targeting: Option<Targeting>,
+    segment_id: Option<Uuid>,
+    frequency_cap: Option<FrequencyCap>,
 }
 impl Builder<CreateCampaign> for CreateCampaignBuilder {
     fn new() -> Self {
@@ -233,11 +266,16 @@ impl Builder<CreateCampaign> for CreateCampaignBuilder {
             clicks_limit: None,
             cost_per_impression: None,
             cost_per_click: None,
+            cost_per_conversion: None,
+            total_budget: None,
+            daily_budget: None,
             ad_title: None,
             ad_text: None,
             start_date: None,
             end_date: None,
             targeting: None,
+            segment_id: None,
+            frequency_cap: None,
         }
     }
 
@@ -267,6 +305,9 @@ impl Builder<CreateCampaign> for CreateCampaignBuilder {
                 .cost_per_click
                 .unwrap_or_else(|| rng.random_range(1.0..100.0))
                 .round_to_digits(2),
+            cost_per_conversion: self.cost_per_conversion.map(|c| c.round_to_digits(2)),
+            total_budget: self.total_budget.map(|b| b.round_to_digits(2)),
+            daily_budget: self.daily_budget.map(|b| b.round_to_digits(2)),
             ad_title: self
                 .ad_title
                 .unwrap_or_else(|| format!("Campaign_{}", counter)),
@@ -275,7 +316,12 @@ impl Builder<CreateCampaign> for CreateCampaignBuilder {
                 .unwrap_or_else(|| format!("Ad content for Campaign_{}", counter)),
             start_date,
             end_date,
-            targeting: self.targeting.unwrap_or_else(Targeting::new),
+            targeting: match self.segment_id {
+                Some(_) => self.targeting.unwrap_or_else(Targeting::empty),
+                None => self.targeting.unwrap_or_else(Targeting::new),
+            },
+            segment_id: self.segment_id,
+            frequency_cap: self.frequency_cap,
         }
     }
 }
@@ -300,6 +346,21 @@ impl CreateCampaignBuilder {
         self
     }
 
+    pub fn with_cost_per_conversion(mut self, cost_per_conversion: f64) -> Self {
+        self.cost_per_conversion = Some(cost_per_conversion);
+        self
+    }
+
+    pub fn with_total_budget(mut self, total_budget: f64) -> Self {
+        self.total_budget = Some(total_budget);
+        self
+    }
+
+    pub fn with_daily_budget(mut self, daily_budget: f64) -> Self {
+        self.daily_budget = Some(daily_budget);
+        self
+    }
+
     pub fn with_ad_title(mut self, ad_title: impl Into<String>) -> Self {
         self.ad_title = Some(ad_title.into());
         self
@@ -324,6 +385,16 @@ impl CreateCampaignBuilder {
         self.targeting = Some(targeting);
         self
     }
+
+    pub fn with_segment_id(mut self, segment_id: Uuid) -> Self {
+        self.segment_id = Some(segment_id);
+        self
+    }
+
+    pub fn with_frequency_cap(mut self, frequency_cap: FrequencyCap) -> Self {
+        self.frequency_cap = Some(frequency_cap);
+        self
+    }
 }
 
 pub struct CampaignUpdateBuilder {
@@ -331,11 +402,16 @@ pub struct CampaignUpdateBuilder {
     clicks_limit: Option<Option<i64>>,
     cost_per_impression: Option<Option<f64>>,
     cost_per_click: Option<Option<f64>>,
+    cost_per_conversion: Option<Option<f64>>,
+    total_budget: Option<Option<f64>>,
+    daily_budget: Option<Option<f64>>,
     ad_title: Option<Option<String>>,
     ad_text: Option<Option<String>>,
     start_date: Option<Option<i64>>,
     end_date: Option<Option<i64>>,
     targeting: Option<Option<Targeting>>,
+    segment_id: Option<Uuid>,
+    frequency_cap: Option<Option<FrequencyCap>>,
 }
 impl Builder<CampaignUpdate> for CampaignUpdateBuilder {
     fn new() -> Self {
@@ -344,11 +420,16 @@ impl Builder<CampaignUpdate> for CampaignUpdateBuilder {
             clicks_limit: None,
             cost_per_impression: None,
             cost_per_click: None,
+            cost_per_conversion: None,
+            total_budget: None,
+            daily_budget: None,
             ad_title: None,
             ad_text: None,
             start_date: None,
             end_date: None,
             targeting: None,
+            segment_id: None,
+            frequency_cap: None,
         }
     }
 
@@ -378,6 +459,12 @@ impl Builder<CampaignUpdate> for CampaignUpdateBuilder {
                 .cost_per_click
                 .unwrap_or_else(|| return_randomly(|| rng.random_range(1.0..100.0)))
                 .map(|c| c.round_to_digits(2)),
+            cost_per_conversion: self
+                .cost_per_conversion
+                .flatten()
+                .map(|c| c.round_to_digits(2)),
+            total_budget: self.total_budget.flatten().map(|b| b.round_to_digits(2)),
+            daily_budget: self.daily_budget.flatten().map(|b| b.round_to_digits(2)),
             ad_title: self
                 .ad_title
                 .unwrap_or_else(|| return_randomly(|| format!("Updated Campaign_{}", counter))),
@@ -386,9 +473,14 @@ impl Builder<CampaignUpdate> for CampaignUpdateBuilder {
             }),
             start_date,
             end_date,
-            targeting: self
-                .targeting
-                .unwrap_or_else(|| return_randomly(Targeting::new)),
+            targeting: match self.segment_id {
+                Some(_) => self.targeting.flatten(),
+                None => self
+                    .targeting
+                    .unwrap_or_else(|| return_randomly(Targeting::new)),
+            },
+            segment_id: self.segment_id,
+            frequency_cap: self.frequency_cap.flatten(),
         }
     }
 }
@@ -413,6 +505,16 @@ impl CampaignUpdateBuilder {
         self
     }
 
+    pub fn with_total_budget(mut self, total_budget: Option<f64>) -> Self {
+        self.total_budget = Some(total_budget);
+        self
+    }
+
+    pub fn with_daily_budget(mut self, daily_budget: Option<f64>) -> Self {
+        self.daily_budget = Some(daily_budget);
+        self
+    }
+
     pub fn with_ad_title(mut self, ad_title: Option<impl Into<String>>) -> Self {
         self.ad_title = Some(ad_title.map(|t| t.into()));
         self
@@ -437,6 +539,16 @@ impl CampaignUpdateBuilder {
         self.targeting = Some(targeting);
         self
     }
+
+    pub fn with_segment_id(mut self, segment_id: Uuid) -> Self {
+        self.segment_id = Some(segment_id);
+        self
+    }
+
+    pub fn with_frequency_cap(mut self, frequency_cap: Option<FrequencyCap>) -> Self {
+        self.frequency_cap = Some(frequency_cap);
+        self
+    }
 }
 
 pub struct TargetingBuilder {
@@ -472,6 +584,12 @@ impl Builder<Targeting> for TargetingBuilder {
             location: self
                 .location
                 .unwrap_or_else(|| return_randomly(|| generate_location(LocationType::City))),
+            locations: None,
+            excluded_locations: None,
+            age_ranges: None,
+            allowed_clients: None,
+            denied_clients: None,
+            rules: None,
         }
     }
 }
@@ -551,7 +669,11 @@ impl Builder<Stats> for StatsBuilder {
             conversion: (clicks_count as f64 / impressions_count as f64) as f32 * 100.0,
             spent_impressions,
             spent_clicks,
+            spent_conversions: 0.0,
             spent_total: spent_impressions + spent_clicks,
+            conversions_count: 0,
+            conversion_value: 0.0,
+            cost_per_acquisition: 0.0,
         }
     }
 }
@@ -5,8 +5,8 @@ use builders::{
 };
 use derivative::Derivative;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::from_value;
-use std::{fmt::Debug, future::Future};
+use serde_json::{from_value, Value};
+use std::{collections::BTreeMap, fmt::Debug, future::Future};
 use uuid::Uuid;
 
 pub mod builders;
@@ -45,6 +45,9 @@ pub struct Client {
     pub age: i64,
     pub location: String,
     pub gender: String,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, Value>,
 }
 impl Model for Client {}
 impl Buildable<ClientBuilder, Self> for Client {}
@@ -54,6 +57,7 @@ pub struct Advertiser {
     #[serde(rename = "advertiser_id")]
     pub id: Uuid,
     pub name: String,
+    pub currency: String,
 }
 impl Model for Advertiser {}
 impl Buildable<AdvertiserBuilder, Self> for Advertiser {}
@@ -74,6 +78,7 @@ pub struct Campaign {
     #[derivative(PartialEq = "ignore")]
     pub id: Uuid,
     pub advertiser_id: Uuid,
+    pub state: String,
 
     #[serde(flatten)]
     pub inner: CreateCampaign,
@@ -94,6 +99,15 @@ impl Campaign {
         if let Some(cost_per_click) = update.cost_per_click {
             self.inner.cost_per_click = cost_per_click;
         }
+        if let Some(cost_per_conversion) = update.cost_per_conversion {
+            self.inner.cost_per_conversion = Some(cost_per_conversion);
+        }
+        if let Some(total_budget) = update.total_budget {
+            self.inner.total_budget = Some(total_budget);
+        }
+        if let Some(daily_budget) = update.daily_budget {
+            self.inner.daily_budget = Some(daily_budget);
+        }
         if let Some(ad_title) = update.ad_title {
             self.inner.ad_title = ad_title;
         }
@@ -109,6 +123,13 @@ impl Campaign {
         if let Some(targeting) = update.targeting {
             self.inner.targeting = targeting;
         }
+        if let Some(segment_id) = update.segment_id {
+            self.inner.targeting = Targeting::empty();
+            self.inner.segment_id = Some(segment_id);
+        }
+        if let Some(frequency_cap) = update.frequency_cap {
+            self.inner.frequency_cap = Some(frequency_cap);
+        }
         self
     }
 }
@@ -119,11 +140,27 @@ pub struct CreateCampaign {
     pub clicks_limit: i64,
     pub cost_per_impression: f64,
     pub cost_per_click: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_per_conversion: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_budget: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_budget: Option<f64>,
+
     pub ad_title: String,
     pub ad_text: String,
     pub start_date: i64,
     pub end_date: i64,
     pub targeting: Targeting,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment_id: Option<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_cap: Option<FrequencyCap>,
 }
 impl Model for CreateCampaign {}
 impl Buildable<CreateCampaignBuilder, Self> for CreateCampaign {}
@@ -142,6 +179,15 @@ pub struct CampaignUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cost_per_click: Option<f64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_per_conversion: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_budget: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_budget: Option<f64>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ad_title: Option<String>,
 
@@ -156,6 +202,12 @@ pub struct CampaignUpdate {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub targeting: Option<Targeting>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment_id: Option<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_cap: Option<FrequencyCap>,
 }
 impl Model for CampaignUpdate {}
 impl Buildable<CampaignUpdateBuilder, Self> for CampaignUpdate {}
@@ -166,11 +218,16 @@ impl OptionalModel for CampaignUpdate {
             clicks_limit: None,
             cost_per_impression: None,
             cost_per_click: None,
+            cost_per_conversion: None,
+            total_budget: None,
+            daily_budget: None,
             ad_title: None,
             ad_text: None,
             start_date: None,
             end_date: None,
             targeting: None,
+            segment_id: None,
+            frequency_cap: None,
         }
     }
 }
@@ -188,6 +245,24 @@ pub struct Targeting {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locations: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_locations: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_ranges: Option<Vec<AgeRange>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_clients: Option<Vec<Uuid>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denied_clients: Option<Vec<Uuid>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<TargetingRule>>,
 }
 impl Model for Targeting {}
 impl Buildable<TargetingBuilder, Self> for Targeting {}
@@ -198,6 +273,268 @@ impl OptionalModel for Targeting {
             age_from: None,
             age_to: None,
             location: None,
+            locations: None,
+            excluded_locations: None,
+            age_ranges: None,
+            allowed_clients: None,
+            denied_clients: None,
+            rules: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CampaignStateChange {
+    pub state: String,
+    pub date: i64,
+}
+impl Model for CampaignStateChange {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CampaignForecast {
+    pub clients_count: i64,
+    pub competing_campaigns_count: i64,
+    pub estimated_daily_impressions: f64,
+    pub estimated_daily_clicks: f64,
+}
+impl Model for CampaignForecast {}
+
+#[derive(Deserialize, Serialize, Derivative, Clone, Debug)]
+#[derivative(PartialEq)]
+pub struct Segment {
+    #[serde(rename = "segment_id")]
+    #[derivative(PartialEq = "ignore")]
+    pub id: Uuid,
+    pub advertiser_id: Uuid,
+
+    #[serde(flatten)]
+    pub inner: CreateSegment,
+}
+impl Model for Segment {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CreateSegment {
+    pub name: String,
+    pub targeting: Targeting,
+}
+impl Model for CreateSegment {}
+impl CreateSegment {
+    pub fn new(name: impl Into<String>, targeting: Targeting) -> Self {
+        Self {
+            name: name.into(),
+            targeting,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct SegmentUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targeting: Option<Targeting>,
+}
+impl Model for SegmentUpdate {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct SegmentReach {
+    pub segment_id: Uuid,
+    pub clients_count: i64,
+}
+impl Model for SegmentReach {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Copy, Debug)]
+pub struct TopUp {
+    pub amount: f64,
+}
+impl Model for TopUp {}
+
+#[derive(Deserialize, Serialize, Derivative, Clone, Debug)]
+#[derivative(PartialEq)]
+pub struct LedgerEntry {
+    #[serde(rename = "ledger_entry_id")]
+    #[derivative(PartialEq = "ignore")]
+    pub id: Uuid,
+    pub advertiser_id: Uuid,
+    pub campaign_id: Option<Uuid>,
+    pub kind: String,
+    pub date: i64,
+    pub unit_price: Option<f64>,
+    pub amount: f64,
+}
+impl Model for LedgerEntry {}
+
+#[derive(Deserialize, Serialize, Derivative, Clone, Debug)]
+#[derivative(PartialEq)]
+pub struct ApiKey {
+    #[serde(rename = "api_key_id")]
+    #[derivative(PartialEq = "ignore")]
+    pub id: Uuid,
+    pub advertiser_id: Option<Uuid>,
+    pub role: String,
+    pub date: i64,
+    #[derivative(PartialEq = "ignore")]
+    pub key: Option<String>,
+}
+impl Model for ApiKey {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CreateApiKey {
+    pub role: String,
+}
+impl Model for CreateApiKey {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct AuditActor {
+    pub role: String,
+    pub advertiser_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Serialize, Derivative, Clone, Debug)]
+#[derivative(PartialEq)]
+pub struct AuditEntry {
+    #[derivative(PartialEq = "ignore")]
+    pub audit_entry_id: Uuid,
+    pub action: String,
+    pub entity: String,
+    pub entity_id: Option<Uuid>,
+    pub advertiser_id: Option<Uuid>,
+    pub actor: AuditActor,
+    pub date: i64,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+impl Model for AuditEntry {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct BillingBalance {
+    pub advertiser_id: Uuid,
+    pub prepaid: bool,
+    pub balance: f64,
+    pub currency: String,
+}
+impl Model for BillingBalance {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct InvoiceLine {
+    pub campaign_id: Uuid,
+    pub kind: String,
+    pub unit_price: f64,
+    pub quantity: i64,
+    pub amount: f64,
+}
+impl Model for InvoiceLine {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct Invoice {
+    pub advertiser_id: Uuid,
+    pub from: i64,
+    pub to: i64,
+    pub lines: Vec<InvoiceLine>,
+    pub total: f64,
+    pub top_ups: f64,
+    pub currency: String,
+}
+impl Model for Invoice {}
+
+#[derive(Deserialize, Serialize, Derivative, Clone, Debug)]
+#[derivative(PartialEq)]
+pub struct Creative {
+    #[serde(rename = "creative_id")]
+    #[derivative(PartialEq = "ignore")]
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+
+    #[serde(flatten)]
+    pub inner: CreateCreative,
+}
+impl Model for Creative {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CreateCreative {
+    pub ad_title: String,
+    pub ad_text: String,
+}
+impl Model for CreateCreative {}
+impl CreateCreative {
+    pub fn new(ad_title: impl Into<String>, ad_text: impl Into<String>) -> Self {
+        Self {
+            ad_title: ad_title.into(),
+            ad_text: ad_text.into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CreativeStats {
+    pub creative_id: Uuid,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+impl Model for CreativeStats {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct TargetingRule {
+    pub attribute: String,
+    pub op: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+impl Model for TargetingRule {}
+impl TargetingRule {
+    pub fn new(attribute: impl Into<String>, op: impl Into<String>) -> Self {
+        Self {
+            attribute: attribute.into(),
+            op: op.into(),
+            value: None,
+            values: None,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct AgeRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_from: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_to: Option<i64>,
+}
+impl Model for AgeRange {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct FrequencyCap {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_day: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifetime: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_days_between: Option<i64>,
+}
+impl Model for FrequencyCap {}
+impl OptionalModel for FrequencyCap {
+    fn empty() -> Self {
+        Self {
+            per_day: None,
+            lifetime: None,
+            min_days_between: None,
         }
     }
 }
@@ -205,16 +542,20 @@ impl OptionalModel for Targeting {
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
 pub struct Ad {
     #[serde(rename = "ad_id")]
-    id: Uuid,
+    pub id: Uuid,
 
     #[serde(rename = "ad_title")]
-    title: String,
+    pub title: String,
 
     #[serde(rename = "ad_text")]
-    text: String,
+    pub text: String,
+
+    pub advertiser_id: Uuid,
 
-    advertiser_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creative_id: Option<Uuid>,
 }
+impl Model for Ad {}
 impl From<Campaign> for Ad {
     fn from(campaign: Campaign) -> Self {
         Self {
@@ -222,10 +563,34 @@ impl From<Campaign> for Ad {
             title: campaign.inner.ad_title,
             text: campaign.inner.ad_text,
             advertiser_id: campaign.advertiser_id,
+            creative_id: None,
         }
     }
 }
 
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct AdDecision {
+    pub campaign_id: Uuid,
+    pub advertiser_id: Uuid,
+    pub eligible: bool,
+    pub predicates: AdPredicates,
+}
+impl Model for AdDecision {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct AdPredicates {
+    pub is_active: bool,
+    pub gender: bool,
+    pub age: bool,
+    pub location: bool,
+    pub client_lists: bool,
+    pub rules: bool,
+    pub active_window: bool,
+    pub impressions_limit: bool,
+    pub budget: bool,
+    pub frequency_cap: bool,
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
 pub struct ClickInfo {
     client_id: Uuid,
@@ -233,6 +598,15 @@ pub struct ClickInfo {
 impl Model for ClickInfo {}
 impl Buildable<ClickInfoBuilder, Self> for ClickInfo {}
 
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct ConversionInfo {
+    pub client_id: Uuid,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+}
+impl Model for ConversionInfo {}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
 pub struct Stats {
     pub impressions_count: i64,
@@ -240,7 +614,11 @@ pub struct Stats {
     pub conversion: f32,
     pub spent_impressions: f64,
     pub spent_clicks: f64,
+    pub spent_conversions: f64,
     pub spent_total: f64,
+    pub conversions_count: i64,
+    pub conversion_value: f64,
+    pub cost_per_acquisition: f64,
 }
 impl Model for Stats {}
 impl Buildable<StatsBuilder, Self> for Stats {}
@@ -252,11 +630,84 @@ impl OptionalModel for Stats {
             conversion: 0.0,
             spent_impressions: 0.0,
             spent_clicks: 0.0,
+            spent_conversions: 0.0,
             spent_total: 0.0,
+            conversions_count: 0,
+            conversion_value: 0.0,
+            cost_per_acquisition: 0.0,
         }
     }
 }
 
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct CampaignStats {
+    pub campaign_id: Uuid,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+impl Model for CampaignStats {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct PeriodStats {
+    pub start_date: i64,
+    pub end_date: i64,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub campaigns: Option<Vec<CampaignStats>>,
+}
+impl Model for PeriodStats {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct AudienceSegmentStats {
+    pub segment: String,
+
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+impl Model for AudienceSegmentStats {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct AudienceStats {
+    pub gender: Vec<AudienceSegmentStats>,
+    pub age: Vec<AudienceSegmentStats>,
+    pub location: Vec<AudienceSegmentStats>,
+}
+impl Model for AudienceStats {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct StatsDiff {
+    pub owner: String,
+    pub owner_id: Uuid,
+    pub period: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<i64>,
+
+    pub before: Stats,
+    pub after: Stats,
+}
+impl Model for StatsDiff {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct StatsOwnerRef {
+    pub owner: String,
+    pub owner_id: Uuid,
+}
+impl Model for StatsOwnerRef {}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct StatsRebuildReport {
+    pub dry_run: bool,
+    pub diffs: Vec<StatsDiff>,
+    pub skipped: Vec<StatsOwnerRef>,
+    pub unpriced: Vec<StatsOwnerRef>,
+}
+impl Model for StatsRebuildReport {}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Copy, Debug)]
 pub struct Time {
     pub current_date: i64,
@@ -0,0 +1,197 @@
+use actix_http::StatusCode;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Ad, Advertiser, Campaign, ClickInfo, Client, CreateCampaign, CreateCreative, Creative,
+        CreativeStats, Model, OptionalModel, Targeting,
+    },
+};
+use uuid::Uuid;
+
+mod common;
+
+#[actix_rt::test]
+async fn create_creative_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let creative = CreateCreative::new("Title", "Text");
+        let resp = api
+            .create_creative(advertiser.id, Uuid::now_v7(), creative)
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn creative_crud() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api
+            .create_campaign(advertiser.id, CreateCampaign::new())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let creative = Creative {
+            id: Uuid::now_v7(),
+            campaign_id: campaign.id,
+            inner: CreateCreative::new("Title A", "Text A"),
+        };
+        let resp = api
+            .create_creative(advertiser.id, campaign.id, creative.inner.clone())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let creative_resp = Creative::from_resp(resp).await;
+        assert_eq!(creative_resp, creative);
+
+        let resp = api
+            .get_creative_by_id(advertiser.id, campaign.id, creative_resp.id)
+            .await;
+        assert_json!(resp, StatusCode::OK, Creative, creative);
+
+        let resp = api.list_creatives(advertiser.id, campaign.id).await;
+        assert_json!(resp, StatusCode::OK, Vec<Creative>, vec![creative.clone()]);
+
+        let resp = api
+            .delete_creative(advertiser.id, campaign.id, creative_resp.id)
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api
+            .get_creative_by_id(advertiser.id, campaign.id, creative_resp.id)
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_ad_for_client_with_creatives() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let mut creatives = vec![];
+        for creative in [
+            CreateCreative::new("Title A", "Text A"),
+            CreateCreative::new("Title B", "Text B"),
+        ] {
+            let resp = api
+                .create_creative(advertiser.id, campaign.id, creative)
+                .await;
+            assert_status!(resp, StatusCode::CREATED);
+            creatives.push(Creative::from_resp(resp).await);
+        }
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let ad = Ad::from_resp(resp).await;
+        let creative = creatives
+            .iter()
+            .find(|creative| Some(creative.id) == ad.creative_id)
+            .expect("ad should be one of the campaign creatives");
+        assert_eq!(ad.id, campaign.id);
+        assert_eq!(ad.title, creative.inner.ad_title);
+        assert_eq!(ad.text, creative.inner.ad_text);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_campaign_creatives_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<CreativeStats>::from_resp(resp).await;
+        assert_eq!(stats.len(), 2);
+        for creative_stats in stats {
+            let shown = Some(creative_stats.creative_id) == ad.creative_id;
+            assert_eq!(creative_stats.stats.impressions_count, shown as i64);
+            assert_eq!(creative_stats.stats.clicks_count, shown as i64);
+        }
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn repeat_impression_keeps_creative() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        for creative in [
+            CreateCreative::new("Title A", "Text A"),
+            CreateCreative::new("Title B", "Text B"),
+        ] {
+            let resp = api
+                .create_creative(advertiser.id, campaign.id, creative)
+                .await;
+            assert_status!(resp, StatusCode::CREATED);
+        }
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let ad = Ad::from_resp(resp).await;
+
+        // Повторные показы не учитываются и не меняют креатив, которому засчитывается переход
+        for _ in 0..10 {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_campaign_creatives_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<CreativeStats>::from_resp(resp).await;
+        assert_eq!(stats.len(), 2);
+        for creative_stats in stats {
+            let shown = Some(creative_stats.creative_id) == ad.creative_id;
+            assert_eq!(creative_stats.stats.impressions_count, shown as i64);
+            assert_eq!(creative_stats.stats.clicks_count, shown as i64);
+        }
+    })
+    .await
+}
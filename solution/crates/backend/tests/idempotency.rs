@@ -0,0 +1,215 @@
+mod common;
+
+use actix_http::StatusCode;
+use actix_web::test::read_body;
+use backend::models::IDEMPOTENT_REPLAYED;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Advertiser, AuditEntry, Campaign, ClickInfo, Client, CreateCampaign, MLScore, Model,
+        OptionalModel, Stats, Targeting,
+    },
+};
+use rstest::rstest;
+use serde_json::json;
+use uuid::Uuid;
+
+#[actix_rt::test]
+async fn repeated_bulk_upsert() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+        let idempotent_api = api.with_idempotency_key("clients-import-1");
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = idempotent_api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+        assert!(resp.headers().get(IDEMPOTENT_REPLAYED).is_none());
+        let body = read_body(resp).await;
+
+        let resp = idempotent_api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+        assert_eq!(resp.headers().get(IDEMPOTENT_REPLAYED).unwrap(), "true");
+        assert_eq!(read_body(resp).await, body);
+
+        // Повторный запрос не выполняется, поэтому изменения записаны в журнал один раз
+        let resp = api.list_audit_entries("entity=CLIENT").await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(
+            Vec::<AuditEntry>::from_resp(resp).await.len(),
+            clients.len()
+        );
+
+        // Ключи различаются для разных методов
+        let resp = idempotent_api
+            .upsert_advertisers(vec![Advertiser::new()])
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        assert!(resp.headers().get(IDEMPOTENT_REPLAYED).is_none());
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn payload_mismatch() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api.with_idempotency_key("advertisers-import-1");
+
+        let resp = api.upsert_advertisers(vec![Advertiser::new()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.upsert_advertisers(vec![Advertiser::new()]).await;
+        assert_status!(resp, StatusCode::CONFLICT);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn failed_request_not_stored() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+        let idempotent_api = api.with_idempotency_key("ml-score-1");
+
+        let client = Client::new();
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let ml_score = MLScore::builder()
+            .with_client_id(client.id)
+            .with_advertiser_id(advertiser.id)
+            .build();
+        let resp = idempotent_api.upsert_ml_score(ml_score.clone()).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = idempotent_api.upsert_ml_score(ml_score.clone()).await;
+        assert_status!(resp, StatusCode::OK);
+        assert!(resp.headers().get(IDEMPOTENT_REPLAYED).is_none());
+
+        let resp = idempotent_api.upsert_ml_score(ml_score).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(resp.headers().get(IDEMPOTENT_REPLAYED).unwrap(), "true");
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn repeated_click() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let idempotent_api = api.with_idempotency_key("click-1");
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = idempotent_api
+            .record_ad_click(campaign.id, click_info.clone())
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+        assert!(resp.headers().get(IDEMPOTENT_REPLAYED).is_none());
+
+        let resp = idempotent_api
+            .record_ad_click(campaign.id, click_info.clone())
+            .await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+        assert_eq!(resp.headers().get(IDEMPOTENT_REPLAYED).unwrap(), "true");
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(Stats::from_resp(resp).await.clicks_count, 1);
+
+        // Тот же ключ с другим путём запроса
+        let resp = idempotent_api
+            .record_ad_click(Uuid::now_v7(), click_info)
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn abandoned_reservation_expires() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api.with_idempotency_key("clients-import-2");
+
+        // Запрос зарезервировал ключ и не сохранил ответ
+        test_env
+            .db
+            .surreal
+            .0
+            .query(
+                "RETURN fn::reserve_idempotency_key(type::thing('idempotency_key', $id), {}, 1s)",
+            )
+            .bind(json!({ "id": ["ADMIN", "POST /clients/bulk", "clients-import-2"] }))
+            .await
+            .unwrap()
+            .check()
+            .unwrap();
+
+        let resp = api.upsert_clients(vec![Client::new()]).await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        // После окончания аренды ключ можно использовать снова
+        actix_rt::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let resp = api.upsert_clients(vec![Client::new()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+        assert!(resp.headers().get(IDEMPOTENT_REPLAYED).is_none());
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn concurrent_requests() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api.with_idempotency_key("clients-import-3");
+        let clients = vec![Client::new()];
+
+        // Ключ достаётся одному запросу, второй получает его ответ или конфликт
+        let (first, second) = futures_util::join!(
+            api.upsert_clients(clients.clone()),
+            api.upsert_clients(clients.clone())
+        );
+        let mut statuses = [first.status(), second.status()];
+        statuses.sort();
+        assert!(
+            statuses == [StatusCode::CREATED, StatusCode::CREATED]
+                || statuses == [StatusCode::CREATED, StatusCode::CONFLICT],
+            "{statuses:?}"
+        );
+    })
+    .await
+}
+
+#[rstest]
+#[case::blank("   ".to_string())]
+#[case::too_long("k".repeat(256))]
+#[actix_rt::test]
+async fn invalid_idempotency_key(#[case] idempotency_key: String) {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api.with_idempotency_key(idempotency_key);
+
+        let resp = api.upsert_clients(vec![Client::new()]).await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
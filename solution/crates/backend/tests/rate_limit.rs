@@ -0,0 +1,133 @@
+mod common;
+
+use actix_http::StatusCode;
+use actix_web::{http::header::RETRY_AFTER, test::TestRequest};
+use backend::{
+    app_setup,
+    utils::rate_limit::{RateLimit, RateLimiter},
+};
+use common::{
+    api::Api,
+    environment::{with_test_environment, TestEnvironment},
+    models::{builders::Buildable, ApiKey, Client, CreateApiKey, Model},
+};
+use uuid::Uuid;
+
+async fn rate_limited_api(
+    test_env: &TestEnvironment,
+    ads: Option<RateLimit>,
+    write: Option<RateLimit>,
+    ip: Option<RateLimit>,
+) -> Api {
+    let config = app_setup(
+        test_env.db.surreal.clone(),
+        test_env.file_host.minio.clone(),
+    );
+    Api::build_rate_limited(config, RateLimiter::new(ads, write, ip)).await
+}
+
+#[actix_rt::test]
+async fn ads_rate_limit() {
+    with_test_environment(|test_env| async move {
+        let ads = RateLimit {
+            per_second: 1,
+            burst: 2,
+        };
+        let api = rate_limited_api(&test_env, Some(ads), None, None).await;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for _ in 0..2 {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_ne!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        }
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get(RETRY_AFTER).unwrap(), "1");
+
+        // Запись пути не влияет на ограничение
+        let req = TestRequest::get()
+            .uri(&format!("//ads?client_id={}", client.id))
+            .to_request();
+        let resp = api.call(req).await;
+        assert_status!(resp, StatusCode::TOO_MANY_REQUESTS);
+
+        // Непроверенные ключи не получают отдельных корзин
+        let resp = api
+            .with_api_key("unknown-key")
+            .get_ad_for_client(client.id)
+            .await;
+        assert_status!(resp, StatusCode::UNAUTHORIZED);
+
+        // Остальные методы чтения не ограничиваются
+        let resp = api.get_client_by_id(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        // Корзины ведутся отдельно для каждого ключа API
+        let resp = api
+            .issue_platform_api_key(CreateApiKey {
+                role: "CLIENT_APP".into(),
+            })
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let client_app_api = api.with_api_key(ApiKey::from_resp(resp).await.key.unwrap());
+
+        let resp = client_app_api.get_ad_for_client(client.id).await;
+        assert_ne!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn write_rate_limit() {
+    with_test_environment(|test_env| async move {
+        let write = RateLimit {
+            per_second: 1,
+            burst: 1,
+        };
+        let api = rate_limited_api(&test_env, None, Some(write), None).await;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.upsert_clients(vec![Client::new()]).await;
+        assert_status!(resp, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get(RETRY_AFTER).unwrap(), "1");
+
+        for _ in 0..3 {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_ne!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        }
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn ip_rate_limit() {
+    with_test_environment(|test_env| async move {
+        let ip = RateLimit {
+            per_second: 1,
+            burst: 2,
+        };
+        let api = rate_limited_api(&test_env, None, None, Some(ip)).await;
+
+        // Запросы с неверным ключом учитываются до его проверки
+        let unknown_key_api = api.with_api_key("unknown-key");
+        for _ in 0..2 {
+            let resp = unknown_key_api.get_client_by_id(Uuid::now_v7()).await;
+            assert_status!(resp, StatusCode::UNAUTHORIZED);
+        }
+
+        let resp = unknown_key_api.get_client_by_id(Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get(RETRY_AFTER).unwrap(), "1");
+
+        let resp = api.get_client_by_id(Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::TOO_MANY_REQUESTS);
+    })
+    .await
+}
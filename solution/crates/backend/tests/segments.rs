@@ -0,0 +1,295 @@
+use actix_http::StatusCode;
+use common::{
+    environment::with_test_environment,
+    models::{
+        builders::{Buildable, Builder},
+        Advertiser, Campaign, CampaignUpdate, Client, CreateCampaign, CreateSegment, Model,
+        OptionalModel, Segment, SegmentReach, SegmentUpdate, Targeting, Time,
+    },
+};
+use rstest::rstest;
+use serial_test::serial;
+use uuid::Uuid;
+
+mod common;
+
+fn location_targeting(location: &str) -> Targeting {
+    Targeting {
+        location: Some(location.into()),
+        ..Targeting::empty()
+    }
+}
+
+#[rstest]
+#[case::empty_name(CreateSegment::new("", Targeting::empty()))]
+#[case::targeting_locations_intersect(CreateSegment::new(
+    "Moscow without Moscow",
+    Targeting {
+        locations: Some(vec!["Moscow".into()]),
+        excluded_locations: Some(vec!["Moscow".into()]),
+        ..Targeting::empty()
+    }
+))]
+#[actix_rt::test]
+async fn create_segment_invalid(#[case] segment: CreateSegment) {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.create_segment(advertiser.id, segment).await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn create_segment_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let segment = CreateSegment::new("Moscow", location_targeting("Moscow"));
+        let resp = api.create_segment(Uuid::now_v7(), segment).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn segment_crud() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let mut segment = Segment {
+            id: Uuid::now_v7(),
+            advertiser_id: advertiser.id,
+            inner: CreateSegment::new("Moscow", location_targeting("Moscow")),
+        };
+        let resp = api
+            .create_segment(advertiser.id, segment.inner.clone())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let segment_resp = Segment::from_resp(resp).await;
+        assert_eq!(segment_resp, segment);
+        segment.id = segment_resp.id;
+
+        let resp = api.get_segment_by_id(advertiser.id, segment.id).await;
+        assert_json!(resp, StatusCode::OK, Segment, segment);
+
+        let resp = api.list_segments(advertiser.id).await;
+        assert_json!(resp, StatusCode::OK, Vec<Segment>, vec![segment.clone()]);
+
+        let segment_update = SegmentUpdate {
+            name: Some("Kazan".into()),
+            targeting: Some(location_targeting("Kazan")),
+        };
+        segment.inner = CreateSegment::new("Kazan", location_targeting("Kazan"));
+        let resp = api
+            .update_segment(advertiser.id, segment.id, segment_update)
+            .await;
+        assert_json!(resp, StatusCode::OK, Segment, segment);
+
+        let resp = api.get_segment_by_id(Uuid::now_v7(), segment.id).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        let resp = api.delete_segment(advertiser.id, segment.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_segment_by_id(advertiser.id, segment.id).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn get_segment_reach() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let clients = vec![
+            Client::builder()
+                .with_location("Moscow")
+                .with_age(20)
+                .build(),
+            Client::builder()
+                .with_location("Moscow")
+                .with_age(40)
+                .build(),
+            Client::builder()
+                .with_location("Kazan")
+                .with_age(20)
+                .build(),
+        ];
+        let resp = api.upsert_clients(clients).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let segment = CreateSegment::new(
+            "Young Moscow",
+            Targeting {
+                age_to: Some(30),
+                ..location_targeting("Moscow")
+            },
+        );
+        let resp = api.create_segment(advertiser.id, segment).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let segment = Segment::from_resp(resp).await;
+
+        let resp = api.get_segment_reach(advertiser.id, segment.id).await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            SegmentReach,
+            SegmentReach {
+                segment_id: segment.id,
+                clients_count: 1,
+            }
+        );
+
+        let resp = api.get_segment_reach(advertiser.id, Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn create_campaign_with_segment_invalid() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let other_advertiser = Advertiser::new();
+        let resp = api
+            .upsert_advertisers(vec![advertiser.clone(), other_advertiser.clone()])
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let segment = CreateSegment::new("Moscow", location_targeting("Moscow"));
+        let resp = api.create_segment(advertiser.id, segment).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let segment = Segment::from_resp(resp).await;
+
+        let campaign = CreateCampaign::builder()
+            .with_segment_id(segment.id)
+            .with_targeting(location_targeting("Kazan"))
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+
+        let campaign = CreateCampaign::builder()
+            .with_segment_id(segment.id)
+            .build();
+        let resp = api.create_campaign(other_advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        let campaign = CreateCampaign::builder()
+            .with_segment_id(Uuid::now_v7())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[actix_rt::test]
+#[serial(time)]
+async fn campaign_with_segment() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api.advance_day(Time::start()).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let moscow_client = Client::builder().with_location("Moscow").build();
+        let kazan_client = Client::builder().with_location("Kazan").build();
+        let resp = api
+            .upsert_clients(vec![moscow_client.clone(), kazan_client.clone()])
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let segment = CreateSegment::new("Moscow", location_targeting("Moscow"));
+        let resp = api.create_segment(advertiser.id, segment).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let segment = Segment::from_resp(resp).await;
+
+        let campaign = Campaign::builder()
+            .with_advertiser_id(advertiser.id)
+            .with_inner(
+                CreateCampaign::builder()
+                    .with_start_date(2)
+                    .with_segment_id(segment.id)
+                    .build(),
+            )
+            .build();
+        let resp = api
+            .create_campaign(advertiser.id, campaign.inner.clone())
+            .await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign_resp = Campaign::from_resp(resp).await;
+        assert_eq!(campaign_resp, campaign);
+
+        // Пока кампания не началась, таргетинг сегмента можно изменить
+        let segment_update = SegmentUpdate {
+            name: None,
+            targeting: Some(location_targeting("Kazan")),
+        };
+        let resp = api
+            .update_segment(advertiser.id, segment.id, segment_update)
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        let mut time = Time::start();
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(moscow_client.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+        let resp = api.get_ad_for_client(kazan_client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let segment_update = SegmentUpdate {
+            name: None,
+            targeting: Some(location_targeting("Moscow")),
+        };
+        let resp = api
+            .update_segment(advertiser.id, segment.id, segment_update)
+            .await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let segment_update = SegmentUpdate {
+            name: Some("Kazan".into()),
+            targeting: None,
+        };
+        let resp = api
+            .update_segment(advertiser.id, segment.id, segment_update)
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.delete_segment(advertiser.id, segment.id).await;
+        assert_status!(resp, StatusCode::CONFLICT);
+
+        let campaign_update = CampaignUpdate {
+            targeting: Some(location_targeting("Moscow")),
+            ..CampaignUpdate::empty()
+        };
+        let resp = api
+            .update_campaign(advertiser.id, campaign_resp.id, campaign_update)
+            .await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
@@ -1,9 +1,12 @@
-use actix_http::StatusCode;
+use actix_http::{header::CONTENT_TYPE, StatusCode};
+use actix_web::test::read_body;
 use common::{
     environment::with_test_environment,
     models::{
         builders::{Buildable, Builder},
-        Advertiser, Campaign, CreateCampaign, Model, OptionalModel, Stats, Time,
+        Advertiser, AudienceSegmentStats, AudienceStats, Campaign, ClickInfo, Client,
+        ConversionInfo, CreateCampaign, Model, OptionalModel, PeriodStats, Stats,
+        StatsRebuildReport, Targeting, Time,
     },
 };
 use rstest::rstest;
@@ -77,6 +80,49 @@ async fn get_campaign_stats_deletion() {
 }
 // TODO: написать больше тестов на получение статистики по рекламной кампании
 
+#[rstest]
+#[actix_rt::test]
+async fn get_campaign_stats_exact_money() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_cost_per_impression(0.1)
+            .with_cost_per_click(0.2)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let clients = vec![Client::new(), Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for client in &clients {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Stats::from_resp(resp).await;
+        assert_eq!(stats.spent_impressions, 0.3);
+        assert_eq!(stats.spent_clicks, 0.2);
+        assert_eq!(stats.spent_total, 0.5);
+    })
+    .await
+}
+
 #[rstest]
 #[actix_rt::test]
 async fn get_advertiser_campaigns_stats_non_existent() {
@@ -157,6 +203,142 @@ async fn get_campaign_daily_stats_empty() {
     })
     .await
 }
+#[rstest]
+#[serial(time)]
+#[actix_rt::test]
+async fn get_campaign_daily_stats_range() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(100)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        for _ in 0..10 {
+            let client = Client::new();
+            let resp = api.upsert_clients(vec![client.clone()]).await;
+            assert_status!(resp, StatusCode::CREATED);
+
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+
+            time.increase_day();
+            let resp = api.advance_day(time).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let resp = api
+            .get_campaign_daily_stats_with_query(campaign.id, "from=3&to=5")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<PeriodStats>::from_resp(resp).await;
+        assert_eq!(
+            stats
+                .iter()
+                .map(|stats| (
+                    stats.start_date,
+                    stats.end_date,
+                    stats.stats.impressions_count
+                ))
+                .collect::<Vec<_>>(),
+            vec![(3, 3, 1), (4, 4, 1), (5, 5, 1)]
+        );
+
+        let resp = api
+            .get_campaign_daily_stats_with_query(campaign.id, "granularity=week")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<PeriodStats>::from_resp(resp).await;
+        assert_eq!(
+            stats
+                .iter()
+                .map(|stats| (
+                    stats.start_date,
+                    stats.end_date,
+                    stats.stats.impressions_count
+                ))
+                .collect::<Vec<_>>(),
+            vec![(1, 7, 7), (8, 14, 3)]
+        );
+
+        let resp = api
+            .get_campaign_daily_stats_with_query(campaign.id, "from=5&to=3")
+            .await;
+        assert_status!(resp, StatusCode::BAD_REQUEST);
+    })
+    .await
+}
+
+#[rstest]
+#[serial(time)]
+#[actix_rt::test]
+async fn get_campaign_daily_stats_paused() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(100)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .set_campaign_state(advertiser.id, campaign.id, "pause")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api
+            .get_campaign_daily_stats_with_query(campaign.id, "from=1&to=1")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<PeriodStats>::from_resp(resp).await;
+        assert_eq!(
+            stats
+                .iter()
+                .map(|stats| (
+                    stats.start_date,
+                    stats.end_date,
+                    stats.stats.impressions_count
+                ))
+                .collect::<Vec<_>>(),
+            vec![(1, 1, 1)]
+        );
+    })
+    .await
+}
 // TODO: написать больше тестов на получение ежедневной статистики по рекламной кампании
 
 #[rstest]
@@ -199,4 +381,515 @@ async fn get_advertiser_daily_stats_empty() {
     })
     .await
 }
+#[rstest]
+#[serial(time)]
+#[actix_rt::test]
+async fn get_advertiser_daily_stats_by_campaign() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let mut campaigns = vec![];
+        for _ in 0..2 {
+            let campaign = CreateCampaign::builder()
+                .with_start_date(1)
+                .with_end_date(100)
+                .with_targeting(Targeting::empty())
+                .build();
+            let resp = api.create_campaign(advertiser.id, campaign).await;
+            assert_status!(resp, StatusCode::CREATED);
+            campaigns.push(Campaign::from_resp(resp).await);
+        }
+
+        for _ in 0..3 {
+            let client = Client::new();
+            let resp = api.upsert_clients(vec![client.clone()]).await;
+            assert_status!(resp, StatusCode::CREATED);
+
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+
+            time.increase_day();
+            let resp = api.advance_day(time).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let resp = api
+            .get_advertiser_daily_stats_with_query(
+                advertiser.id,
+                "granularity=month&by_campaign=true",
+            )
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<PeriodStats>::from_resp(resp).await;
+        assert_eq!(stats.len(), 1);
+        assert_eq!((stats[0].start_date, stats[0].end_date), (1, 30));
+        assert_eq!(stats[0].stats.impressions_count, 3);
+
+        let campaigns_stats = stats[0].campaigns.clone().unwrap();
+        assert_eq!(campaigns_stats.len(), campaigns.len());
+        assert!(campaigns_stats.iter().all(|stats| campaigns
+            .iter()
+            .any(|campaign| campaign.id == stats.campaign_id)));
+        assert_eq!(
+            campaigns_stats
+                .iter()
+                .map(|stats| stats.stats.impressions_count)
+                .sum::<i64>(),
+            3
+        );
+
+        let resp = api.get_advertiser_daily_stats(advertiser.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = Vec::<PeriodStats>::from_resp(resp).await;
+        assert_eq!(stats.len(), 4);
+        assert!(stats.iter().all(|stats| stats.campaigns.is_none()));
+    })
+    .await
+}
 // TODO: написать больше тестов на получение ежедневной статистики по рекламодателю
+
+#[rstest]
+#[actix_rt::test]
+async fn get_campaign_audience_stats_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api.get_campaign_audience_stats(Uuid::now_v7()).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[rstest]
+#[actix_rt::test]
+async fn get_audience_stats() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let clients = vec![
+            Client::builder()
+                .with_gender("MALE")
+                .with_age(20)
+                .with_location("Moscow")
+                .build(),
+            Client::builder()
+                .with_gender("FEMALE")
+                .with_age(30)
+                .with_location("Moscow")
+                .build(),
+            Client::builder()
+                .with_gender("FEMALE")
+                .with_age(70)
+                .with_location("Kazan")
+                .build(),
+        ];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for client in &clients {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api.get_campaign_audience_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        let stats = AudienceStats::from_resp(resp).await;
+
+        let counts = |segments: &[AudienceSegmentStats]| {
+            segments
+                .iter()
+                .map(|segment| {
+                    (
+                        segment.segment.clone(),
+                        segment.stats.impressions_count,
+                        segment.stats.clicks_count,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            counts(&stats.gender),
+            vec![("FEMALE".into(), 2, 0), ("MALE".into(), 1, 1)]
+        );
+        assert_eq!(
+            counts(&stats.age),
+            vec![
+                ("18-24".into(), 1, 1),
+                ("25-34".into(), 1, 0),
+                ("65+".into(), 1, 0)
+            ]
+        );
+        assert_eq!(
+            counts(&stats.location),
+            vec![("Kazan".into(), 1, 0), ("Moscow".into(), 2, 1)]
+        );
+        assert_eq!(stats.location[1].stats.conversion, 50.0);
+
+        let resp = api.get_advertiser_audience_stats(advertiser.id).await;
+        assert_json!(resp, StatusCode::OK, AudienceStats, stats);
+    })
+    .await
+}
+
+#[rstest]
+#[actix_rt::test]
+async fn export_advertiser_interactions_non_existent() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let resp = api
+            .export_advertiser_interactions(Uuid::now_v7(), "csv")
+            .await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+    })
+    .await
+}
+
+#[rstest]
+#[actix_rt::test]
+async fn export_advertiser_interactions_csv() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let clients = vec![Client::new(), Client::new()];
+        let resp = api.upsert_clients(clients.clone()).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        for client in &clients {
+            let resp = api.get_ad_for_client(client.id).await;
+            assert_status!(resp, StatusCode::OK);
+        }
+
+        let click_info = ClickInfo::builder().with_client_id(clients[0].id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let resp = api
+            .export_advertiser_interactions(advertiser.id, "csv")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), "text/csv");
+
+        let body = String::from_utf8(read_body(resp).await.to_vec()).unwrap();
+        let mut lines = body.lines();
+        let header = lines.next().unwrap();
+        assert!(header.starts_with("client_id,campaign_id,"));
+        assert!(header.ends_with(",spent_total,spent_estimated,conversion_value"));
+
+        let mut rows: Vec<&str> = lines.collect();
+        rows.sort();
+        let mut expected: Vec<String> = clients
+            .iter()
+            .map(|client| format!("{},{},", client.id, campaign.id))
+            .collect();
+        expected.sort();
+        assert_eq!(rows.len(), expected.len());
+        for (row, prefix) in rows.iter().zip(expected) {
+            assert!(row.starts_with(&prefix), "{row} should start with {prefix}");
+            assert!(row.ends_with(",false,"), "{row} should have stored prices");
+        }
+    })
+    .await
+}
+
+#[rstest]
+#[actix_rt::test]
+async fn export_campaign_daily_stats_parquet() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder().with_start_date(1).build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let resp = api
+            .export_campaign_daily_stats(campaign.id, "parquet")
+            .await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(CONTENT_TYPE).unwrap(),
+            "application/vnd.apache.parquet"
+        );
+
+        let body = read_body(resp).await;
+        assert!(body.starts_with(b"PAR1"));
+        assert!(body.ends_with(b"PAR1"));
+    })
+    .await
+}
+
+#[rstest]
+#[serial(time)]
+#[actix_rt::test]
+async fn rebuild_stats() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(100)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        time.increase_day();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.rebuild_stats("").await;
+        assert_json!(
+            resp,
+            StatusCode::OK,
+            StatsRebuildReport,
+            StatsRebuildReport {
+                dry_run: true,
+                diffs: vec![],
+                skipped: vec![],
+                unpriced: vec![]
+            }
+        );
+
+        test_env
+            .db
+            .surreal
+            .0
+            .query("UPDATE stats_campaign SET total.impressions_count = 100")
+            .await
+            .unwrap();
+
+        let resp = api.rebuild_stats("dry_run=true").await;
+        assert_status!(resp, StatusCode::OK);
+        let report = StatsRebuildReport::from_resp(resp).await;
+        assert_eq!(report.diffs.len(), 1);
+        let diff = &report.diffs[0];
+        assert_eq!(
+            (diff.owner.as_str(), diff.owner_id, diff.period.as_str()),
+            ("campaign", campaign.id, "total")
+        );
+        assert_eq!(
+            (diff.before.impressions_count, diff.after.impressions_count),
+            (100, 1)
+        );
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(Stats::from_resp(resp).await.impressions_count, 100);
+
+        let resp = api.rebuild_stats("dry_run=false").await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(StatsRebuildReport::from_resp(resp).await.diffs.len(), 1);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(Stats::from_resp(resp).await.impressions_count, 1);
+
+        let resp = api.rebuild_stats("").await;
+        assert_status!(resp, StatusCode::OK);
+        assert!(StatsRebuildReport::from_resp(resp).await.diffs.is_empty());
+    })
+    .await
+}
+
+#[actix_rt::test]
+async fn rebuild_stats_conversion_prices() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_targeting(Targeting::empty())
+            .with_cost_per_conversion(25.0)
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let click_info = ClickInfo::builder().with_client_id(client.id).build();
+        let resp = api.record_ad_click(campaign.id, click_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        let conversion_info = ConversionInfo {
+            client_id: client.id,
+            value: None,
+        };
+        let resp = api.record_ad_conversion(campaign.id, conversion_info).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        // Конверсия пересчитывается по цене, действовавшей в момент её записи
+        test_env
+            .db
+            .surreal
+            .0
+            .query("UPDATE campaign SET cost_per_conversion = 50dec")
+            .await
+            .unwrap();
+
+        let resp = api.rebuild_stats("").await;
+        assert_status!(resp, StatusCode::OK);
+        let report = StatsRebuildReport::from_resp(resp).await;
+        assert!(report.diffs.is_empty());
+        assert!(report.unpriced.is_empty());
+
+        // Для конверсий без сохранённой цены пересчитываются только количества
+        test_env
+            .db
+            .surreal
+            .0
+            .query("UPDATE interacted_with SET conversion_price = NONE")
+            .await
+            .unwrap();
+        test_env
+            .db
+            .surreal
+            .0
+            .query("UPDATE stats_campaign SET total.conversions_count = 5, total.spent_conversions = 1000dec")
+            .await
+            .unwrap();
+
+        let resp = api.rebuild_stats("dry_run=false").await;
+        assert_status!(resp, StatusCode::OK);
+        let report = StatsRebuildReport::from_resp(resp).await;
+        assert_eq!(
+            report
+                .unpriced
+                .iter()
+                .map(|unpriced| (unpriced.owner.as_str(), unpriced.owner_id))
+                .collect::<Vec<_>>(),
+            vec![("campaign", campaign.id), ("advertiser", advertiser.id)]
+        );
+        assert_eq!(report.diffs.len(), 1);
+        let diff = &report.diffs[0];
+        assert_eq!(
+            (diff.before.conversions_count, diff.after.conversions_count),
+            (5, 1)
+        );
+        assert_eq!(diff.before.spent_total, diff.after.spent_total);
+        assert!(report.skipped.is_empty());
+    })
+    .await
+}
+
+#[rstest]
+#[serial(time)]
+#[actix_rt::test]
+async fn rebuild_stats_purged_campaign() {
+    with_test_environment(|test_env| async move {
+        let api = test_env.api;
+
+        let mut time = Time::start();
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let advertiser = Advertiser::new();
+        let resp = api.upsert_advertisers(vec![advertiser.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let campaign = CreateCampaign::builder()
+            .with_start_date(1)
+            .with_end_date(100)
+            .with_targeting(Targeting::empty())
+            .build();
+        let resp = api.create_campaign(advertiser.id, campaign).await;
+        assert_status!(resp, StatusCode::CREATED);
+        let campaign = Campaign::from_resp(resp).await;
+
+        let client = Client::new();
+        let resp = api.upsert_clients(vec![client.clone()]).await;
+        assert_status!(resp, StatusCode::CREATED);
+
+        let resp = api.get_ad_for_client(client.id).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.delete_campaign(advertiser.id, campaign.id).await;
+        assert_status!(resp, StatusCode::NO_CONTENT);
+
+        time.current_date += 31;
+        let resp = api.advance_day(time).await;
+        assert_status!(resp, StatusCode::OK);
+
+        let resp = api.get_campaign_stats(campaign.id).await;
+        assert_status!(resp, StatusCode::NOT_FOUND);
+
+        // Вклад удалённой кампании остаётся в пересчитанной статистике рекламодателя
+        let resp = api.rebuild_stats("dry_run=false").await;
+        assert_status!(resp, StatusCode::OK);
+        assert!(StatsRebuildReport::from_resp(resp).await.diffs.is_empty());
+
+        let resp = api.get_advertiser_campaigns_stats(advertiser.id).await;
+        assert_status!(resp, StatusCode::OK);
+        assert_eq!(Stats::from_resp(resp).await.impressions_count, 1);
+    })
+    .await
+}
@@ -7,6 +7,7 @@ pub mod state;
 
 environment_variables! {
     BACKEND_ADDRESS: String = "localhost:8080",
+    BACKEND_API_KEY: String = "",
 }
 
 pub fn bot_setup() -> DependencyMap {
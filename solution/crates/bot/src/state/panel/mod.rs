@@ -67,6 +67,7 @@ async fn enter(
                         "http://{}/advertisers/bulk",
                         config::BACKEND_ADDRESS.as_str()
                     ))
+                    .bearer_auth(config::BACKEND_API_KEY.as_str())
                     .json(&json!([
                             {
                                 "advertiser_id": Uuid::now_v7(),
@@ -117,6 +118,7 @@ async fn enter(
                         "http://{}/advertisers/{text}",
                         config::BACKEND_ADDRESS.as_str()
                     ))
+                    .bearer_auth(config::BACKEND_API_KEY.as_str())
                     .send()
                     .await
                 {